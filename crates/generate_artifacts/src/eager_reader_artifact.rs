@@ -13,6 +13,10 @@ use isograph_schema::{RefetchedPathsMap, UserWrittenClientTypeInfo};
 use std::{borrow::Cow, collections::BTreeSet, path::PathBuf};
 
 use crate::{
+    format_parameter_type::{
+        collect_enum_scalars, collect_scalar_imports, format_enum_imports, format_scalar_imports,
+        FormattedTypeCache, TypeAliasBuilder, TypeFormatOptions,
+    },
     generate_artifacts::{
         generate_client_field_parameter_type, generate_client_field_updatable_data_type,
         generate_output_type, generate_parameters, print_javascript_type_declaration,
@@ -22,11 +26,12 @@ use crate::{
     },
     import_statements::{
         param_type_imports_to_import_param_statement, param_type_imports_to_import_statement,
-        reader_imports_to_import_statement,
+        reader_imports_to_import_statement, ScalarTypeImports,
     },
     reader_ast::generate_reader_ast,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn generate_eager_reader_artifacts<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     client_selectable: &ClientSelectable<TNetworkProtocol>,
@@ -35,6 +40,8 @@ pub(crate) fn generate_eager_reader_artifacts<TNetworkProtocol: NetworkProtocol>
     refetched_paths: &RefetchedPathsMap,
     file_extensions: GenerateFileExtensionsOption,
     has_updatable: bool,
+    type_format_options: &TypeFormatOptions<'_, TNetworkProtocol>,
+    type_cache: &mut FormattedTypeCache,
 ) -> Vec<ArtifactPathAndContent> {
     let ts_file_extension = file_extensions.ts();
     let user_written_component_variant = info.client_field_directive_set;
@@ -44,6 +51,7 @@ pub(crate) fn generate_eager_reader_artifacts<TNetworkProtocol: NetworkProtocol>
 
     let (reader_ast, reader_imports) = generate_reader_ast(
         schema,
+        type_format_options,
         client_selectable.selection_set_for_parent_query(),
         0,
         refetched_paths,
@@ -136,9 +144,41 @@ pub(crate) fn generate_eager_reader_artifacts<TNetworkProtocol: NetworkProtocol>
             .variable_definitions()
             .iter()
             .map(|x| &x.item);
-        let parameters_types = generate_parameters(schema, parameters);
-        let parameters_content =
-            format!("export type {reader_parameters_type} = {parameters_types}\n");
+        let mut type_aliases = type_format_options
+            .hoist_type_aliases
+            .then(TypeAliasBuilder::new);
+        let parameters_types = generate_parameters(
+            schema,
+            parameters,
+            type_format_options,
+            Some(type_cache),
+            type_aliases.as_mut(),
+        );
+        let mut scalar_imports = BTreeSet::new();
+        let mut enum_scalars = BTreeSet::new();
+        for parameter in client_selectable.variable_definitions() {
+            collect_scalar_imports(schema, &parameter.item.type_, &mut scalar_imports);
+            collect_enum_scalars(
+                schema,
+                type_format_options.scalar_literal_types,
+                &parameter.item.type_,
+                &mut enum_scalars,
+            );
+        }
+        let parameters_content = format!(
+            "{}{}{}export type {reader_parameters_type} = {parameters_types}\n",
+            format_scalar_imports(&scalar_imports),
+            format_enum_imports(
+                type_format_options.enum_emit_style,
+                &enum_scalars,
+                file_extensions
+            ),
+            type_aliases
+                .map(|type_aliases| {
+                    type_aliases.declarations(type_format_options.export_declarations)
+                })
+                .unwrap_or_default()
+        );
         path_and_contents.push(ArtifactPathAndContent {
             file_name: *RESOLVER_PARAMETERS_TYPE_FILE_NAME,
             file_content: parameters_content,
@@ -172,6 +212,7 @@ pub(crate) fn generate_eager_reader_condition_artifact<TNetworkProtocol: Network
 
     let (reader_ast, reader_imports) = generate_reader_ast(
         schema,
+        &TypeFormatOptions::default(),
         inline_fragment_reader_selections,
         0,
         refetch_paths,
@@ -221,6 +262,7 @@ pub(crate) fn generate_eager_reader_param_type_artifact<TNetworkProtocol: Networ
     schema: &Schema<TNetworkProtocol>,
     client_scalar_selectable: &ClientSelectable<TNetworkProtocol>,
     file_extensions: GenerateFileExtensionsOption,
+    type_format_options: &TypeFormatOptions<'_, TNetworkProtocol>,
 ) -> ArtifactPathAndContent {
     let ts_file_extension = file_extensions.ts();
     let parent_type = schema
@@ -231,22 +273,27 @@ pub(crate) fn generate_eager_reader_param_type_artifact<TNetworkProtocol: Networ
     let mut loadable_fields = BTreeSet::new();
     let mut link_fields = false;
     let mut updatable_fields = false;
+    let mut scalar_type_imports = ScalarTypeImports::default();
     let client_field_parameter_type = generate_client_field_parameter_type(
         schema,
+        type_format_options,
         client_scalar_selectable.selection_set_for_parent_query(),
         &mut param_type_imports,
         &mut loadable_fields,
         1,
         &mut link_fields,
+        &mut scalar_type_imports,
     );
     let updatable_data_type = generate_client_field_updatable_data_type(
         schema,
+        type_format_options,
         client_scalar_selectable.selection_set_for_parent_query(),
         &mut param_type_imports,
         &mut loadable_fields,
         1,
         &mut link_fields,
         &mut updatable_fields,
+        &mut scalar_type_imports,
     );
 
     let param_type_import_statement =
@@ -298,24 +345,36 @@ pub(crate) fn generate_eager_reader_param_type_artifact<TNetworkProtocol: Networ
     };
 
     let indent = "  ";
+    let readonly = type_format_options.readonly_modifier();
     let start_update_type = if updatable_fields {
         format!(
-            "{}readonly startUpdate: StartUpdate<{}>,\n",
+            "{}{readonly}startUpdate: StartUpdate<{}>,\n",
             indent, updatable_data_type
         )
     } else {
         "".to_string()
     };
 
+    let scalar_type_import_statements = format!(
+        "{}{}",
+        format_scalar_imports(&scalar_type_imports.scalar_imports),
+        format_enum_imports(
+            type_format_options.enum_emit_style,
+            &scalar_type_imports.enum_scalars,
+            file_extensions
+        )
+    );
+
     let param_type_content = format!(
         "{param_type_import_statement}\
+        {scalar_type_import_statements}\
         {link_field_imports}\
         {start_update_imports}\
         {loadable_field_imports}\
         {parameters_import}\n\
         export type {reader_param_type} = {{\n\
-        {indent}readonly data: {client_field_parameter_type},\n\
-        {indent}readonly parameters: {parameters_type},\n\
+        {indent}{readonly}data: {client_field_parameter_type},\n\
+        {indent}{readonly}parameters: {parameters_type},\n\
         {start_update_type}\
         }};\n",
     );
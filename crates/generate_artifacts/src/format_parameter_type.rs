@@ -1,119 +1,1360 @@
-use std::fmt::Debug;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt::{self, Debug, Write},
+};
 
-use common_lang_types::SelectableName;
+use common_lang_types::{
+    GraphQLScalarTypeName, Location, SelectableAlias, SelectableName, WithLocation,
+};
 use graphql_lang_types::{GraphQLNonNullTypeAnnotation, GraphQLTypeAnnotation};
+use intern::Lookup;
+use lazy_static::lazy_static;
 
+use isograph_config::{
+    CompilerConfigOptions, EnumEmitStyle, FieldNameTransform, GenerateFileExtensionsOption,
+    OptionalFieldStyle, PropertyOrder, ScalarLiteralTypes, ScalarMapTypes,
+    VoidInNullableParameterTypesOption,
+};
 use isograph_lang_types::{
-    DefinitionLocation, SelectionType, ServerEntityId, TypeAnnotation, UnionVariant,
+    quote_string_literal, DefinitionLocation, SelectionType, ServerEntityId, ServerObjectEntityId,
+    ServerScalarEntityId, TypeAnnotation, UnionVariant,
 };
-use isograph_schema::{NetworkProtocol, Schema, ServerSelectableId};
+use isograph_schema::{
+    JavascriptImport, NetworkProtocol, Schema, ServerFieldKind, ServerSelectableId,
+};
+
+use crate::{generate_artifacts::ENUMS, import_statements::ScalarTypeImports};
+
+/// Allows the rendering of specific object types (e.g. a branded type for money)
+/// to be customized. If render_object returns Some, the default rendering is skipped.
+pub trait TypeRenderOverride<TNetworkProtocol: NetworkProtocol> {
+    fn render_object(
+        &self,
+        schema: &Schema<TNetworkProtocol>,
+        object_entity_id: ServerObjectEntityId,
+    ) -> Option<String>;
+
+    /// Allows the name under which a server field (e.g. a snake_case field) is read
+    /// to be rewritten. This renames the property in the data that client fields
+    /// read, and the generated reader reads the field under the new name, as if it
+    /// were aliased. Fields that are aliased in the selection, parameters and the
+    /// fields of input objects (which are sent to the server) are not renamed.
+    fn transform_field_name(&self, _field_name: SelectableName) -> Option<String> {
+        None
+    }
+}
+
+/// Render every type in the default manner.
+pub struct NoTypeRenderOverride;
+
+impl<TNetworkProtocol: NetworkProtocol> TypeRenderOverride<TNetworkProtocol>
+    for NoTypeRenderOverride
+{
+    fn render_object(
+        &self,
+        _schema: &Schema<TNetworkProtocol>,
+        _object_entity_id: ServerObjectEntityId,
+    ) -> Option<String> {
+        None
+    }
+}
+
+/// The options that determine how types are emitted. These are built once per
+/// generation pass from the compiler config options, and are then passed by
+/// reference to everything that formats a type.
+pub struct TypeFormatOptions<'a, TNetworkProtocol: NetworkProtocol> {
+    pub type_render_override: &'a dyn TypeRenderOverride<TNetworkProtocol>,
+    pub scalar_literal_types: &'a ScalarLiteralTypes,
+    pub scalar_map_types: &'a ScalarMapTypes,
+    pub nullable_type_wrapper: Option<&'a str>,
+    pub enum_emit_style: EnumEmitStyle,
+    pub optional_field_style: Option<OptionalFieldStyle>,
+    pub input_object_property_order: PropertyOrder,
+    pub readonly_properties: bool,
+    pub link_specified_by_urls: bool,
+    pub void_in_nullable_parameter_types: VoidInNullableParameterTypesOption,
+    pub hoist_type_aliases: bool,
+    pub export_declarations: bool,
+    pub brand_id_types: bool,
+}
+
+/// Read snake_case server fields under a camelCase name, e.g. `first_name` as
+/// `firstName`. Leading underscores are kept, so `__typename` is not renamed.
+pub struct CamelCaseFieldNames;
+
+impl<TNetworkProtocol: NetworkProtocol> TypeRenderOverride<TNetworkProtocol>
+    for CamelCaseFieldNames
+{
+    fn render_object(
+        &self,
+        _schema: &Schema<TNetworkProtocol>,
+        _object_entity_id: ServerObjectEntityId,
+    ) -> Option<String> {
+        None
+    }
+
+    fn transform_field_name(&self, field_name: SelectableName) -> Option<String> {
+        let field_name = field_name.lookup();
+        let unprefixed = field_name.trim_start_matches('_');
+        let prefix = &field_name[..field_name.len() - unprefixed.len()];
+        let mut parts = unprefixed.split('_');
+        let first = format!("{prefix}{}", parts.next()?);
+        Some(parts.fold(first, |mut name, part| {
+            let mut chars = part.chars();
+            if let Some(first_char) = chars.next() {
+                name.push(first_char.to_ascii_uppercase());
+                name.push_str(chars.as_str());
+            }
+            name
+        }))
+    }
+}
+
+impl<'a, TNetworkProtocol: NetworkProtocol> TypeFormatOptions<'a, TNetworkProtocol> {
+    pub fn new(options: &'a CompilerConfigOptions) -> Self {
+        Self {
+            type_render_override: match options.field_name_transform {
+                FieldNameTransform::Unchanged => &NoTypeRenderOverride,
+                FieldNameTransform::CamelCase => &CamelCaseFieldNames,
+            },
+            scalar_literal_types: &options.scalar_literal_types,
+            scalar_map_types: &options.scalar_map_types,
+            nullable_type_wrapper: options.nullable_type_wrapper.as_deref(),
+            enum_emit_style: options.enum_emit_style,
+            optional_field_style: options.optional_field_style,
+            input_object_property_order: options.input_object_property_order,
+            readonly_properties: options.readonly_properties,
+            link_specified_by_urls: options.link_specified_by_urls,
+            void_in_nullable_parameter_types: options.void_in_nullable_parameter_types,
+            hoist_type_aliases: options.hoist_type_aliases,
+            export_declarations: options.export_declarations,
+            brand_id_types: options.brand_id_types,
+        }
+    }
+}
+
+impl<TNetworkProtocol: NetworkProtocol> TypeFormatOptions<'_, TNetworkProtocol> {
+    /// The modifier that precedes the name of each emitted property
+    pub(crate) fn readonly_modifier(&self) -> &'static str {
+        if self.readonly_properties {
+            "readonly "
+        } else {
+            ""
+        }
+    }
+
+    /// The style of nullable parameters and fields of input objects, which can be
+    /// omitted.
+    pub(crate) fn parameter_optional_field_style(&self) -> OptionalFieldStyle {
+        self.optional_field_style
+            .unwrap_or(OptionalFieldStyle::Both)
+    }
+
+    /// The style of nullable fields of reader outputs, which are always present.
+    pub(crate) fn reader_optional_field_style(&self) -> OptionalFieldStyle {
+        self.optional_field_style
+            .unwrap_or(OptionalFieldStyle::UnionNull)
+    }
+
+    /// The name under which a server field that is selected without an alias is
+    /// read, if transform_field_name renames it.
+    pub(crate) fn renamed_server_field(&self, field_name: SelectableName) -> Option<String> {
+        self.type_render_override
+            .transform_field_name(field_name)
+            .filter(|renamed| renamed != field_name.lookup())
+    }
+
+    /// The property name of a server field in the data that is read. Aliased fields
+    /// are read under their alias, and other fields under their (possibly renamed)
+    /// name.
+    pub(crate) fn reader_property_name(
+        &self,
+        field_name: SelectableName,
+        reader_alias: Option<WithLocation<SelectableAlias>>,
+    ) -> String {
+        match reader_alias {
+            Some(reader_alias) => reader_alias.item.to_string(),
+            None => match self.renamed_server_field(field_name) {
+                Some(renamed) => format_property_name(&renamed),
+                None => field_name.to_string(),
+            },
+        }
+    }
+}
+
+lazy_static! {
+    static ref NO_SCALAR_LITERAL_TYPES: ScalarLiteralTypes = ScalarLiteralTypes::default();
+    static ref NO_SCALAR_MAP_TYPES: ScalarMapTypes = ScalarMapTypes::default();
+}
+
+impl<TNetworkProtocol: NetworkProtocol> Default for TypeFormatOptions<'_, TNetworkProtocol> {
+    /// The options with which types are formatted if the config file does not
+    /// specify any formatting options.
+    fn default() -> Self {
+        Self {
+            type_render_override: &NoTypeRenderOverride,
+            scalar_literal_types: &NO_SCALAR_LITERAL_TYPES,
+            scalar_map_types: &NO_SCALAR_MAP_TYPES,
+            nullable_type_wrapper: None,
+            enum_emit_style: EnumEmitStyle::default(),
+            optional_field_style: None,
+            input_object_property_order: PropertyOrder::default(),
+            readonly_properties: true,
+            link_specified_by_urls: false,
+            void_in_nullable_parameter_types: VoidInNullableParameterTypesOption::default(),
+            hoist_type_aliases: false,
+            export_declarations: false,
+            brand_id_types: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceMapEntry {
+    /// The zero-indexed line of the formatted output on which the field is emitted
+    pub line: usize,
+    pub location: Location,
+}
+
+/// Accumulates, for every server field that is emitted while formatting a type,
+/// the line on which it is emitted and the location at which it was defined.
+#[derive(Debug, Default)]
+pub struct SourceMapBuilder {
+    entries: Vec<SourceMapEntry>,
+}
+
+impl SourceMapBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> &[SourceMapEntry] {
+        &self.entries
+    }
+
+    fn add_entry(&mut self, line: usize, location: Location) {
+        self.entries.push(SourceMapEntry { line, location });
+    }
+}
+
+const TYPESCRIPT_RESERVED_WORDS: &[&str] = &[
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "null",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+];
+
+/// Reserved words and names that are not valid identifiers are emitted
+/// as string literals, e.g. "class".
+fn format_property_name(name: &str) -> String {
+    let is_valid_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_valid_identifier && !TYPESCRIPT_RESERVED_WORDS.contains(&name) {
+        name.to_string()
+    } else {
+        quote_string_literal(name)
+    }
+}
+
+/// Objects that have already been formatted, keyed by the object and the
+/// indentation level at which they were formatted, so that objects that are
+/// referenced by many parameters are only formatted once. The formatted objects
+/// depend on the formatting options, so a cache should only be used for a single
+/// generation pass. Objects are not cached while a source map is being built.
+#[derive(Debug, Default)]
+pub struct FormattedTypeCache {
+    formatted_objects: HashMap<(ServerObjectEntityId, u8), String>,
+    hits: usize,
+}
+
+impl FormattedTypeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of times that a previously formatted object was reused
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+}
+
+#[derive(Debug)]
+struct TypeAlias {
+    index: usize,
+    formatted: String,
+}
+
+impl TypeAlias {
+    fn name(&self) -> String {
+        format!("Union{}", self.index)
+    }
+}
+
+/// Opt-in deduplication of parameter types that are unions of scalars (e.g.
+/// `string | null`). Every parameter type is first counted, keyed by its
+/// structure (so where the type was written in the source does not matter.) Each
+/// shape that occurs more than once is then declared once, as a `type` alias,
+/// and referenced by name.
+#[derive(Debug, Default)]
+pub struct TypeAliasBuilder {
+    occurrences: BTreeMap<TypeAnnotation<ServerEntityId>, usize>,
+    aliases: BTreeMap<TypeAnnotation<ServerEntityId>, TypeAlias>,
+}
 
-pub(crate) fn format_parameter_type<TNetworkProtocol: NetworkProtocol>(
+impl TypeAliasBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an occurrence of a type. This must be called for every type before
+    /// any type is referenced.
+    pub(crate) fn count(&mut self, type_: &GraphQLTypeAnnotation<ServerEntityId>) {
+        let type_annotation = TypeAnnotation::from_graphql_type_annotation(type_.clone());
+        if is_hoistable_union(&type_annotation) {
+            *self.occurrences.entry(type_annotation).or_default() += 1;
+        }
+    }
+
+    /// The name of the alias for type_, if its shape occurs more than once. The
+    /// alias is declared as whatever format_type returns the first time that its
+    /// shape is referenced.
+    pub(crate) fn reference(
+        &mut self,
+        type_: &GraphQLTypeAnnotation<ServerEntityId>,
+        format_type: impl FnOnce() -> String,
+    ) -> Option<String> {
+        let type_annotation = TypeAnnotation::from_graphql_type_annotation(type_.clone());
+        if self
+            .occurrences
+            .get(&type_annotation)
+            .copied()
+            .unwrap_or_default()
+            < 2
+        {
+            return None;
+        }
+        let index = self.aliases.len();
+        let alias = self
+            .aliases
+            .entry(type_annotation)
+            .or_insert_with(|| TypeAlias {
+                index,
+                formatted: format_type(),
+            });
+        Some(alias.name())
+    }
+
+    /// The `type` declarations of every alias that has been referenced, in the
+    /// order in which they were first referenced. If export_declarations is true,
+    /// the declarations are exported.
+    pub fn declarations(&self, export_declarations: bool) -> String {
+        let export = if export_declarations { "export " } else { "" };
+        let mut aliases = self.aliases.values().collect::<Vec<_>>();
+        aliases.sort_by_key(|alias| alias.index);
+        aliases
+            .into_iter()
+            .map(|alias| format!("{export}type {} = {};\n", alias.name(), alias.formatted))
+            .collect()
+    }
+}
+
+/// Only unions (i.e. nullable types) that consist solely of scalars are hoisted.
+/// These are formatted on a single line, unlike input objects.
+fn is_hoistable_union(type_annotation: &TypeAnnotation<ServerEntityId>) -> bool {
+    fn contains_only_scalars(type_annotation: &TypeAnnotation<ServerEntityId>) -> bool {
+        match type_annotation {
+            TypeAnnotation::Scalar(entity_id) => matches!(entity_id, ServerEntityId::Scalar(_)),
+            TypeAnnotation::Union(union) => union.variants.iter().all(|variant| match variant {
+                UnionVariant::Scalar(entity_id) => matches!(entity_id, ServerEntityId::Scalar(_)),
+                UnionVariant::Plural(type_annotation) => contains_only_scalars(type_annotation),
+            }),
+            TypeAnnotation::Plural(type_annotation) => contains_only_scalars(type_annotation),
+        }
+    }
+
+    match type_annotation {
+        TypeAnnotation::Union(union) => {
+            (union.variants.len() > 1 || union.nullable) && contains_only_scalars(type_annotation)
+        }
+        TypeAnnotation::Scalar(_) | TypeAnnotation::Plural(_) => false,
+    }
+}
+
+/// Tracks the line that is currently being written, so that source map entries
+/// can be recorded while the formatted type is streamed to the underlying writer.
+struct TypeWriter<'a> {
+    inner: &'a mut dyn Write,
+    line: usize,
+    type_cache: Option<&'a mut FormattedTypeCache>,
+}
+
+impl<'a> TypeWriter<'a> {
+    fn new(inner: &'a mut dyn Write) -> Self {
+        Self {
+            inner,
+            line: 0,
+            type_cache: None,
+        }
+    }
+}
+
+impl Write for TypeWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.line += s.matches('\n').count();
+        self.inner.write_str(s)
+    }
+}
+
+/// e.g. `T | null`, or `Maybe<T>` if the nullable type wrapper is `Maybe`
+fn write_nullable_type(
+    out: &mut TypeWriter<'_>,
+    nullable_type_wrapper: Option<&str>,
+    write_type: impl FnOnce(&mut TypeWriter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    match nullable_type_wrapper {
+        Some(nullable_type_wrapper) => {
+            write!(out, "{nullable_type_wrapper}<")?;
+            write_type(out)?;
+            out.write_str(">")
+        }
+        None => {
+            write_type(out)?;
+            out.write_str(" | null")
+        }
+    }
+}
+
+/// Convenience wrapper around write_parameter_type, which collects the formatted
+/// type into a String.
+pub fn format_parameter_type<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     type_: GraphQLTypeAnnotation<ServerEntityId>,
     indentation_level: u8,
+    source_map: Option<&mut SourceMapBuilder>,
 ) -> String {
+    let mut s = String::new();
+    write_parameter_type(
+        &mut s,
+        schema,
+        options,
+        type_,
+        indentation_level,
+        source_map,
+        None,
+    )
+    .expect("Expected writing to a String to succeed");
+    s
+}
+
+/// Like format_parameter_type, but the formatted type is itself indented by
+/// indentation_level, so that the opening and closing braces of an object line up
+/// when the type is embedded in an indented block (rather than after e.g. a field
+/// name, which format_parameter_type assumes.)
+pub fn format_indented_parameter_type<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
+    type_: GraphQLTypeAnnotation<ServerEntityId>,
+    indentation_level: u8,
+    source_map: Option<&mut SourceMapBuilder>,
+) -> String {
+    let mut s = "  ".repeat(indentation_level as usize);
+    write_parameter_type(
+        &mut s,
+        schema,
+        options,
+        type_,
+        indentation_level,
+        source_map,
+        None,
+    )
+    .expect("Expected writing to a String to succeed");
+    s
+}
+
+/// Write the type of a parameter to out, without building intermediate Strings.
+/// Source map entries are relative to the first line that is written.
+pub fn write_parameter_type<TNetworkProtocol: NetworkProtocol>(
+    out: &mut impl Write,
+    schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
+    type_: GraphQLTypeAnnotation<ServerEntityId>,
+    indentation_level: u8,
+    source_map: Option<&mut SourceMapBuilder>,
+    type_cache: Option<&mut FormattedTypeCache>,
+) -> fmt::Result {
+    let out = &mut TypeWriter::new(out);
+    out.type_cache = type_cache;
     match type_ {
         GraphQLTypeAnnotation::Named(named_inner_type) => {
-            format!(
-                "{} | null | void",
-                format_server_field_type(schema, named_inner_type.item, indentation_level)
-            )
+            if let Some(nullable_type_wrapper) = options.nullable_type_wrapper {
+                write!(out, "{nullable_type_wrapper}<")?;
+            }
+            write_server_field_type(
+                out,
+                schema,
+                options,
+                named_inner_type.item,
+                None,
+                indentation_level,
+                source_map,
+            )?;
+            let void_in_nullable_parameter_types = options.void_in_nullable_parameter_types;
+            match options.nullable_type_wrapper {
+                Some(_) => write!(out, ">{}", void_in_nullable_parameter_types.void_suffix()),
+                None => out.write_str(void_in_nullable_parameter_types.nullable_suffix()),
+            }
         }
         GraphQLTypeAnnotation::List(list) => {
-            format!(
-                "ReadonlyArray<{}> | null",
-                format_server_field_type(schema, *list.inner(), indentation_level)
-            )
+            write_nullable_type(out, options.nullable_type_wrapper, |out| {
+                out.write_str("ReadonlyArray<")?;
+                write_list_item_type(out, schema, options, list.0, indentation_level, source_map)?;
+                out.write_str(">")
+            })
         }
         GraphQLTypeAnnotation::NonNull(non_null) => match *non_null {
-            GraphQLNonNullTypeAnnotation::Named(named_inner_type) => {
-                format_server_field_type(schema, named_inner_type.item, indentation_level)
-            }
+            GraphQLNonNullTypeAnnotation::Named(named_inner_type) => write_server_field_type(
+                out,
+                schema,
+                options,
+                named_inner_type.item,
+                None,
+                indentation_level,
+                source_map,
+            ),
             GraphQLNonNullTypeAnnotation::List(list) => {
-                format!(
-                    "ReadonlyArray<{}>",
-                    format_server_field_type(schema, *list.inner(), indentation_level)
+                out.write_str("ReadonlyArray<")?;
+                write_list_item_type(out, schema, options, list.0, indentation_level, source_map)?;
+                out.write_str(">")
+            }
+        },
+    }
+}
+
+/// Unlike top-level parameters, list items cannot be omitted, so nullable
+/// items are never void.
+fn write_list_item_type<TNetworkProtocol: NetworkProtocol>(
+    out: &mut TypeWriter<'_>,
+    schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
+    type_: GraphQLTypeAnnotation<ServerEntityId>,
+    indentation_level: u8,
+    source_map: Option<&mut SourceMapBuilder>,
+) -> fmt::Result {
+    match type_ {
+        GraphQLTypeAnnotation::Named(named_inner_type) => {
+            write_nullable_type(out, options.nullable_type_wrapper, |out| {
+                write_server_field_type(
+                    out,
+                    schema,
+                    options,
+                    named_inner_type.item,
+                    None,
+                    indentation_level,
+                    source_map,
                 )
+            })
+        }
+        GraphQLTypeAnnotation::List(list) => {
+            write_nullable_type(out, options.nullable_type_wrapper, |out| {
+                out.write_str("ReadonlyArray<")?;
+                write_list_item_type(out, schema, options, list.0, indentation_level, source_map)?;
+                out.write_str(">")
+            })
+        }
+        GraphQLTypeAnnotation::NonNull(non_null) => match *non_null {
+            GraphQLNonNullTypeAnnotation::Named(named_inner_type) => write_server_field_type(
+                out,
+                schema,
+                options,
+                named_inner_type.item,
+                None,
+                indentation_level,
+                source_map,
+            ),
+            GraphQLNonNullTypeAnnotation::List(list) => {
+                out.write_str("ReadonlyArray<")?;
+                write_list_item_type(out, schema, options, list.0, indentation_level, source_map)?;
+                out.write_str(">")
             }
         },
     }
 }
 
-fn format_server_field_type<TNetworkProtocol: NetworkProtocol>(
+/// parent_object_entity_id is the object that the field whose type is being
+/// written belongs to, if the field is part of the data that is read. It is None
+/// for parameters (including the fields of input objects.)
+fn write_server_field_type<TNetworkProtocol: NetworkProtocol>(
+    out: &mut TypeWriter<'_>,
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     field: ServerEntityId,
+    parent_object_entity_id: Option<ServerObjectEntityId>,
     indentation_level: u8,
-) -> String {
+    source_map: Option<&mut SourceMapBuilder>,
+) -> fmt::Result {
     match field {
+        ServerEntityId::Object(object_entity_id) => match out.type_cache.take() {
+            Some(type_cache) if source_map.is_none() => write_cached_server_object_type(
+                out,
+                type_cache,
+                schema,
+                options,
+                object_entity_id,
+                indentation_level,
+            ),
+            type_cache => {
+                out.type_cache = type_cache;
+                write_server_object_type(
+                    out,
+                    schema,
+                    options,
+                    object_entity_id,
+                    indentation_level,
+                    source_map,
+                )
+            }
+        },
+        ServerEntityId::Scalar(scalar_entity_id) => {
+            let scalar = schema
+                .server_entity_data
+                .server_scalar_entity(scalar_entity_id);
+            if options.link_specified_by_urls {
+                if let Some(url) = scalar.specified_by_url {
+                    write!(
+                        out,
+                        "/** @see {} */ ",
+                        escape_doc_comment_text(url.lookup())
+                    )?;
+                }
+            }
+            match options.scalar_literal_types.get(&scalar.name.item) {
+                Some(literals) => match options.enum_emit_style {
+                    EnumEmitStyle::StringLiteralUnion => {
+                        for (index, literal) in literals.iter().enumerate() {
+                            if index != 0 {
+                                out.write_str(" | ")?;
+                            }
+                            out.write_str(&quote_string_literal(literal))?;
+                        }
+                        Ok(())
+                    }
+                    // The declaration (see format_enum_declarations) is a type
+                    // of the same name
+                    EnumEmitStyle::ConstEnum | EnumEmitStyle::ConstObject => {
+                        write!(out, "{}", scalar.name.item)
+                    }
+                },
+                None => match options.scalar_map_types.get(&scalar.name.item) {
+                    Some(value_type) => write!(out, "Record<string, {value_type}>"),
+                    None => out.write_str(&TNetworkProtocol::format_scalar_type(scalar)),
+                },
+            }?;
+            // The id fields of each object are branded with the name of the object,
+            // so that the ids of different types are not interchangeable. Parameters
+            // are not branded, so that any id that was read can be passed to them.
+            if let Some(parent_object_entity_id) = parent_object_entity_id {
+                if options.brand_id_types
+                    && scalar_entity_id == schema.server_entity_data.id_type_id
+                {
+                    let parent_object = schema
+                        .server_entity_data
+                        .server_object_entity(parent_object_entity_id);
+                    write!(out, " & {{ readonly __brand: '{}ID' }}", parent_object.name)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// The type of a scalar field of a reader output, which is formatted in the same
+/// manner as a scalar in a parameter type (except that id fields of
+/// parent_object_entity_id may be branded.) The scalar is recorded in
+/// scalar_type_imports, if its type must be imported.
+pub(crate) fn format_reader_scalar_type<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
+    scalar_entity_id: ServerScalarEntityId,
+    parent_object_entity_id: ServerObjectEntityId,
+    scalar_type_imports: &mut ScalarTypeImports,
+) -> String {
+    let scalar = schema
+        .server_entity_data
+        .server_scalar_entity(scalar_entity_id);
+    if let Some(javascript_import) = scalar.javascript_import {
+        scalar_type_imports.scalar_imports.insert(javascript_import);
+    }
+    if options.scalar_literal_types.contains_key(&scalar.name.item) {
+        scalar_type_imports.enum_scalars.insert(scalar.name.item);
+    }
+
+    let mut s = String::new();
+    write_server_field_type(
+        &mut TypeWriter::new(&mut s),
+        schema,
+        options,
+        ServerEntityId::Scalar(scalar_entity_id),
+        Some(parent_object_entity_id),
+        0,
+        None,
+    )
+    .expect("Expected writing to a String to succeed");
+    s
+}
+
+/// Write the object from the type cache, formatting (and caching) it first if it
+/// has not yet been formatted at this indentation level.
+fn write_cached_server_object_type<'a, TNetworkProtocol: NetworkProtocol>(
+    out: &mut TypeWriter<'a>,
+    type_cache: &'a mut FormattedTypeCache,
+    schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
+    object_entity_id: ServerObjectEntityId,
+    indentation_level: u8,
+) -> fmt::Result {
+    let key = (object_entity_id, indentation_level);
+    if type_cache.formatted_objects.contains_key(&key) {
+        type_cache.hits += 1;
+    } else {
+        let mut formatted = String::new();
+        let mut formatted_out = TypeWriter::new(&mut formatted);
+        // Objects that are nested within this object are cached as well
+        formatted_out.type_cache = Some(&mut *type_cache);
+        write_server_object_type(
+            &mut formatted_out,
+            schema,
+            options,
+            object_entity_id,
+            indentation_level,
+            None,
+        )?;
+        type_cache.formatted_objects.insert(key, formatted);
+    }
+
+    let result = out.write_str(&type_cache.formatted_objects[&key]);
+    out.type_cache = Some(type_cache);
+    result
+}
+
+fn write_server_object_type<TNetworkProtocol: NetworkProtocol>(
+    out: &mut TypeWriter<'_>,
+    schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
+    object_entity_id: ServerObjectEntityId,
+    indentation_level: u8,
+    mut source_map: Option<&mut SourceMapBuilder>,
+) -> fmt::Result {
+    if let Some(rendered_object) = options
+        .type_render_override
+        .render_object(schema, object_entity_id)
+    {
+        return out.write_str(&rendered_object);
+    }
+
+    if TNetworkProtocol::is_one_of(
+        schema
+            .server_entity_data
+            .server_object_entity(object_entity_id),
+    ) {
+        return write_one_of_input_object(
+            out,
+            schema,
+            options,
+            object_entity_id,
+            indentation_level,
+            source_map,
+        );
+    }
+
+    // TODO this is bad; we should never create a type containing all of the fields
+    // on a given object. This is currently used for input objects, and we should
+    // consider how to do this is a not obviously broken manner.
+    out.write_str("{\n")?;
+    for (name, server_selectable_id) in ordered_server_selectables(
+        schema,
+        object_entity_id,
+        options.input_object_property_order,
+    ) {
+        write_field_definition(
+            out,
+            schema,
+            options,
+            name,
+            server_selectable_id,
+            indentation_level + 1,
+            source_map.as_deref_mut(),
+        )?;
+    }
+    write!(out, "{}}}", "  ".repeat(indentation_level as usize))
+}
+
+/// A stable entry point for custom generators, which need the type of an arbitrary
+/// entity as it is emitted in artifacts, with the default formatting options.
+pub trait JavascriptTypeFor {
+    fn javascript_type_for(&self, entity: ServerEntityId, indentation_level: u8) -> String;
+}
+
+impl<TNetworkProtocol: NetworkProtocol> JavascriptTypeFor for Schema<TNetworkProtocol> {
+    fn javascript_type_for(&self, entity: ServerEntityId, indentation_level: u8) -> String {
+        let mut s = String::new();
+        write_server_field_type(
+            &mut TypeWriter::new(&mut s),
+            self,
+            &TypeFormatOptions::default(),
+            entity,
+            None,
+            indentation_level,
+            None,
+        )
+        .expect("Expected writing to a String to succeed");
+        s
+    }
+}
+
+/// Exactly one field of a @oneOf input object must be provided, so it is emitted
+/// as a union of objects with a single, non-null field, e.g. `{ a: A } | { b: B }`.
+fn write_one_of_input_object<TNetworkProtocol: NetworkProtocol>(
+    out: &mut TypeWriter<'_>,
+    schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
+    object_entity_id: ServerObjectEntityId,
+    indentation_level: u8,
+    mut source_map: Option<&mut SourceMapBuilder>,
+) -> fmt::Result {
+    let indent = "  ".repeat(indentation_level as usize);
+    let readonly = options.readonly_modifier();
+    for (index, (name, server_selectable_id)) in ordered_server_selectables(
+        schema,
+        object_entity_id,
+        options.input_object_property_order,
+    )
+    .into_iter()
+    .enumerate()
+    {
+        if index != 0 {
+            out.write_str(" | ")?;
+        }
+        out.write_str("{\n")?;
+
+        let (location, type_annotation) = match schema.server_selectable(server_selectable_id) {
+            SelectionType::Scalar(scalar_selectable) => (
+                scalar_selectable.name.location,
+                scalar_selectable
+                    .target_scalar_entity
+                    .clone()
+                    .map(&mut SelectionType::Scalar),
+            ),
+            SelectionType::Object(object_selectable) => (
+                object_selectable.name.location,
+                object_selectable
+                    .target_object_entity
+                    .clone()
+                    .map(&mut SelectionType::Object),
+            ),
+        };
+        let name = format_property_name(name.lookup());
+        if let Some(source_map) = source_map.as_deref_mut() {
+            source_map.add_entry(out.line, location);
+        }
+        write!(out, "{indent}  {readonly}{name}: ")?;
+        write_type_annotation(
+            out,
+            schema,
+            options,
+            &non_null_type_annotation(type_annotation),
+            indentation_level + 2,
+            source_map.as_deref_mut(),
+            false,
+        )?;
+        write!(out, ",\n{indent}}}")?;
+    }
+    Ok(())
+}
+
+/// The server selectables of an object, in the order in which they are emitted.
+fn ordered_server_selectables<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    object_entity_id: ServerObjectEntityId,
+    property_order: PropertyOrder,
+) -> Vec<(&SelectableName, ServerSelectableId)> {
+    // Selectables are stored by name, so they are already in alphabetical order.
+    let mut selectables = schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&object_entity_id)
+        .expect("Expected object_entity_id to exist in server_object_entity_available_selectables")
+        .selectables
+        .iter()
+        .filter_map(
+            |(name, field_definition_location)| match field_definition_location {
+                DefinitionLocation::Server(s) => Some((name, *s)),
+                DefinitionLocation::Client(_) => None,
+            },
+        )
+        .collect::<Vec<_>>();
+
+    match property_order {
+        PropertyOrder::Alphabetical => {}
+        PropertyOrder::SchemaOrder => {
+            // Fields can be defined in several files (e.g. in schema extensions), so
+            // they are sorted by file, and then by their position within that file.
+            // Generated selectables have no location, and are sorted after the others
+            // (and remain in alphabetical order, since the sort is stable.)
+            selectables.sort_by_key(|(_, server_selectable_id)| {
+                let location = match schema.server_selectable(*server_selectable_id) {
+                    SelectionType::Scalar(scalar_selectable) => scalar_selectable.name.location,
+                    SelectionType::Object(object_selectable) => object_selectable.name.location,
+                };
+                match location {
+                    Location::Embedded(embedded_location) => (
+                        false,
+                        embedded_location
+                            .text_source
+                            .relative_path_to_source_file
+                            .lookup(),
+                        embedded_location.span.start,
+                    ),
+                    Location::Generated => (true, "", 0),
+                }
+            });
+        }
+    }
+    selectables
+}
+
+/// Whether a field is marked optional (with `?`), and the type annotation with
+/// which it is emitted, according to the optional field style.
+pub(crate) fn apply_optional_field_style<T: Ord + Debug>(
+    optional_field_style: OptionalFieldStyle,
+    type_annotation: TypeAnnotation<T>,
+) -> (bool, TypeAnnotation<T>) {
+    let is_optional = is_nullable(&type_annotation);
+    match optional_field_style {
+        OptionalFieldStyle::QuestionMark => {
+            (is_optional, non_null_type_annotation(type_annotation))
+        }
+        OptionalFieldStyle::UnionNull => (false, type_annotation),
+        OptionalFieldStyle::Both => (is_optional, type_annotation),
+    }
+}
+
+/// Like apply_optional_field_style, but for the type of a parameter.
+pub(crate) fn apply_optional_parameter_style<T>(
+    optional_field_style: OptionalFieldStyle,
+    type_: GraphQLTypeAnnotation<T>,
+) -> (bool, GraphQLTypeAnnotation<T>) {
+    let is_optional = type_.is_nullable();
+    match optional_field_style {
+        OptionalFieldStyle::QuestionMark => (is_optional, non_null_graphql_type_annotation(type_)),
+        OptionalFieldStyle::UnionNull => (false, type_),
+        OptionalFieldStyle::Both => (is_optional, type_),
+    }
+}
+
+fn non_null_graphql_type_annotation<T>(
+    type_: GraphQLTypeAnnotation<T>,
+) -> GraphQLTypeAnnotation<T> {
+    match type_ {
+        GraphQLTypeAnnotation::Named(named) => {
+            GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::Named(named)))
+        }
+        GraphQLTypeAnnotation::List(list) => {
+            GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::List(*list)))
+        }
+        non_null @ GraphQLTypeAnnotation::NonNull(_) => non_null,
+    }
+}
+
+/// The field of a @oneOf input object that is provided cannot be null, and
+/// fields marked optional with OptionalFieldStyle::QuestionMark are not
+/// additionally unioned with null.
+fn non_null_type_annotation<T: Ord>(type_annotation: TypeAnnotation<T>) -> TypeAnnotation<T> {
+    match type_annotation {
+        TypeAnnotation::Union(mut union) if union.nullable => {
+            if union.variants.len() == 1 {
+                match union
+                    .variants
+                    .pop_first()
+                    .expect("Expected variant to exist")
+                {
+                    UnionVariant::Scalar(inner) => TypeAnnotation::Scalar(inner),
+                    UnionVariant::Plural(inner) => TypeAnnotation::Plural(Box::new(inner)),
+                }
+            } else {
+                union.nullable = false;
+                TypeAnnotation::Union(union)
+            }
+        }
+        type_annotation => type_annotation,
+    }
+}
+
+/// Collect the imports of the scalars that are referenced by a type (directly, or
+/// through the fields of input objects), so that the generated file can import them.
+pub(crate) fn collect_scalar_imports<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    type_: &GraphQLTypeAnnotation<ServerEntityId>,
+    scalar_imports: &mut BTreeSet<JavascriptImport>,
+) {
+    visit_scalar_entities(
+        schema,
+        *type_.inner(),
+        &mut HashSet::new(),
+        &mut |scalar_entity_id| {
+            if let Some(javascript_import) = schema
+                .server_entity_data
+                .server_scalar_entity(scalar_entity_id)
+                .javascript_import
+            {
+                scalar_imports.insert(javascript_import);
+            }
+        },
+    );
+}
+
+/// Calls visit_scalar with each scalar that is referenced by an entity (directly,
+/// or through the fields of input objects.)
+fn visit_scalar_entities<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    entity_id: ServerEntityId,
+    visited_objects: &mut HashSet<ServerObjectEntityId>,
+    visit_scalar: &mut impl FnMut(ServerScalarEntityId),
+) {
+    match entity_id {
+        ServerEntityId::Scalar(scalar_entity_id) => visit_scalar(scalar_entity_id),
         ServerEntityId::Object(object_entity_id) => {
-            // TODO this is bad; we should never create a type containing all of the fields
-            // on a given object. This is currently used for input objects, and we should
-            // consider how to do this is a not obviously broken manner.
-            let mut s = "{\n".to_string();
-            for (name, server_selectable_id) in schema
+            // Input objects can be recursive
+            if !visited_objects.insert(object_entity_id) {
+                return;
+            }
+            for server_selectable_id in schema
                 .server_entity_data
                 .server_object_entity_extra_info
                 .get(&object_entity_id)
                 .expect("Expected object_entity_id to exist in server_object_entity_available_selectables")
                 .selectables
-                .iter()
-                .filter_map(
-                    |(name, field_definition_location)| match field_definition_location {
-                        DefinitionLocation::Server(s) => Some((name, *s)),
-                        DefinitionLocation::Client(_) => None,
-                    },
-                )
+                .values()
+                .filter_map(|selectable_id| selectable_id.as_server())
             {
-                let field_type = format_field_definition(
-                    schema,
-                    name,
-                    server_selectable_id,
-                    indentation_level + 1,
-                );
-                s.push_str(&field_type)
+                let target_entity_id = match schema.server_selectable(*server_selectable_id) {
+                    SelectionType::Scalar(scalar_selectable) => {
+                        ServerEntityId::Scalar(*scalar_selectable.target_scalar_entity.inner())
+                    }
+                    SelectionType::Object(object_selectable) => {
+                        ServerEntityId::Object(*object_selectable.target_object_entity.inner())
+                    }
+                };
+                visit_scalar_entities(schema, target_entity_id, visited_objects, visit_scalar);
             }
-            s.push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
-            s
         }
-        ServerEntityId::Scalar(scalar_entity_id) => schema
-            .server_entity_data
-            .server_scalar_entity(scalar_entity_id)
-            .javascript_name
-            .to_string(),
     }
 }
 
-fn format_field_definition<TNetworkProtocol: NetworkProtocol>(
+/// Collect the names of the scalars with literal types that are referenced by a
+/// type (directly, or through the fields of input objects), so that the generated
+/// file can declare them.
+pub(crate) fn collect_enum_scalars<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    scalar_literal_types: &ScalarLiteralTypes,
+    type_: &GraphQLTypeAnnotation<ServerEntityId>,
+    enum_scalars: &mut BTreeSet<GraphQLScalarTypeName>,
+) {
+    visit_scalar_entities(
+        schema,
+        *type_.inner(),
+        &mut HashSet::new(),
+        &mut |scalar_entity_id| {
+            let name = schema
+                .server_entity_data
+                .server_scalar_entity(scalar_entity_id)
+                .name
+                .item;
+            if scalar_literal_types.contains_key(&name) {
+                enum_scalars.insert(name);
+            }
+        },
+    );
+}
+
+/// The import of the enums that are referenced as types when the enum_emit_style
+/// is ConstEnum or ConstObject. The enums are declared in the enums artifact.
+pub(crate) fn format_enum_imports(
+    enum_emit_style: EnumEmitStyle,
+    enum_scalars: &BTreeSet<GraphQLScalarTypeName>,
+    file_extensions: GenerateFileExtensionsOption,
+) -> String {
+    match enum_emit_style {
+        EnumEmitStyle::StringLiteralUnion => String::new(),
+        EnumEmitStyle::ConstEnum | EnumEmitStyle::ConstObject => {
+            if enum_scalars.is_empty() {
+                return String::new();
+            }
+            format!(
+                "import type {{ {} }} from '../../{}{}';\n",
+                enum_scalars
+                    .iter()
+                    .map(|name| name.lookup())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                *ENUMS,
+                file_extensions.ts()
+            )
+        }
+    }
+}
+
+/// The declarations of the enums that are referenced as types when the
+/// enum_emit_style is ConstEnum or ConstObject.
+pub(crate) fn format_enum_declarations(
+    scalar_literal_types: &ScalarLiteralTypes,
+    enum_emit_style: EnumEmitStyle,
+    enum_scalars: &BTreeSet<GraphQLScalarTypeName>,
+) -> String {
+    let mut declarations = String::new();
+    for name in enum_scalars {
+        let Some(literals) = scalar_literal_types.get(name) else {
+            continue;
+        };
+        match enum_emit_style {
+            EnumEmitStyle::StringLiteralUnion => {}
+            EnumEmitStyle::ConstEnum => {
+                declarations.push_str(&format!("export const enum {name} {{\n"));
+                for literal in literals {
+                    declarations.push_str(&format!(
+                        "  {} = {},\n",
+                        format_property_name(literal),
+                        quote_string_literal(literal)
+                    ));
+                }
+                declarations.push_str("}\n");
+            }
+            EnumEmitStyle::ConstObject => {
+                declarations.push_str(&format!("export const {name} = {{\n"));
+                for literal in literals {
+                    declarations.push_str(&format!(
+                        "  {}: {},\n",
+                        format_property_name(literal),
+                        quote_string_literal(literal)
+                    ));
+                }
+                declarations.push_str(&format!(
+                    "}} as const;\nexport type {name} = (typeof {name})[keyof typeof {name}];\n"
+                ));
+            }
+        }
+    }
+    declarations
+}
+
+/// For each abstract object (i.e. each object with inline fragments, such as an
+/// interface or a union), a union of the shapes of its concrete implementors, along
+/// with a type guard for each implementor that narrows the unions that contain it,
+/// using __typename as the discriminant, e.g.
+/// `export function isUser(node: ActorUnion | NodeUnion): node is UserShape { ... }`.
+pub fn format_type_guards<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
+) -> String {
+    // The names of the unions that contain each concrete type
+    let mut unions_by_concrete_type = BTreeMap::<&str, BTreeSet<&str>>::new();
+    let mut unions = BTreeMap::<&str, BTreeSet<&str>>::new();
+    for (index, object) in schema.server_entity_data.server_objects.iter().enumerate() {
+        if object.concrete_type.is_some() {
+            continue;
+        }
+        let concrete_types = schema
+            .implementors_of(index.into())
+            .into_iter()
+            .filter_map(|object_entity_id| {
+                schema
+                    .server_entity_data
+                    .server_object_entity(object_entity_id)
+                    .concrete_type
+            })
+            .map(|concrete_type| concrete_type.lookup())
+            .collect::<BTreeSet<_>>();
+        if concrete_types.is_empty() {
+            continue;
+        }
+        for concrete_type in concrete_types.iter() {
+            unions_by_concrete_type
+                .entry(concrete_type)
+                .or_default()
+                .insert(object.name.lookup());
+        }
+        unions.insert(object.name.lookup(), concrete_types);
+    }
+
+    let readonly = options.readonly_modifier();
+    let mut type_guards = String::new();
+    for concrete_type in unions_by_concrete_type.keys() {
+        type_guards.push_str(&format!(
+            "export type {concrete_type}Shape = {{ {readonly}__typename: \"{concrete_type}\" }};\n"
+        ));
+    }
+    for (name, concrete_types) in unions.iter() {
+        type_guards.push_str(&format!(
+            "export type {name}Union = {};\n",
+            concrete_types
+                .iter()
+                .map(|concrete_type| format!("{concrete_type}Shape"))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ));
+    }
+    for (concrete_type, union_names) in unions_by_concrete_type.iter() {
+        type_guards.push_str(&format!(
+            "export function is{concrete_type}(node: {}): node is {concrete_type}Shape {{\n\
+            \x20 return node.__typename === \"{concrete_type}\";\n\
+            }}\n",
+            union_names
+                .iter()
+                .map(|name| format!("{name}Union"))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ));
+    }
+    type_guards
+}
+
+/// The text of a doc comment, with `*/` escaped so that it cannot end the comment.
+fn escape_doc_comment_text(text: &str) -> String {
+    text.replace("*/", "*\\/")
+}
+
+/// A `@deprecated` doc comment, e.g. for a deprecated argument.
+pub(crate) fn format_deprecation_comment(
+    deprecation_reason: &str,
+    indentation_level: u8,
+) -> String {
+    format!(
+        "{}/** @deprecated {} */\n",
+        "  ".repeat(indentation_level as usize),
+        escape_doc_comment_text(deprecation_reason)
+    )
+}
+
+pub(crate) fn format_scalar_imports(scalar_imports: &BTreeSet<JavascriptImport>) -> String {
+    let mut import_statements = scalar_imports
+        .iter()
+        .map(|javascript_import| {
+            format!(
+                "import type {{ {} }} from '{}';\n",
+                javascript_import.type_name, javascript_import.module_path
+            )
+        })
+        .collect::<Vec<_>>();
+    import_statements.sort();
+    import_statements.concat()
+}
+
+fn write_field_definition<TNetworkProtocol: NetworkProtocol>(
+    out: &mut TypeWriter<'_>,
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     name: &SelectableName,
     server_selectable_id: ServerSelectableId,
     indentation_level: u8,
-) -> String {
-    let (is_optional, selection_type) = match schema.server_selectable(server_selectable_id) {
-        SelectionType::Scalar(scalar_selectable) => (
-            is_nullable(&scalar_selectable.target_scalar_entity),
+    mut source_map: Option<&mut SourceMapBuilder>,
+) -> fmt::Result {
+    let indent = "  ".repeat(indentation_level as usize);
+    let readonly = options.readonly_modifier();
+    let name = format_property_name(name.lookup());
+    let server_selectable = schema.server_selectable(server_selectable_id);
+    if let SelectionType::Scalar(scalar_selectable) = &server_selectable {
+        if scalar_selectable.kind == ServerFieldKind::TypenameField
+            && !TNetworkProtocol::emit_typename_field()
+        {
+            return Ok(());
+        }
+    }
+    if let Some(source_map) = source_map.as_deref_mut() {
+        let location = match &server_selectable {
+            SelectionType::Scalar(scalar_selectable) => scalar_selectable.name.location,
+            SelectionType::Object(object_selectable) => object_selectable.name.location,
+        };
+        source_map.add_entry(out.line, location);
+    }
+
+    let selection_type = match server_selectable {
+        SelectionType::Scalar(scalar_selectable) => {
+            // The __typename of a concrete type can only be the name of that type,
+            // so we emit it as a string literal (which can act as a discriminant.)
+            if scalar_selectable.kind == ServerFieldKind::TypenameField {
+                if let Some(concrete_type) = schema
+                    .server_entity_data
+                    .server_object_entity(scalar_selectable.parent_object_entity_id)
+                    .concrete_type
+                {
+                    return writeln!(out, "{indent}{readonly}{name}: \"{concrete_type}\",");
+                }
+            }
             scalar_selectable
                 .target_scalar_entity
                 .clone()
-                .map(&mut SelectionType::Scalar),
-        ),
-        SelectionType::Object(object_selectable) => (
-            is_nullable(&object_selectable.target_object_entity),
-            object_selectable
-                .target_object_entity
-                .clone()
-                .map(&mut SelectionType::Object),
-        ),
+                .map(&mut SelectionType::Scalar)
+        }
+        SelectionType::Object(object_selectable) => object_selectable
+            .target_object_entity
+            .clone()
+            .map(&mut SelectionType::Object),
     };
 
-    format!(
-        "{}readonly {}{}: {},\n",
-        "  ".repeat(indentation_level as usize),
-        name,
-        if is_optional { "?" } else { "" },
-        format_type_annotation(schema, &selection_type, indentation_level + 1),
-    )
+    let (is_optional, selection_type) =
+        apply_optional_field_style(options.parameter_optional_field_style(), selection_type);
+    write!(
+        out,
+        "{indent}{readonly}{name}{}: ",
+        if is_optional { "?" } else { "" }
+    )?;
+    write_type_annotation(
+        out,
+        schema,
+        options,
+        &selection_type,
+        indentation_level + 1,
+        source_map,
+        false,
+    )?;
+    out.write_str(",\n")
 }
 
 fn is_nullable<T: Ord + Debug>(type_annotation: &TypeAnnotation<T>) -> bool {
@@ -124,79 +1365,1693 @@ fn is_nullable<T: Ord + Debug>(type_annotation: &TypeAnnotation<T>) -> bool {
     }
 }
 
-fn format_type_annotation<TNetworkProtocol: NetworkProtocol>(
+fn write_type_annotation<TNetworkProtocol: NetworkProtocol>(
+    out: &mut TypeWriter<'_>,
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     type_annotation: &TypeAnnotation<ServerEntityId>,
     indentation_level: u8,
-) -> String {
+    mut source_map: Option<&mut SourceMapBuilder>,
+    // Whether the type is e.g. the item of an array, rather than the entire type
+    // of a field
+    is_nested: bool,
+) -> fmt::Result {
     match &type_annotation {
-        TypeAnnotation::Scalar(scalar) => {
-            format_server_field_type(schema, *scalar, indentation_level + 1)
-        }
+        TypeAnnotation::Scalar(scalar) => write_server_field_type(
+            out,
+            schema,
+            options,
+            *scalar,
+            None,
+            indentation_level + 1,
+            source_map,
+        ),
         TypeAnnotation::Union(union_type_annotation) => {
             if union_type_annotation.variants.is_empty() {
                 panic!("Unexpected union with not enough variants.");
             }
 
-            let mut s = String::new();
             if union_type_annotation.variants.len() > 1 || union_type_annotation.nullable {
-                s.push('(');
+                // A nullable type that is not combined with anything else, e.g.
+                // `string | null`, does not need to be parenthesized.
+                let parenthesize = is_nested
+                    || union_type_annotation.variants.len() > 1
+                    || !union_type_annotation.nullable;
+                match options.nullable_type_wrapper {
+                    Some(nullable_type_wrapper) if union_type_annotation.nullable => {
+                        write!(out, "{nullable_type_wrapper}<")?
+                    }
+                    _ if parenthesize => out.write_str("(")?,
+                    _ => {}
+                };
                 for (index, variant) in union_type_annotation.variants.iter().enumerate() {
                     if index != 0 {
-                        s.push_str(" | ");
+                        out.write_str(" | ")?;
                     }
 
                     match variant {
-                        UnionVariant::Scalar(scalar) => {
-                            s.push_str(&format_server_field_type(
-                                schema,
-                                *scalar,
-                                indentation_level + 1,
-                            ));
-                        }
+                        UnionVariant::Scalar(scalar) => write_server_field_type(
+                            out,
+                            schema,
+                            options,
+                            *scalar,
+                            None,
+                            indentation_level + 1,
+                            source_map.as_deref_mut(),
+                        )?,
                         UnionVariant::Plural(type_annotation) => {
-                            s.push_str("ReadonlyArray<");
-                            s.push_str(&format_type_annotation(
+                            out.write_str("ReadonlyArray<")?;
+                            write_type_annotation(
+                                out,
                                 schema,
+                                options,
                                 type_annotation,
                                 indentation_level + 1,
-                            ));
-                            s.push('>');
+                                source_map.as_deref_mut(),
+                                true,
+                            )?;
+                            out.write_str(">")?;
                         }
                     }
                 }
-                if union_type_annotation.nullable {
-                    s.push_str(" | null");
+                match options.nullable_type_wrapper {
+                    Some(_) if union_type_annotation.nullable => out.write_str(">"),
+                    Some(_) => out.write_str(")"),
+                    None if union_type_annotation.nullable && parenthesize => {
+                        out.write_str(" | null)")
+                    }
+                    None if union_type_annotation.nullable => out.write_str(" | null"),
+                    None => out.write_str(")"),
                 }
-                s.push(')');
-                s
             } else {
                 let variant = union_type_annotation
                     .variants
                     .first()
                     .expect("Expected variant to exist");
                 match variant {
-                    UnionVariant::Scalar(scalar) => {
-                        format_server_field_type(schema, *scalar, indentation_level + 1)
-                    }
+                    UnionVariant::Scalar(scalar) => write_server_field_type(
+                        out,
+                        schema,
+                        options,
+                        *scalar,
+                        None,
+                        indentation_level + 1,
+                        source_map,
+                    ),
                     UnionVariant::Plural(type_annotation) => {
-                        format!(
-                            "ReadonlyArray<{}>",
-                            format_server_field_type(
-                                schema,
-                                *type_annotation.inner(),
-                                indentation_level + 1
-                            )
-                        )
+                        out.write_str("ReadonlyArray<")?;
+                        // write_type_annotation increments the indentation level
+                        write_type_annotation(
+                            out,
+                            schema,
+                            options,
+                            type_annotation,
+                            indentation_level,
+                            source_map,
+                            true,
+                        )?;
+                        out.write_str(">")
                     }
                 }
             }
         }
         TypeAnnotation::Plural(type_annotation) => {
-            format!(
-                "ReadonlyArray<{}>",
-                format_server_field_type(schema, *type_annotation.inner(), indentation_level + 1)
+            out.write_str("ReadonlyArray<")?;
+            write_type_annotation(
+                out,
+                schema,
+                options,
+                type_annotation,
+                indentation_level,
+                source_map,
+                true,
+            )?;
+            out.write_str(">")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeSet, error::Error, marker::PhantomData};
+
+    use common_lang_types::{
+        Location, QueryOperationName, QueryText, SelectableName, Span, TextSource, WithLocation,
+        WithSpan,
+    };
+    use graphql_lang_types::{
+        GraphQLListTypeAnnotation, GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation,
+        GraphQLTypeAnnotation,
+    };
+    use graphql_network_protocol::{
+        GraphQLNetworkProtocol, GraphQLSchemaObjectAssociatedData,
+        GraphQLSchemaOriginalDefinitionType, GraphqlSchema,
+    };
+    use intern::{string_key::Intern, Lookup};
+    use isograph_config::{
+        CompilerConfigOptions, EnumEmitStyle, OptionalFieldStyle, PropertyOrder,
+        ScalarLiteralTypes, ScalarMapTypes, VoidInNullableParameterTypesOption,
+    };
+    use isograph_lang_types::{
+        ServerEntityId, ServerObjectEntityId, ServerScalarEntityId, TypeAnnotation,
+    };
+    use isograph_schema::{
+        JavascriptImport, MergedSelectionMap, NetworkProtocol, ProcessTypeSystemDocumentOutcome,
+        RootOperationName, Schema, SchemaServerObjectSelectableVariant, ServerFieldKind,
+        ServerObjectEntity, ServerObjectSelectable, ServerScalarEntity, ServerScalarSelectable,
+        ValidatedVariableDefinition,
+    };
+    use pico::Database;
+
+    use super::{
+        collect_enum_scalars, collect_scalar_imports, format_deprecation_comment,
+        format_enum_declarations, format_indented_parameter_type, format_parameter_type,
+        format_property_name, format_reader_scalar_type, format_scalar_imports, format_type_guards,
+        is_nullable, write_parameter_type, write_type_annotation, CamelCaseFieldNames,
+        FormattedTypeCache, JavascriptTypeFor, SourceMapBuilder, SourceMapEntry, TypeFormatOptions,
+        TypeRenderOverride, TypeWriter,
+    };
+    use crate::import_statements::ScalarTypeImports;
+
+    fn format_type_annotation(
+        schema: &GraphqlSchema,
+        options: &TypeFormatOptions<'_, GraphQLNetworkProtocol>,
+        type_annotation: &TypeAnnotation<ServerEntityId>,
+        indentation_level: u8,
+        source_map: Option<&mut SourceMapBuilder>,
+    ) -> String {
+        let mut s = String::new();
+        let mut out = TypeWriter::new(&mut s);
+        write_type_annotation(
+            &mut out,
+            schema,
+            options,
+            type_annotation,
+            indentation_level,
+            source_map,
+            false,
+        )
+        .expect("Expected writing to a String to succeed");
+        s
+    }
+
+    /// Format type_ as the type of a parameter (at indentation level 1) with options
+    fn format_with_options(
+        schema: &GraphqlSchema,
+        options: TypeFormatOptions<'_, GraphQLNetworkProtocol>,
+        type_: &GraphQLTypeAnnotation<ServerEntityId>,
+    ) -> String {
+        format_parameter_type(schema, &options, type_.clone(), 1, None)
+    }
+
+    fn string_type(schema: &GraphqlSchema) -> GraphQLNamedTypeAnnotation<ServerEntityId> {
+        GraphQLNamedTypeAnnotation(WithSpan::new(
+            ServerEntityId::Scalar(schema.server_entity_data.string_type_id),
+            Span::todo_generated(),
+        ))
+    }
+
+    fn non_null(
+        type_: GraphQLNonNullTypeAnnotation<ServerEntityId>,
+    ) -> GraphQLTypeAnnotation<ServerEntityId> {
+        GraphQLTypeAnnotation::NonNull(Box::new(type_))
+    }
+
+    /// [String!]!, [String]!, [String!] and [String]
+    fn list_types(schema: &GraphqlSchema) -> [GraphQLTypeAnnotation<ServerEntityId>; 4] {
+        let non_null_string = non_null(GraphQLNonNullTypeAnnotation::Named(string_type(schema)));
+        let nullable_string = GraphQLTypeAnnotation::Named(string_type(schema));
+        [
+            non_null(GraphQLNonNullTypeAnnotation::List(
+                GraphQLListTypeAnnotation(non_null_string.clone()),
+            )),
+            non_null(GraphQLNonNullTypeAnnotation::List(
+                GraphQLListTypeAnnotation(nullable_string.clone()),
+            )),
+            GraphQLTypeAnnotation::List(Box::new(GraphQLListTypeAnnotation(non_null_string))),
+            GraphQLTypeAnnotation::List(Box::new(GraphQLListTypeAnnotation(nullable_string))),
+        ]
+    }
+
+    #[test]
+    fn list_parameter_types_respect_item_nullability() {
+        let schema = GraphqlSchema::new();
+        let formatted = list_types(&schema).map(|type_| {
+            format_parameter_type(&schema, &TypeFormatOptions::default(), type_, 1, None)
+        });
+
+        assert_eq!(
+            formatted,
+            [
+                "ReadonlyArray<string>",
+                "ReadonlyArray<string | null>",
+                "ReadonlyArray<string> | null",
+                "ReadonlyArray<string | null> | null",
+            ]
+        );
+    }
+
+    #[test]
+    fn nullable_types_are_wrapped_with_the_nullable_type_wrapper() {
+        let schema = GraphqlSchema::new();
+        let formatted = list_types(&schema).map(|type_| {
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions {
+                    nullable_type_wrapper: Some("Maybe"),
+                    ..Default::default()
+                },
+                type_,
+                1,
+                None,
+            )
+        });
+        assert_eq!(
+            formatted,
+            [
+                "ReadonlyArray<string>",
+                "ReadonlyArray<Maybe<string>>",
+                "Maybe<ReadonlyArray<string>>",
+                "Maybe<ReadonlyArray<Maybe<string>>>",
+            ]
+        );
+
+        let nullable_string = GraphQLTypeAnnotation::Named(string_type(&schema));
+        assert_eq!(
+            [
+                VoidInNullableParameterTypesOption::IncludeVoidInNullableParameterTypes,
+                VoidInNullableParameterTypesOption::ExcludeVoidFromNullableParameterTypes,
+            ]
+            .map(|void_in_nullable_parameter_types| {
+                format_parameter_type(
+                    &schema,
+                    &TypeFormatOptions {
+                        nullable_type_wrapper: Some("Maybe"),
+                        void_in_nullable_parameter_types,
+                        ..Default::default()
+                    },
+                    nullable_string.clone(),
+                    1,
+                    None,
+                )
+            }),
+            ["Maybe<string> | void", "Maybe<string>"]
+        );
+
+        let string = ServerEntityId::Scalar(schema.server_entity_data.string_type_id);
+        assert_eq!(
+            format_type_annotation(
+                &schema,
+                &TypeFormatOptions {
+                    nullable_type_wrapper: Some("Maybe"),
+                    ..Default::default()
+                },
+                &TypeAnnotation::nullable_scalar(string),
+                1,
+                None,
+            ),
+            "Maybe<string>"
+        );
+    }
+
+    #[test]
+    fn type_annotation_constructors() {
+        let schema = GraphqlSchema::new();
+        let string = ServerEntityId::Scalar(schema.server_entity_data.string_type_id);
+        let type_annotations = [
+            TypeAnnotation::non_null_scalar(string),
+            TypeAnnotation::nullable_scalar(string),
+            TypeAnnotation::list_of(TypeAnnotation::non_null_scalar(string)),
+            TypeAnnotation::list_of(TypeAnnotation::nullable_scalar(string)),
+        ];
+
+        assert_eq!(
+            type_annotations.each_ref().map(is_nullable),
+            [false, true, false, false]
+        );
+        assert_eq!(
+            type_annotations.each_ref().map(|type_annotation| {
+                format_type_annotation(
+                    &schema,
+                    &TypeFormatOptions::default(),
+                    type_annotation,
+                    1,
+                    None,
+                )
+            }),
+            [
+                "string",
+                "string | null",
+                "ReadonlyArray<string>",
+                "ReadonlyArray<(string | null)>",
+            ]
+        );
+        assert_eq!(
+            type_annotations[3],
+            TypeAnnotation::from_graphql_type_annotation(list_types(&schema)[1].clone())
+        );
+    }
+
+    #[test]
+    fn list_type_annotations_respect_item_nullability() {
+        let schema = GraphqlSchema::new();
+        let formatted = list_types(&schema).map(|type_| {
+            format_type_annotation(
+                &schema,
+                &TypeFormatOptions::default(),
+                &TypeAnnotation::from_graphql_type_annotation(type_),
+                1,
+                None,
+            )
+        });
+
+        assert_eq!(
+            formatted,
+            [
+                "ReadonlyArray<string>",
+                "ReadonlyArray<(string | null)>",
+                "ReadonlyArray<string> | null",
+                "ReadonlyArray<(string | null)> | null",
+            ]
+        );
+    }
+
+    #[test]
+    fn only_nested_nullable_types_are_parenthesized() {
+        let schema = GraphqlSchema::new();
+        let int = ServerEntityId::Scalar(schema.server_entity_data.int_type_id);
+        let options = TypeFormatOptions::default();
+
+        assert_eq!(
+            format_type_annotation(
+                &schema,
+                &options,
+                &TypeAnnotation::nullable_scalar(int),
+                1,
+                None,
+            ),
+            "number | null"
+        );
+        assert_eq!(
+            format_type_annotation(
+                &schema,
+                &options,
+                &TypeAnnotation::list_of(TypeAnnotation::nullable_scalar(int)),
+                1,
+                None,
+            ),
+            "ReadonlyArray<(number | null)>"
+        );
+    }
+
+    #[test]
+    fn nullable_named_parameter_type_can_exclude_void() {
+        let schema = GraphqlSchema::new();
+        let nullable_string =
+            GraphQLTypeAnnotation::Named(GraphQLNamedTypeAnnotation(WithSpan::new(
+                ServerEntityId::Scalar(schema.server_entity_data.string_type_id),
+                Span::todo_generated(),
+            )));
+
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions::default(),
+                nullable_string.clone(),
+                1,
+                None
+            ),
+            "string | null | void"
+        );
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions {
+                    void_in_nullable_parameter_types:
+                        VoidInNullableParameterTypesOption::ExcludeVoidFromNullableParameterTypes,
+                    ..Default::default()
+                },
+                nullable_string,
+                1,
+                None
+            ),
+            "string | null"
+        );
+    }
+
+    struct MoneyRenderOverride;
+
+    impl TypeRenderOverride<GraphQLNetworkProtocol> for MoneyRenderOverride {
+        fn render_object(
+            &self,
+            schema: &GraphqlSchema,
+            object_entity_id: ServerObjectEntityId,
+        ) -> Option<String> {
+            let object = schema
+                .server_entity_data
+                .server_object_entity(object_entity_id);
+            (object.name == "Money").then(|| "Branded<number, 'Money'>".to_string())
+        }
+    }
+
+    #[test]
+    fn type_render_override_replaces_object_rendering() {
+        let mut schema = GraphqlSchema::new();
+        let money_object_entity_id = schema
+            .server_entity_data
+            .insert_server_object_entity(
+                ServerObjectEntity {
+                    description: None,
+                    name: "Money".intern().into(),
+                    name_location: Location::generated(),
+                    concrete_type: None,
+                    output_associated_data: GraphQLSchemaObjectAssociatedData {
+                        original_definition_type: GraphQLSchemaOriginalDefinitionType::InputObject,
+                        is_one_of: false,
+                    },
+                },
+                Location::generated(),
+            )
+            .expect("Expected object to be inserted");
+        let money = non_null(GraphQLNonNullTypeAnnotation::Named(
+            GraphQLNamedTypeAnnotation(WithSpan::new(
+                ServerEntityId::Object(money_object_entity_id),
+                Span::todo_generated(),
+            )),
+        ));
+
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions {
+                    type_render_override: &MoneyRenderOverride,
+                    ..Default::default()
+                },
+                money,
+                1,
+                None
+            ),
+            "Branded<number, 'Money'>"
+        );
+    }
+
+    #[test]
+    fn typename_of_concrete_type_is_a_string_literal() {
+        let mut schema = GraphqlSchema::new();
+        let user_object_entity_id = schema
+            .server_entity_data
+            .insert_server_object_entity(
+                ServerObjectEntity {
+                    description: None,
+                    name: "User".intern().into(),
+                    name_location: Location::generated(),
+                    concrete_type: Some("User".intern().into()),
+                    output_associated_data: GraphQLSchemaObjectAssociatedData {
+                        original_definition_type: GraphQLSchemaOriginalDefinitionType::Object,
+                        is_one_of: false,
+                    },
+                },
+                Location::generated(),
+            )
+            .expect("Expected object to be inserted");
+        for (name, kind) in [
+            ("__typename", ServerFieldKind::TypenameField),
+            ("name", ServerFieldKind::ActualField),
+        ] {
+            schema
+                .insert_server_scalar_selectable(
+                    ServerScalarSelectable {
+                        description: None,
+                        name: WithLocation::new(name.intern().into(), Location::generated()),
+                        target_scalar_entity: TypeAnnotation::Scalar(
+                            schema.server_entity_data.string_type_id,
+                        ),
+                        kind,
+                        parent_object_entity_id: user_object_entity_id,
+                        arguments: vec![],
+                        phantom_data: PhantomData,
+                    },
+                    &CompilerConfigOptions::default(),
+                    None,
+                )
+                .expect("Expected scalar selectable to be inserted");
+        }
+        let user = non_null(GraphQLNonNullTypeAnnotation::Named(
+            GraphQLNamedTypeAnnotation(WithSpan::new(
+                ServerEntityId::Object(user_object_entity_id),
+                Span::todo_generated(),
+            )),
+        ));
+
+        assert_eq!(
+            format_parameter_type(&schema, &TypeFormatOptions::default(), user, 1, None),
+            "{\n    readonly __typename: \"User\",\n    readonly name: string,\n  }"
+        );
+    }
+
+    #[test]
+    fn id_fields_of_the_data_that_is_read_are_branded() {
+        let mut schema = GraphqlSchema::new();
+        let user_object_entity_id = schema
+            .server_entity_data
+            .insert_server_object_entity(
+                ServerObjectEntity {
+                    description: None,
+                    name: "User".intern().into(),
+                    name_location: Location::generated(),
+                    concrete_type: Some("User".intern().into()),
+                    output_associated_data: GraphQLSchemaObjectAssociatedData {
+                        original_definition_type: GraphQLSchemaOriginalDefinitionType::Object,
+                        is_one_of: false,
+                    },
+                },
+                Location::generated(),
+            )
+            .expect("Expected object to be inserted");
+        let id_type_id = schema.server_entity_data.id_type_id;
+        let options = TypeFormatOptions {
+            brand_id_types: true,
+            ..Default::default()
+        };
+        let format_reader_type = |options: &TypeFormatOptions<'_, GraphQLNetworkProtocol>,
+                                  scalar_entity_id| {
+            format_reader_scalar_type(
+                &schema,
+                options,
+                scalar_entity_id,
+                user_object_entity_id,
+                &mut ScalarTypeImports::default(),
+            )
+        };
+
+        assert_eq!(
+            format_reader_type(&options, id_type_id),
+            "string & { readonly __brand: 'UserID' }"
+        );
+        assert_eq!(
+            format_reader_type(&options, schema.server_entity_data.string_type_id),
+            "string"
+        );
+        assert_eq!(
+            format_reader_type(&TypeFormatOptions::default(), id_type_id),
+            "string"
+        );
+        // Parameters are not branded
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &options,
+                non_null(GraphQLNonNullTypeAnnotation::Named(
+                    GraphQLNamedTypeAnnotation(WithSpan::new(
+                        ServerEntityId::Scalar(id_type_id),
+                        Span::todo_generated(),
+                    ))
+                )),
+                1,
+                None
+            ),
+            "string"
+        );
+    }
+
+    #[test]
+    fn type_guards_narrow_to_each_concrete_type() {
+        let mut schema = GraphqlSchema::new();
+        let mut insert_object = |name: &str, concrete_type: Option<&str>| {
+            schema
+                .server_entity_data
+                .insert_server_object_entity(
+                    ServerObjectEntity {
+                        description: None,
+                        name: name.intern().into(),
+                        name_location: Location::generated(),
+                        concrete_type: concrete_type.map(|name| name.intern().into()),
+                        output_associated_data: GraphQLSchemaObjectAssociatedData {
+                            original_definition_type: if concrete_type.is_some() {
+                                GraphQLSchemaOriginalDefinitionType::Object
+                            } else {
+                                GraphQLSchemaOriginalDefinitionType::Union
+                            },
+                            is_one_of: false,
+                        },
+                    },
+                    Location::generated(),
+                )
+                .expect("Expected object to be inserted")
+        };
+        let node_object_entity_id = insert_object("Node", None);
+        let user_object_entity_id = insert_object("User", Some("User"));
+        let pet_object_entity_id = insert_object("Pet", Some("Pet"));
+        for (name, object_entity_id) in [
+            ("asUser", user_object_entity_id),
+            ("asPet", pet_object_entity_id),
+        ] {
+            schema
+                .insert_server_object_selectable(ServerObjectSelectable {
+                    description: None,
+                    name: WithLocation::new(name.intern().into(), Location::generated()),
+                    target_object_entity: TypeAnnotation::nullable_scalar(object_entity_id),
+                    object_selectable_variant: SchemaServerObjectSelectableVariant::InlineFragment,
+                    parent_object_entity_id: node_object_entity_id,
+                    arguments: vec![],
+                    phantom_data: PhantomData,
+                })
+                .expect("Expected object selectable to be inserted");
+        }
+
+        assert_eq!(
+            format_type_guards(&schema, &TypeFormatOptions::default()),
+            "export type PetShape = { readonly __typename: \"Pet\" };\n\
+            export type UserShape = { readonly __typename: \"User\" };\n\
+            export type NodeUnion = PetShape | UserShape;\n\
+            export function isPet(node: NodeUnion): node is PetShape {\n\
+            \x20 return node.__typename === \"Pet\";\n\
+            }\n\
+            export function isUser(node: NodeUnion): node is UserShape {\n\
+            \x20 return node.__typename === \"User\";\n\
+            }\n"
+        );
+    }
+
+    #[test]
+    fn source_map_maps_emitted_fields_to_their_definitions() {
+        let mut schema = GraphqlSchema::new();
+        let mut insert_input_object = |name: &str| {
+            schema
+                .server_entity_data
+                .insert_server_object_entity(
+                    ServerObjectEntity {
+                        description: None,
+                        name: name.intern().into(),
+                        name_location: Location::generated(),
+                        concrete_type: None,
+                        output_associated_data: GraphQLSchemaObjectAssociatedData {
+                            original_definition_type:
+                                GraphQLSchemaOriginalDefinitionType::InputObject,
+                            is_one_of: false,
+                        },
+                    },
+                    Location::generated(),
+                )
+                .expect("Expected object to be inserted")
+        };
+        let profile_object_entity_id = insert_input_object("ProfileInput");
+        let address_object_entity_id = insert_input_object("AddressInput");
+
+        let location = |start: u32| {
+            Location::new(
+                TextSource {
+                    relative_path_to_source_file: "schema.graphql".intern().into(),
+                    span: None,
+                    current_working_directory: "cwd".intern().into(),
+                },
+                Span::new(start, start + 4),
+            )
+        };
+        for (name, parent_object_entity_id, start) in [
+            ("name", profile_object_entity_id, 10),
+            ("city", address_object_entity_id, 20),
+        ] {
+            schema
+                .insert_server_scalar_selectable(
+                    ServerScalarSelectable {
+                        description: None,
+                        name: WithLocation::new(name.intern().into(), location(start)),
+                        target_scalar_entity: TypeAnnotation::Scalar(
+                            schema.server_entity_data.string_type_id,
+                        ),
+                        kind: ServerFieldKind::ActualField,
+                        parent_object_entity_id,
+                        arguments: vec![],
+                        phantom_data: PhantomData,
+                    },
+                    &CompilerConfigOptions::default(),
+                    None,
+                )
+                .expect("Expected scalar selectable to be inserted");
+        }
+        schema
+            .insert_server_object_selectable(ServerObjectSelectable {
+                description: None,
+                name: WithLocation::new("address".intern().into(), location(30)),
+                target_object_entity: TypeAnnotation::Scalar(address_object_entity_id),
+                object_selectable_variant: SchemaServerObjectSelectableVariant::LinkedField,
+                parent_object_entity_id: profile_object_entity_id,
+                arguments: vec![],
+                phantom_data: PhantomData,
+            })
+            .expect("Expected object selectable to be inserted");
+
+        let mut source_map = SourceMapBuilder::new();
+        let formatted = format_parameter_type(
+            &schema,
+            &TypeFormatOptions::default(),
+            non_null(GraphQLNonNullTypeAnnotation::Named(
+                GraphQLNamedTypeAnnotation(WithSpan::new(
+                    ServerEntityId::Object(profile_object_entity_id),
+                    Span::todo_generated(),
+                )),
+            )),
+            1,
+            Some(&mut source_map),
+        );
+
+        assert_eq!(
+            formatted,
+            "{\n    readonly address: {\n          readonly city: string,\n        },\n    readonly name: string,\n  }"
+        );
+        assert_eq!(
+            source_map.entries(),
+            [
+                SourceMapEntry {
+                    line: 1,
+                    location: location(30)
+                },
+                SourceMapEntry {
+                    line: 2,
+                    location: location(20)
+                },
+                SourceMapEntry {
+                    line: 4,
+                    location: location(10)
+                },
+            ]
+        );
+    }
+
+    /// A non-null input object type with a String field for each of field_names
+    fn input_object_with_fields(
+        schema: &mut GraphqlSchema,
+        field_names: &[&str],
+    ) -> GraphQLTypeAnnotation<ServerEntityId> {
+        let string_type_id = schema.server_entity_data.string_type_id;
+        input_object_with_scalar_fields(
+            schema,
+            field_names.iter().map(|name| {
+                (
+                    *name,
+                    TypeAnnotation::Scalar(string_type_id),
+                    Location::generated(),
+                )
+            }),
+        )
+    }
+
+    /// A non-null input object type with a field of each name, type and location
+    fn input_object_with_scalar_fields<'a>(
+        schema: &mut GraphqlSchema,
+        fields: impl IntoIterator<Item = (&'a str, TypeAnnotation<ServerScalarEntityId>, Location)>,
+    ) -> GraphQLTypeAnnotation<ServerEntityId> {
+        let object_entity_id = schema
+            .server_entity_data
+            .insert_server_object_entity(
+                ServerObjectEntity {
+                    description: None,
+                    name: "UserInput".intern().into(),
+                    name_location: Location::generated(),
+                    concrete_type: None,
+                    output_associated_data: GraphQLSchemaObjectAssociatedData {
+                        original_definition_type: GraphQLSchemaOriginalDefinitionType::InputObject,
+                        is_one_of: false,
+                    },
+                },
+                Location::generated(),
+            )
+            .expect("Expected object to be inserted");
+        for (name, target_scalar_entity, location) in fields {
+            schema
+                .insert_server_scalar_selectable(
+                    ServerScalarSelectable {
+                        description: None,
+                        name: WithLocation::new(name.intern().into(), location),
+                        target_scalar_entity,
+                        kind: ServerFieldKind::ActualField,
+                        parent_object_entity_id: object_entity_id,
+                        arguments: vec![],
+                        phantom_data: PhantomData,
+                    },
+                    &CompilerConfigOptions::default(),
+                    None,
+                )
+                .expect("Expected scalar selectable to be inserted");
+        }
+        non_null(GraphQLNonNullTypeAnnotation::Named(
+            GraphQLNamedTypeAnnotation(WithSpan::new(
+                ServerEntityId::Object(object_entity_id),
+                Span::todo_generated(),
+            )),
+        ))
+    }
+
+    /// Renames fields according to a fixed list of renames
+    struct RenamedFields(&'static [(&'static str, &'static str)]);
+
+    impl TypeRenderOverride<GraphQLNetworkProtocol> for RenamedFields {
+        fn render_object(
+            &self,
+            _schema: &GraphqlSchema,
+            _object_entity_id: ServerObjectEntityId,
+        ) -> Option<String> {
+            None
+        }
+
+        fn transform_field_name(&self, field_name: SelectableName) -> Option<String> {
+            self.0
+                .iter()
+                .find(|(name, _)| *name == field_name.lookup())
+                .map(|(_, renamed)| renamed.to_string())
+        }
+    }
+
+    #[test]
+    fn indented_parameter_types_can_be_embedded_in_a_block() {
+        let mut schema = GraphqlSchema::new();
+        let user_input = input_object_with_fields(&mut schema, &["name"]);
+
+        let formatted = format_indented_parameter_type(
+            &schema,
+            &TypeFormatOptions::default(),
+            user_input,
+            2,
+            None,
+        );
+        assert_eq!(
+            format!("{{\n  input: [\n{formatted},\n  ],\n}}"),
+            "{\n  input: [\n    {\n      readonly name: string,\n    },\n  ],\n}"
+        );
+    }
+
+    #[test]
+    fn formatted_objects_are_cached_per_indentation_level() {
+        let mut schema = GraphqlSchema::new();
+        let user_input = input_object_with_fields(&mut schema, &["first_name", "age"]);
+        let mut type_cache = FormattedTypeCache::new();
+        let format = |indentation_level, type_cache: Option<&mut FormattedTypeCache>| {
+            let mut s = String::new();
+            write_parameter_type(
+                &mut s,
+                &schema,
+                &TypeFormatOptions::default(),
+                user_input.clone(),
+                indentation_level,
+                None,
+                type_cache,
+            )
+            .expect("Expected writing to a String to succeed");
+            s
+        };
+
+        let first = format(1, Some(&mut type_cache));
+        let second = format(1, Some(&mut type_cache));
+        assert_eq!(first, second);
+        assert_eq!(first, format(1, None));
+        assert_eq!(type_cache.hits(), 1);
+
+        assert_eq!(format(2, Some(&mut type_cache)), format(2, None));
+        assert_eq!(type_cache.hits(), 1);
+    }
+
+    #[test]
+    fn property_orders() {
+        let mut schema = GraphqlSchema::new();
+        let location = |file: &str, start: u32| {
+            Location::new(
+                TextSource {
+                    relative_path_to_source_file: file.intern().into(),
+                    span: None,
+                    current_working_directory: "cwd".intern().into(),
+                },
+                Span::new(start, start + 4),
+            )
+        };
+        let string = TypeAnnotation::Scalar(schema.server_entity_data.string_type_id);
+        let user_input = input_object_with_scalar_fields(
+            &mut schema,
+            [
+                (
+                    "nickname",
+                    string.clone(),
+                    location("schema_extension.graphql", 5),
+                ),
+                ("name", string.clone(), location("schema.graphql", 10)),
+                ("age", string.clone(), location("schema.graphql", 20)),
+                ("__typename", string.clone(), Location::generated()),
+                ("birthday", string, location("schema.graphql", 30)),
+            ],
+        );
+
+        assert_eq!(
+            format_with_options(
+                &schema,
+                TypeFormatOptions {
+                    input_object_property_order: PropertyOrder::Alphabetical,
+                    ..Default::default()
+                },
+                &user_input
+            ),
+            "{\n    readonly __typename: string,\n    readonly age: string,\n    \
+            readonly birthday: string,\n    readonly name: string,\n    \
+            readonly nickname: string,\n  }"
+        );
+        assert_eq!(
+            format_with_options(
+                &schema,
+                TypeFormatOptions {
+                    input_object_property_order: PropertyOrder::SchemaOrder,
+                    ..Default::default()
+                },
+                &user_input
+            ),
+            "{\n    readonly name: string,\n    readonly age: string,\n    \
+            readonly birthday: string,\n    readonly nickname: string,\n    \
+            readonly __typename: string,\n  }"
+        );
+    }
+
+    #[test]
+    fn readonly_properties_can_be_disabled() {
+        let mut schema = GraphqlSchema::new();
+        let user_input = input_object_with_fields(&mut schema, &["name", "age"]);
+
+        assert_eq!(
+            format_with_options(&schema, TypeFormatOptions::default(), &user_input),
+            "{\n    readonly age: string,\n    readonly name: string,\n  }"
+        );
+        assert_eq!(
+            format_with_options(
+                &schema,
+                TypeFormatOptions {
+                    readonly_properties: false,
+                    ..Default::default()
+                },
+                &user_input
+            ),
+            "{\n    age: string,\n    name: string,\n  }"
+        );
+    }
+
+    #[test]
+    fn field_name_transform_does_not_rename_input_object_fields() {
+        let mut schema = GraphqlSchema::new();
+        let user_input = input_object_with_fields(&mut schema, &["first_name", "age"]);
+
+        // Parameters are sent to the server as-is, so they keep the schema's names.
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions {
+                    type_render_override: &CamelCaseFieldNames,
+                    ..Default::default()
+                },
+                user_input,
+                1,
+                None
+            ),
+            "{\n    readonly age: string,\n    readonly first_name: string,\n  }"
+        );
+    }
+
+    #[test]
+    fn field_name_transform_renames_reader_properties() {
+        let options = TypeFormatOptions::<GraphQLNetworkProtocol> {
+            type_render_override: &CamelCaseFieldNames,
+            ..Default::default()
+        };
+        let reader_property_name =
+            |name: &str| options.reader_property_name(name.intern().into(), None);
+
+        assert_eq!(reader_property_name("first_name"), "firstName");
+        assert_eq!(reader_property_name("age"), "age");
+        assert_eq!(reader_property_name("__typename"), "__typename");
+        assert_eq!(options.renamed_server_field("age".intern().into()), None);
+        assert_eq!(
+            options.reader_property_name(
+                "first_name".intern().into(),
+                Some(WithLocation::new(
+                    "name".intern().into(),
+                    Location::generated()
+                ))
+            ),
+            "name"
+        );
+    }
+
+    #[test]
+    fn renamed_reader_properties_that_are_reserved_words_are_quoted() {
+        let options = TypeFormatOptions::<GraphQLNetworkProtocol> {
+            type_render_override: &RenamedFields(&[("class_name", "class")]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            options.reader_property_name("class_name".intern().into(), None),
+            "\"class\""
+        );
+        assert_eq!(
+            options.renamed_server_field("class_name".intern().into()),
+            Some("class".to_string())
+        );
+    }
+
+    /// Records every chunk that is written, so that we can check that the
+    /// output is actually streamed.
+    #[derive(Default)]
+    struct ChunkWriter {
+        chunks: Vec<String>,
+    }
+
+    impl std::fmt::Write for ChunkWriter {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            self.chunks.push(s.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn javascript_type_for_matches_formatted_parameter_types() {
+        let mut schema = GraphqlSchema::new();
+        let user_input = input_object_with_fields(&mut schema, &["first_name", "age"]);
+        let GraphQLTypeAnnotation::NonNull(user_input) = user_input else {
+            panic!("Expected a non-null type");
+        };
+        let GraphQLNonNullTypeAnnotation::Named(user_input) = *user_input else {
+            panic!("Expected a named type");
+        };
+
+        for named_type in [string_type(&schema), user_input] {
+            let entity = named_type.item;
+            assert_eq!(
+                schema.javascript_type_for(entity, 1),
+                format_parameter_type(
+                    &schema,
+                    &TypeFormatOptions::default(),
+                    non_null(GraphQLNonNullTypeAnnotation::Named(named_type)),
+                    1,
+                    None
+                )
+            );
+        }
+        assert_eq!(
+            schema.javascript_type_for(
+                ServerEntityId::Scalar(schema.server_entity_data.string_type_id),
+                0
+            ),
+            "string"
+        );
+    }
+
+    #[test]
+    fn streaming_and_string_output_are_identical() {
+        let mut schema = GraphqlSchema::new();
+        let user_input = input_object_with_fields(&mut schema, &["first_name", "age"]);
+
+        for nullable_type_wrapper in [None, Some("Maybe")] {
+            for type_ in list_types(&schema).into_iter().chain([user_input.clone()]) {
+                let mut string_source_map = SourceMapBuilder::new();
+                let formatted = format_parameter_type(
+                    &schema,
+                    &TypeFormatOptions {
+                        nullable_type_wrapper,
+                        ..Default::default()
+                    },
+                    type_.clone(),
+                    1,
+                    Some(&mut string_source_map),
+                );
+
+                let mut writer = ChunkWriter::default();
+                let mut streaming_source_map = SourceMapBuilder::new();
+                write_parameter_type(
+                    &mut writer,
+                    &schema,
+                    &TypeFormatOptions {
+                        nullable_type_wrapper,
+                        ..Default::default()
+                    },
+                    type_,
+                    1,
+                    Some(&mut streaming_source_map),
+                    None,
+                )
+                .expect("Expected writing to succeed");
+
+                assert!(writer.chunks.len() > 1);
+                assert_eq!(writer.chunks.concat(), formatted);
+                assert_eq!(streaming_source_map.entries(), string_source_map.entries());
+            }
+        }
+    }
+
+    #[test]
+    fn one_of_input_objects_are_formatted_as_unions() {
+        let mut schema = GraphqlSchema::new();
+        let object_entity_id = schema
+            .server_entity_data
+            .insert_server_object_entity(
+                ServerObjectEntity {
+                    description: None,
+                    name: "UserBy".intern().into(),
+                    name_location: Location::generated(),
+                    concrete_type: None,
+                    output_associated_data: GraphQLSchemaObjectAssociatedData {
+                        original_definition_type: GraphQLSchemaOriginalDefinitionType::InputObject,
+                        is_one_of: true,
+                    },
+                },
+                Location::generated(),
+            )
+            .expect("Expected object to be inserted");
+        let location = |start: u32| {
+            Location::new(
+                TextSource {
+                    relative_path_to_source_file: "schema.graphql".intern().into(),
+                    span: None,
+                    current_working_directory: "cwd".intern().into(),
+                },
+                Span::new(start, start + 4),
+            )
+        };
+        for (name, start) in [("id", 10), ("email", 20)] {
+            schema
+                .insert_server_scalar_selectable(
+                    ServerScalarSelectable {
+                        description: None,
+                        name: WithLocation::new(name.intern().into(), location(start)),
+                        target_scalar_entity: TypeAnnotation::nullable_scalar(
+                            schema.server_entity_data.string_type_id,
+                        ),
+                        kind: ServerFieldKind::ActualField,
+                        parent_object_entity_id: object_entity_id,
+                        arguments: vec![],
+                        phantom_data: PhantomData,
+                    },
+                    &CompilerConfigOptions::default(),
+                    None,
+                )
+                .expect("Expected scalar selectable to be inserted");
+        }
+
+        let mut source_map = SourceMapBuilder::new();
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions::default(),
+                non_null(GraphQLNonNullTypeAnnotation::Named(
+                    GraphQLNamedTypeAnnotation(WithSpan::new(
+                        ServerEntityId::Object(object_entity_id),
+                        Span::todo_generated(),
+                    ))
+                )),
+                1,
+                Some(&mut source_map)
+            ),
+            "{\n    readonly email: string,\n  } | {\n    readonly id: string,\n  }"
+        );
+        assert_eq!(
+            source_map.entries(),
+            [
+                SourceMapEntry {
+                    line: 1,
+                    location: location(20)
+                },
+                SourceMapEntry {
+                    line: 3,
+                    location: location(10)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reserved_word_fields_are_quoted() {
+        let mut schema = GraphqlSchema::new();
+        let user_input = input_object_with_fields(&mut schema, &["class", "name"]);
+
+        assert_eq!(
+            format_parameter_type(&schema, &TypeFormatOptions::default(), user_input, 1, None),
+            "{\n    readonly \"class\": string,\n    readonly name: string,\n  }"
+        );
+        assert_eq!(format_property_name("data-\"id\""), "\"data-\\\"id\\\"\"");
+    }
+
+    #[test]
+    fn deprecation_comments_are_escaped() {
+        assert_eq!(
+            format_deprecation_comment("Use petId", 1),
+            "  /** @deprecated Use petId */\n"
+        );
+        assert_eq!(
+            format_deprecation_comment("Use petId */ instead", 0),
+            "/** @deprecated Use petId *\\/ instead */\n"
+        );
+    }
+
+    #[test]
+    fn specified_by_url_is_linked() {
+        let mut schema = GraphqlSchema::new();
+        let date_time_scalar_entity_id = schema.server_entity_data.server_scalars.len().into();
+        schema
+            .server_entity_data
+            .insert_server_scalar_entity(
+                ServerScalarEntity {
+                    description: None,
+                    name: WithLocation::new("DateTime".intern().into(), Location::generated()),
+                    javascript_name: "string".intern().into(),
+                    specified_by_url: Some(
+                        "https://example.com/date-time?format=*/*".intern().into(),
+                    ),
+                    javascript_import: None,
+                    output_format: PhantomData,
+                },
+                Location::generated(),
+            )
+            .expect("Expected scalar to be inserted");
+        let date_time = non_null(GraphQLNonNullTypeAnnotation::Named(
+            GraphQLNamedTypeAnnotation(WithSpan::new(
+                ServerEntityId::Scalar(date_time_scalar_entity_id),
+                Span::todo_generated(),
+            )),
+        ));
+
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions::default(),
+                date_time.clone(),
+                1,
+                None
+            ),
+            "string"
+        );
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions {
+                    link_specified_by_urls: true,
+                    ..Default::default()
+                },
+                date_time,
+                1,
+                None
+            ),
+            "/** @see https://example.com/date-time?format=*\\/* */ string"
+        );
+    }
+
+    #[test]
+    fn scalar_literal_types_are_formatted_as_unions() {
+        let mut schema = GraphqlSchema::new();
+        let color_scalar_entity_id = schema.server_entity_data.server_scalars.len().into();
+        schema
+            .server_entity_data
+            .insert_server_scalar_entity(
+                ServerScalarEntity {
+                    description: None,
+                    name: WithLocation::new("Color".intern().into(), Location::generated()),
+                    javascript_name: "string".intern().into(),
+                    specified_by_url: None,
+                    javascript_import: None,
+                    output_format: PhantomData,
+                },
+                Location::generated(),
+            )
+            .expect("Expected scalar to be inserted");
+        let color = GraphQLTypeAnnotation::Named(GraphQLNamedTypeAnnotation(WithSpan::new(
+            ServerEntityId::Scalar(color_scalar_entity_id),
+            Span::todo_generated(),
+        )));
+        let scalar_literal_types = ScalarLiteralTypes::from([(
+            "Color".intern().into(),
+            vec!["RED".to_string(), "GREEN".to_string(), "BLUE".to_string()],
+        )]);
+
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions {
+                    scalar_literal_types: &scalar_literal_types,
+                    void_in_nullable_parameter_types:
+                        VoidInNullableParameterTypesOption::ExcludeVoidFromNullableParameterTypes,
+                    ..Default::default()
+                },
+                color,
+                1,
+                None
+            ),
+            "\"RED\" | \"GREEN\" | \"BLUE\" | null"
+        );
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions {
+                    scalar_literal_types: &scalar_literal_types,
+                    void_in_nullable_parameter_types:
+                        VoidInNullableParameterTypesOption::ExcludeVoidFromNullableParameterTypes,
+                    ..Default::default()
+                },
+                non_null(GraphQLNonNullTypeAnnotation::Named(string_type(&schema))),
+                1,
+                None
+            ),
+            "string"
+        );
+    }
+
+    #[test]
+    fn enum_emit_styles() {
+        let mut schema = GraphqlSchema::new();
+        let status_scalar_entity_id = schema.server_entity_data.server_scalars.len().into();
+        schema
+            .server_entity_data
+            .insert_server_scalar_entity(
+                ServerScalarEntity {
+                    description: None,
+                    name: WithLocation::new("Status".intern().into(), Location::generated()),
+                    javascript_name: "string".intern().into(),
+                    specified_by_url: None,
+                    javascript_import: None,
+                    output_format: PhantomData,
+                },
+                Location::generated(),
+            )
+            .expect("Expected scalar to be inserted");
+        let status = non_null(GraphQLNonNullTypeAnnotation::Named(
+            GraphQLNamedTypeAnnotation(WithSpan::new(
+                ServerEntityId::Scalar(status_scalar_entity_id),
+                Span::todo_generated(),
+            )),
+        ));
+        let scalar_literal_types = ScalarLiteralTypes::from([(
+            "Status".intern().into(),
+            vec![
+                "ACTIVE".to_string(),
+                "INACTIVE".to_string(),
+                "ON \"HOLD\"".to_string(),
+            ],
+        )]);
+        let mut enum_scalars = BTreeSet::new();
+        collect_enum_scalars(&schema, &scalar_literal_types, &status, &mut enum_scalars);
+        assert_eq!(enum_scalars, BTreeSet::from(["Status".intern().into()]));
+
+        let format = |enum_emit_style| {
+            (
+                format_enum_declarations(&scalar_literal_types, enum_emit_style, &enum_scalars),
+                format_with_options(
+                    &schema,
+                    TypeFormatOptions {
+                        scalar_literal_types: &scalar_literal_types,
+                        enum_emit_style,
+                        ..Default::default()
+                    },
+                    &status,
+                ),
+            )
+        };
+
+        assert_eq!(
+            format(EnumEmitStyle::StringLiteralUnion),
+            (
+                "".to_string(),
+                "\"ACTIVE\" | \"INACTIVE\" | \"ON \\\"HOLD\\\"\"".to_string()
+            )
+        );
+        assert_eq!(
+            format(EnumEmitStyle::ConstEnum),
+            (
+                "export const enum Status {\n  ACTIVE = \"ACTIVE\",\n  INACTIVE = \"INACTIVE\",\n  \
+                \"ON \\\"HOLD\\\"\" = \"ON \\\"HOLD\\\"\",\n}\n"
+                    .to_string(),
+                "Status".to_string()
+            )
+        );
+        assert_eq!(
+            format(EnumEmitStyle::ConstObject),
+            (
+                "export const Status = {\n  ACTIVE: \"ACTIVE\",\n  INACTIVE: \"INACTIVE\",\n  \
+                \"ON \\\"HOLD\\\"\": \"ON \\\"HOLD\\\"\",\n} as const;\n\
+                export type Status = (typeof Status)[keyof typeof Status];\n"
+                    .to_string(),
+                "Status".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn optional_field_styles() {
+        let mut schema = GraphqlSchema::new();
+        let string_type_id = schema.server_entity_data.string_type_id;
+        let user_input = input_object_with_scalar_fields(
+            &mut schema,
+            [
+                (
+                    "name",
+                    TypeAnnotation::Scalar(string_type_id),
+                    Location::generated(),
+                ),
+                (
+                    "nickname",
+                    TypeAnnotation::nullable_scalar(string_type_id),
+                    Location::generated(),
+                ),
+            ],
+        );
+
+        let format = |optional_field_style| {
+            format_with_options(
+                &schema,
+                TypeFormatOptions {
+                    optional_field_style: Some(optional_field_style),
+                    ..Default::default()
+                },
+                &user_input,
             )
+        };
+
+        assert_eq!(
+            format(OptionalFieldStyle::QuestionMark),
+            "{\n    readonly name: string,\n    readonly nickname?: string,\n  }"
+        );
+        assert_eq!(
+            format(OptionalFieldStyle::UnionNull),
+            "{\n    readonly name: string,\n    readonly nickname: string | null,\n  }"
+        );
+        assert_eq!(
+            format(OptionalFieldStyle::Both),
+            "{\n    readonly name: string,\n    readonly nickname?: string | null,\n  }"
+        );
+    }
+
+    #[test]
+    fn scalar_map_types_are_formatted_as_records() {
+        let mut schema = GraphqlSchema::new();
+        let json_scalar_entity_id = schema.server_entity_data.server_scalars.len().into();
+        schema
+            .server_entity_data
+            .insert_server_scalar_entity(
+                ServerScalarEntity {
+                    description: None,
+                    name: WithLocation::new("JSONObject".intern().into(), Location::generated()),
+                    javascript_name: "string".intern().into(),
+                    specified_by_url: None,
+                    javascript_import: None,
+                    output_format: PhantomData,
+                },
+                Location::generated(),
+            )
+            .expect("Expected scalar to be inserted");
+        let json_object = GraphQLTypeAnnotation::NonNull(Box::new(
+            GraphQLNonNullTypeAnnotation::Named(GraphQLNamedTypeAnnotation(WithSpan::new(
+                ServerEntityId::Scalar(json_scalar_entity_id),
+                Span::todo_generated(),
+            ))),
+        ));
+        let scalar_map_types =
+            ScalarMapTypes::from([("JSONObject".intern().into(), "unknown".to_string())]);
+
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions {
+                    scalar_map_types: &scalar_map_types,
+                    void_in_nullable_parameter_types:
+                        VoidInNullableParameterTypesOption::ExcludeVoidFromNullableParameterTypes,
+                    ..Default::default()
+                },
+                json_object,
+                1,
+                None
+            ),
+            "Record<string, unknown>"
+        );
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions {
+                    scalar_map_types: &scalar_map_types,
+                    void_in_nullable_parameter_types:
+                        VoidInNullableParameterTypesOption::ExcludeVoidFromNullableParameterTypes,
+                    ..Default::default()
+                },
+                non_null(GraphQLNonNullTypeAnnotation::Named(string_type(&schema))),
+                1,
+                None
+            ),
+            "string"
+        );
+    }
+
+    #[test]
+    fn scalar_imports_are_collected() {
+        let mut schema = GraphqlSchema::new();
+        let decimal_scalar_entity_id = schema.server_entity_data.server_scalars.len().into();
+        let decimal_import = JavascriptImport {
+            type_name: "Decimal".intern().into(),
+            module_path: "decimal.js".intern().into(),
+        };
+        schema
+            .server_entity_data
+            .insert_server_scalar_entity(
+                ServerScalarEntity {
+                    description: None,
+                    name: WithLocation::new("Decimal".intern().into(), Location::generated()),
+                    javascript_name: "string".intern().into(),
+                    specified_by_url: None,
+                    javascript_import: Some(decimal_import),
+                    output_format: PhantomData,
+                },
+                Location::generated(),
+            )
+            .expect("Expected scalar to be inserted");
+        let user_input = input_object_with_fields(&mut schema, &["name"]);
+        let user_input_object_entity_id = match user_input.inner() {
+            ServerEntityId::Object(object_entity_id) => *object_entity_id,
+            ServerEntityId::Scalar(_) => panic!("Expected UserInput to be an object"),
+        };
+        schema
+            .insert_server_scalar_selectable(
+                ServerScalarSelectable {
+                    description: None,
+                    name: WithLocation::new("balance".intern().into(), Location::generated()),
+                    target_scalar_entity: TypeAnnotation::Scalar(decimal_scalar_entity_id),
+                    kind: ServerFieldKind::ActualField,
+                    parent_object_entity_id: user_input_object_entity_id,
+                    arguments: vec![],
+                    phantom_data: PhantomData,
+                },
+                &CompilerConfigOptions::default(),
+                None,
+            )
+            .expect("Expected scalar selectable to be inserted");
+
+        assert_eq!(
+            format_parameter_type(
+                &schema,
+                &TypeFormatOptions::default(),
+                user_input.clone(),
+                1,
+                None
+            ),
+            "{\n    readonly balance: Decimal,\n    readonly name: string,\n  }"
+        );
+
+        let mut scalar_imports = BTreeSet::new();
+        collect_scalar_imports(&schema, &user_input, &mut scalar_imports);
+        assert_eq!(scalar_imports, BTreeSet::from([decimal_import]));
+        assert_eq!(
+            format_scalar_imports(&scalar_imports),
+            "import type { Decimal } from 'decimal.js';\n"
+        );
+    }
+
+    /// A protocol whose responses do not contain __typename, and whose scalars
+    /// are all serialized as strings. It has no type system documents (types are
+    /// inserted into the schema directly), and each query is a GET request.
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash, Default)]
+    struct RestNetworkProtocol {}
+
+    impl NetworkProtocol for RestNetworkProtocol {
+        type Sources = ();
+
+        type SchemaObjectAssociatedData = ();
+
+        fn parse_and_process_type_system_documents(
+            _db: &Database,
+            _sources: &Self::Sources,
+            _options: &CompilerConfigOptions,
+        ) -> Result<ProcessTypeSystemDocumentOutcome<Self>, Box<dyn Error>> {
+            Ok(ProcessTypeSystemDocumentOutcome {
+                scalars: vec![],
+                objects: vec![],
+            })
+        }
+
+        fn generate_query_text<'a>(
+            query_name: QueryOperationName,
+            _schema: &Schema<Self>,
+            _selection_map: &MergedSelectionMap,
+            _query_variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
+            _root_operation_name: &RootOperationName,
+        ) -> QueryText {
+            QueryText(format!("GET /{query_name}"))
+        }
+
+        fn format_scalar_type(_scalar: &ServerScalarEntity<Self>) -> String {
+            "string".to_string()
+        }
+
+        fn emit_typename_field() -> bool {
+            false
         }
     }
+
+    #[test]
+    fn custom_network_protocol_customizes_formatting() {
+        let mut schema = Schema::<RestNetworkProtocol>::new();
+        let user_object_entity_id = schema
+            .server_entity_data
+            .insert_server_object_entity(
+                ServerObjectEntity {
+                    description: None,
+                    name: "User".intern().into(),
+                    name_location: Location::generated(),
+                    concrete_type: Some("User".intern().into()),
+                    output_associated_data: (),
+                },
+                Location::generated(),
+            )
+            .expect("Expected object to be inserted");
+        for (name, kind) in [
+            ("__typename", ServerFieldKind::TypenameField),
+            ("age", ServerFieldKind::ActualField),
+        ] {
+            schema
+                .insert_server_scalar_selectable(
+                    ServerScalarSelectable {
+                        description: None,
+                        name: WithLocation::new(name.intern().into(), Location::generated()),
+                        target_scalar_entity: TypeAnnotation::Scalar(
+                            schema.server_entity_data.int_type_id,
+                        ),
+                        kind,
+                        parent_object_entity_id: user_object_entity_id,
+                        arguments: vec![],
+                        phantom_data: PhantomData,
+                    },
+                    &CompilerConfigOptions::default(),
+                    None,
+                )
+                .expect("Expected scalar selectable to be inserted");
+        }
+        let user = non_null(GraphQLNonNullTypeAnnotation::Named(
+            GraphQLNamedTypeAnnotation(WithSpan::new(
+                ServerEntityId::Object(user_object_entity_id),
+                Span::todo_generated(),
+            )),
+        ));
+
+        assert_eq!(
+            format_parameter_type(&schema, &TypeFormatOptions::default(), user, 1, None),
+            "{\n    readonly age: string,\n  }"
+        );
+    }
 }
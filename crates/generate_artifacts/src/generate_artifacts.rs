@@ -1,6 +1,6 @@
 use common_lang_types::{
     derive_display, ArtifactFileName, ArtifactFilePrefix, ArtifactPathAndContent, DescriptionValue,
-    Location, ObjectTypeAndFieldName, SelectableNameOrAlias, Span, WithLocation, WithSpan,
+    Location, ObjectTypeAndFieldName, Span, WithLocation, WithSpan,
 };
 use graphql_lang_types::{
     GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation, GraphQLTypeAnnotation,
@@ -8,11 +8,11 @@ use graphql_lang_types::{
 use intern::{string_key::Intern, Lookup};
 
 use core::panic;
-use isograph_config::CompilerConfig;
+use isograph_config::{CompilerConfig, EnumEmitStyle};
 use isograph_lang_types::{
-    ArgumentKeyAndValue, ClientFieldDirectiveSet, ClientScalarSelectableId, DefinitionLocation,
-    EmptyDirectiveSet, NonConstantValue, ObjectSelectionDirectiveSet, ScalarSelection,
-    ScalarSelectionDirectiveSet, SelectionFieldArgument, SelectionType,
+    quote_string_literal, ArgumentKeyAndValue, ClientFieldDirectiveSet, ClientScalarSelectableId,
+    DefinitionLocation, EmptyDirectiveSet, NonConstantValue, ObjectSelectionDirectiveSet,
+    ScalarSelection, ScalarSelectionDirectiveSet, SelectionFieldArgument, SelectionType,
     SelectionTypeContainingSelections, ServerEntityId, ServerObjectEntityId, TypeAnnotation,
     UnionVariant, VariableDefinition,
 };
@@ -39,8 +39,13 @@ use crate::{
         generate_entrypoint_artifacts,
         generate_entrypoint_artifacts_with_client_field_traversal_result,
     },
-    format_parameter_type::format_parameter_type,
-    import_statements::{LinkImports, ParamTypeImports, UpdatableImports},
+    format_parameter_type::{
+        apply_optional_field_style, apply_optional_parameter_style, format_deprecation_comment,
+        format_enum_declarations, format_parameter_type, format_reader_scalar_type,
+        format_type_guards, write_parameter_type, FormattedTypeCache, TypeAliasBuilder,
+        TypeFormatOptions,
+    },
+    import_statements::{LinkImports, ParamTypeImports, ScalarTypeImports, UpdatableImports},
     iso_overload_file::build_iso_overload_artifact,
     refetch_reader_artifact::{
         generate_refetch_output_type_artifact, generate_refetch_reader_artifact,
@@ -50,6 +55,8 @@ use crate::{
 lazy_static! {
     pub static ref ENTRYPOINT_FILE_NAME: ArtifactFileName = "entrypoint.ts".intern().into();
     pub static ref ENTRYPOINT: ArtifactFilePrefix = "entrypoint".intern().into();
+    pub static ref ENUMS_FILE_NAME: ArtifactFileName = "enums.ts".intern().into();
+    pub static ref ENUMS: ArtifactFilePrefix = "enums".intern().into();
     pub static ref ISO_TS_FILE_NAME: ArtifactFileName = "iso.ts".intern().into();
     pub static ref ISO_TS: ArtifactFilePrefix = "iso".intern().into();
     pub static ref NORMALIZATION_AST_FILE_NAME: ArtifactFileName =
@@ -71,6 +78,7 @@ lazy_static! {
     pub static ref RESOLVER_READER_FILE_NAME: ArtifactFileName =
         "resolver_reader.ts".intern().into();
     pub static ref RESOLVER_READER: ArtifactFilePrefix = "resolver_reader".intern().into();
+    pub static ref TYPE_GUARDS_FILE_NAME: ArtifactFileName = "type_guards.ts".intern().into();
 }
 
 /// Get all artifacts according to the following scheme:
@@ -117,6 +125,8 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
     let mut encountered_client_type_map = BTreeMap::new();
     let mut path_and_contents = vec![];
     let mut encountered_output_types = HashSet::<ClientSelectableId>::new();
+    let mut type_cache = FormattedTypeCache::new();
+    let type_format_options = TypeFormatOptions::new(&config.options);
 
     // For each entrypoint, generate an entrypoint artifact and refetch artifacts
     for entrypoint_id in schema.entrypoints.keys() {
@@ -176,6 +186,8 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
                     &traversal_state.refetch_paths,
                     config.options.include_file_extensions_in_import_statements,
                     traversal_state.has_updatable,
+                    &type_format_options,
+                    &mut type_cache,
                 ));
             }
             DefinitionLocation::Client(SelectionType::Scalar(client_scalar_selectable_id)) => {
@@ -192,6 +204,8 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
                             &traversal_state.refetch_paths,
                             config.options.include_file_extensions_in_import_statements,
                             traversal_state.has_updatable,
+                            &type_format_options,
+                            &mut type_cache,
                         ));
 
                         if *was_ever_selected_loadably {
@@ -238,6 +252,7 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
                                 ],
                             );
                             let id_var = ValidatedVariableDefinition {
+                                description: None,
                                 name: WithLocation::new("id".intern().into(), Location::Generated),
                                 type_: GraphQLTypeAnnotation::NonNull(Box::new(
                                     GraphQLNonNullTypeAnnotation::Named(
@@ -250,6 +265,7 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
                                     ),
                                 )),
                                 default_value: None,
+                                deprecation_reason: None,
                             };
                             let variable_definitions_iter = client_scalar_selectable
                                 .variable_definitions
@@ -311,6 +327,7 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
             schema,
             &user_written_client_type,
             config.options.include_file_extensions_in_import_statements,
+            &type_format_options,
         ));
 
         match encountered_client_type_map.get(&DefinitionLocation::Client(client_type_id)) {
@@ -380,9 +397,43 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
         config.options.no_babel_transform,
     ));
 
+    if let Some(enums_artifact) = generate_enums_artifact(&type_format_options) {
+        path_and_contents.push(enums_artifact);
+    }
+
+    if config.options.emit_type_guards {
+        path_and_contents.push(ArtifactPathAndContent {
+            file_content: format_type_guards(schema, &type_format_options),
+            file_name: *TYPE_GUARDS_FILE_NAME,
+            type_and_field: None,
+        });
+    }
+
     path_and_contents
 }
 
+/// The declarations of the scalars with literal types, if they are emitted as enums
+/// (rather than as unions of string literals.) They are declared once, and every
+/// file that references one of them imports it from this artifact.
+fn generate_enums_artifact<TNetworkProtocol: NetworkProtocol>(
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
+) -> Option<ArtifactPathAndContent> {
+    if options.enum_emit_style == EnumEmitStyle::StringLiteralUnion
+        || options.scalar_literal_types.is_empty()
+    {
+        return None;
+    }
+    Some(ArtifactPathAndContent {
+        file_content: format_enum_declarations(
+            options.scalar_literal_types,
+            options.enum_emit_style,
+            &options.scalar_literal_types.keys().copied().collect(),
+        ),
+        file_name: *ENUMS_FILE_NAME,
+        type_and_field: None,
+    })
+}
+
 pub(crate) fn get_serialized_field_arguments(
     // TODO make this an iterator
     arguments: &[ArgumentKeyAndValue],
@@ -443,11 +494,12 @@ fn get_serialized_field_argument(
             )
         }
         NonConstantValue::String(s) => {
+            let s = quote_string_literal(s.lookup());
             format!(
                 "\n\
                 {indent_1}[\n\
                 {indent_2}\"{argument_name}\",\n\
-                {indent_2}{{ kind: \"String\", value: \"{s}\" }},\n\
+                {indent_2}{{ kind: \"String\", value: {s} }},\n\
                 {indent_1}],\n"
             )
         }
@@ -533,11 +585,13 @@ pub(crate) fn generate_output_type<TNetworkProtocol: NetworkProtocol>(
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn generate_client_field_parameter_type<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     selection_map: &[WithSpan<ValidatedSelection>],
     nested_client_field_imports: &mut ParamTypeImports,
     loadable_fields: &mut ParamTypeImports,
     indentation_level: u8,
     link_fields: &mut LinkImports,
+    scalar_type_imports: &mut ScalarTypeImports,
 ) -> ClientFieldParameterType {
     // TODO use unwraps
     let mut client_field_parameter_type = "{\n".to_string();
@@ -545,12 +599,14 @@ pub(crate) fn generate_client_field_parameter_type<TNetworkProtocol: NetworkProt
     for selection in selection_map.iter() {
         write_param_type_from_selection(
             schema,
+            options,
             &mut client_field_parameter_type,
             selection,
             nested_client_field_imports,
             loadable_fields,
             indentation_level + 1,
             link_fields,
+            scalar_type_imports,
         );
     }
     client_field_parameter_type.push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
@@ -561,12 +617,14 @@ pub(crate) fn generate_client_field_parameter_type<TNetworkProtocol: NetworkProt
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn generate_client_field_updatable_data_type<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     selection_map: &[WithSpan<ValidatedSelection>],
     nested_client_field_imports: &mut ParamTypeImports,
     loadable_fields: &mut ParamTypeImports,
     indentation_level: u8,
     link_fields: &mut LinkImports,
     updatable_fields: &mut UpdatableImports,
+    scalar_type_imports: &mut ScalarTypeImports,
 ) -> ClientFieldUpdatableDataType {
     // TODO use unwraps
 
@@ -575,6 +633,7 @@ pub(crate) fn generate_client_field_updatable_data_type<TNetworkProtocol: Networ
     for selection in selection_map.iter() {
         write_updatable_data_type_from_selection(
             schema,
+            options,
             &mut client_field_updatable_data_type,
             selection,
             nested_client_field_imports,
@@ -582,6 +641,7 @@ pub(crate) fn generate_client_field_updatable_data_type<TNetworkProtocol: Networ
             indentation_level + 1,
             link_fields,
             updatable_fields,
+            scalar_type_imports,
         );
     }
 
@@ -594,12 +654,14 @@ pub(crate) fn generate_client_field_updatable_data_type<TNetworkProtocol: Networ
 #[allow(clippy::too_many_arguments)]
 fn write_param_type_from_selection<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     query_type_declaration: &mut String,
     selection: &WithSpan<ValidatedSelection>,
     nested_client_field_imports: &mut ParamTypeImports,
     loadable_fields: &mut ParamTypeImports,
     indentation_level: u8,
     link_fields: &mut LinkImports,
+    scalar_type_imports: &mut ScalarTypeImports,
 ) {
     match &selection.item {
         SelectionTypeContainingSelections::Scalar(scalar_field_selection) => {
@@ -613,28 +675,40 @@ fn write_param_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                         indentation_level,
                     );
 
-                    let name_or_alias = scalar_field_selection.name_or_alias().item;
+                    let name_or_alias = options.reader_property_name(
+                        scalar_field_selection.name.item.into(),
+                        scalar_field_selection.reader_alias,
+                    );
 
-                    let output_type =
-                        field
-                            .target_scalar_entity
-                            .clone()
-                            .map(&mut |scalar_entity_id| {
-                                schema
-                                    .server_entity_data
-                                    .server_scalar_entity(scalar_entity_id)
-                                    .javascript_name
-                            });
+                    let (is_optional, output_type) = apply_optional_field_style(
+                        options.reader_optional_field_style(),
+                        field.target_scalar_entity.clone(),
+                    );
+                    let output_type = output_type.map(&mut |scalar_entity_id| {
+                        format_reader_scalar_type(
+                            schema,
+                            options,
+                            scalar_entity_id,
+                            field.parent_object_entity_id,
+                            scalar_type_imports,
+                        )
+                    });
+                    let optional_marker = if is_optional { "?" } else { "" };
 
                     query_type_declaration.push_str(&format!(
-                        "{}readonly {}: {},\n",
+                        "{}{}{}{optional_marker}: {},\n",
                         "  ".repeat(indentation_level as usize),
+                        options.readonly_modifier(),
                         name_or_alias,
-                        print_javascript_type_declaration(&output_type)
+                        print_wrapped_javascript_type_declaration(
+                            &output_type,
+                            options.nullable_type_wrapper
+                        )
                     ));
                 }
                 DefinitionLocation::Client(client_field_id) => write_param_type_from_client_field(
                     schema,
+                    options,
                     query_type_declaration,
                     nested_client_field_imports,
                     loadable_fields,
@@ -663,35 +737,56 @@ fn write_param_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                 indentation_level,
             );
             query_type_declaration.push_str(&"  ".repeat(indentation_level as usize).to_string());
-            let name_or_alias = linked_field.name_or_alias().item;
+            let name_or_alias = match field {
+                DefinitionLocation::Server(_) => options
+                    .reader_property_name(linked_field.name.item.into(), linked_field.reader_alias),
+                DefinitionLocation::Client(_) => linked_field.name_or_alias().item.to_string(),
+            };
 
-            let type_annotation = output_type_annotation(&field).clone().map(&mut |_| {
+            // Client pointers are wrapped in a LoadableField, and are never optional
+            let (is_optional, type_annotation) = match field {
+                DefinitionLocation::Server(_) => apply_optional_field_style(
+                    options.reader_optional_field_style(),
+                    output_type_annotation(&field).clone(),
+                ),
+                DefinitionLocation::Client(_) => (false, output_type_annotation(&field).clone()),
+            };
+            let type_annotation = type_annotation.map(&mut |_| {
                 generate_client_field_parameter_type(
                     schema,
+                    options,
                     &linked_field.selection_set,
                     nested_client_field_imports,
                     loadable_fields,
                     indentation_level,
                     link_fields,
+                    scalar_type_imports,
                 )
             });
 
             query_type_declaration.push_str(&format!(
-                "readonly {}: {},\n",
+                "{}{}{}: {},\n",
+                options.readonly_modifier(),
                 name_or_alias,
+                if is_optional { "?" } else { "" },
                 match field {
                     DefinitionLocation::Client(client_pointer) => {
                         loadable_fields.insert(client_pointer.type_and_field);
 
-                        let inner_output_type = print_javascript_type_declaration(&type_annotation);
+                        let inner_output_type = print_wrapped_javascript_type_declaration(
+                            &type_annotation,
+                            options.nullable_type_wrapper,
+                        );
 
                         format!(
                             "LoadableField<{}__param, {inner_output_type}>",
                             client_pointer.type_and_field.underscore_separated(),
                         )
                     }
-                    DefinitionLocation::Server(_) =>
-                        print_javascript_type_declaration(&type_annotation),
+                    DefinitionLocation::Server(_) => print_wrapped_javascript_type_declaration(
+                        &type_annotation,
+                        options.nullable_type_wrapper
+                    ),
                 }
             ));
         }
@@ -701,6 +796,7 @@ fn write_param_type_from_selection<TNetworkProtocol: NetworkProtocol>(
 #[allow(clippy::too_many_arguments)]
 fn write_param_type_from_client_field<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     query_type_declaration: &mut String,
     nested_client_field_imports: &mut BTreeSet<ObjectTypeAndFieldName>,
     loadable_fields: &mut BTreeSet<ObjectTypeAndFieldName>,
@@ -722,7 +818,8 @@ fn write_param_type_from_client_field<TNetworkProtocol: NetworkProtocol>(
             let output_type = "Link";
             query_type_declaration.push_str(
                 &(format!(
-                    "readonly {}: {},\n",
+                    "{}{}: {},\n",
+                    options.readonly_modifier(),
                     scalar_field_selection.name_or_alias().item,
                     output_type
                 )),
@@ -751,7 +848,11 @@ fn write_param_type_from_client_field<TNetworkProtocol: NetworkProtocol>(
                         format!(
                             ",\n{indent}Omit<ExtractParameters<{}__param>, keyof {}>",
                             client_field.type_and_field.underscore_separated(),
-                            get_loadable_field_type_from_arguments(schema, provided_arguments)
+                            get_loadable_field_type_from_arguments(
+                                schema,
+                                options,
+                                provided_arguments
+                            )
                         )
                     };
 
@@ -768,7 +869,8 @@ fn write_param_type_from_client_field<TNetworkProtocol: NetworkProtocol>(
             };
             query_type_declaration.push_str(
                 &(format!(
-                    "readonly {}: {},\n",
+                    "{}{}: {},\n",
+                    options.readonly_modifier(),
                     scalar_field_selection.name_or_alias().item,
                     output_type
                 )),
@@ -780,6 +882,7 @@ fn write_param_type_from_client_field<TNetworkProtocol: NetworkProtocol>(
 #[allow(clippy::too_many_arguments)]
 fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     query_type_declaration: &mut String,
     selection: &WithSpan<ValidatedSelection>,
     nested_client_field_imports: &mut ParamTypeImports,
@@ -787,6 +890,7 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
     indentation_level: u8,
     link_fields: &mut LinkImports,
     updatable_fields: &mut UpdatableImports,
+    scalar_type_imports: &mut ScalarTypeImports,
 ) {
     match &selection.item {
         SelectionTypeContainingSelections::Scalar(scalar_field_selection) => {
@@ -800,18 +904,25 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                         indentation_level,
                     );
 
-                    let name_or_alias = scalar_field_selection.name_or_alias().item;
+                    let name_or_alias = options.reader_property_name(
+                        scalar_field_selection.name.item.into(),
+                        scalar_field_selection.reader_alias,
+                    );
 
-                    let output_type =
-                        field
-                            .target_scalar_entity
-                            .clone()
-                            .map(&mut |scalar_entity_id| {
-                                schema
-                                    .server_entity_data
-                                    .server_scalar_entity(scalar_entity_id)
-                                    .javascript_name
-                            });
+                    let (is_optional, output_type) = apply_optional_field_style(
+                        options.reader_optional_field_style(),
+                        field.target_scalar_entity.clone(),
+                    );
+                    let output_type = output_type.map(&mut |scalar_entity_id| {
+                        format_reader_scalar_type(
+                            schema,
+                            options,
+                            scalar_entity_id,
+                            field.parent_object_entity_id,
+                            scalar_type_imports,
+                        )
+                    });
+                    let optional_marker = if is_optional { "?" } else { "" };
 
                     match scalar_field_selection.scalar_selection_directive_set {
                         ScalarSelectionDirectiveSet::Updatable(_) => {
@@ -819,9 +930,12 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                             query_type_declaration
                                 .push_str(&"  ".repeat(indentation_level as usize).to_string());
                             query_type_declaration.push_str(&format!(
-                                "{}: {},\n",
+                                "{}{optional_marker}: {},\n",
                                 name_or_alias,
-                                print_javascript_type_declaration(&output_type)
+                                print_wrapped_javascript_type_declaration(
+                                    &output_type,
+                                    options.nullable_type_wrapper
+                                )
                             ));
                         }
                         ScalarSelectionDirectiveSet::Loadable(_) => {
@@ -829,10 +943,14 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                         }
                         ScalarSelectionDirectiveSet::None(_) => {
                             query_type_declaration.push_str(&format!(
-                                "{}readonly {}: {},\n",
+                                "{}{}{}{optional_marker}: {},\n",
                                 "  ".repeat(indentation_level as usize),
+                                options.readonly_modifier(),
                                 name_or_alias,
-                                print_javascript_type_declaration(&output_type)
+                                print_wrapped_javascript_type_declaration(
+                                    &output_type,
+                                    options.nullable_type_wrapper
+                                )
                             ));
                         }
                     }
@@ -840,6 +958,7 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                 DefinitionLocation::Client(client_field_id) => {
                     write_param_type_from_client_field(
                         schema,
+                        options,
                         query_type_declaration,
                         nested_client_field_imports,
                         loadable_fields,
@@ -860,17 +979,27 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                 indentation_level,
             );
             query_type_declaration.push_str(&"  ".repeat(indentation_level as usize).to_string());
-            let name_or_alias = linked_field.name_or_alias().item;
+            let name_or_alias = match field {
+                DefinitionLocation::Server(_) => options
+                    .reader_property_name(linked_field.name.item.into(), linked_field.reader_alias),
+                DefinitionLocation::Client(_) => linked_field.name_or_alias().item.to_string(),
+            };
 
-            let type_annotation = output_type_annotation(&field).clone().map(&mut |_| {
+            let (is_optional, type_annotation) = apply_optional_field_style(
+                options.reader_optional_field_style(),
+                output_type_annotation(&field).clone(),
+            );
+            let type_annotation = type_annotation.map(&mut |_| {
                 generate_client_field_updatable_data_type(
                     schema,
+                    options,
                     &linked_field.selection_set,
                     nested_client_field_imports,
                     loadable_fields,
                     indentation_level,
                     link_fields,
                     updatable_fields,
+                    scalar_type_imports,
                 )
             });
 
@@ -880,16 +1009,22 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                     write_getter_and_setter(
                         query_type_declaration,
                         indentation_level,
-                        name_or_alias,
+                        options.nullable_type_wrapper,
+                        &name_or_alias,
                         output_type_annotation(&field),
                         &type_annotation,
                     );
                 }
                 ObjectSelectionDirectiveSet::None(_) => {
                     query_type_declaration.push_str(&format!(
-                        "readonly {}: {},\n",
+                        "{}{}{}: {},\n",
+                        options.readonly_modifier(),
                         name_or_alias,
-                        print_javascript_type_declaration(&type_annotation),
+                        if is_optional { "?" } else { "" },
+                        print_wrapped_javascript_type_declaration(
+                            &type_annotation,
+                            options.nullable_type_wrapper
+                        ),
                     ));
                 }
             }
@@ -900,14 +1035,15 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
 fn write_getter_and_setter(
     query_type_declaration: &mut String,
     indentation_level: u8,
-    name_or_alias: SelectableNameOrAlias,
+    nullable_type_wrapper: Option<&str>,
+    name_or_alias: &str,
     output_type_annotation: &TypeAnnotation<ServerObjectEntityId>,
     type_annotation: &TypeAnnotation<ClientFieldUpdatableDataType>,
 ) {
     query_type_declaration.push_str(&format!(
         "get {}(): {},\n",
         name_or_alias,
-        print_javascript_type_declaration(type_annotation),
+        print_wrapped_javascript_type_declaration(type_annotation, nullable_type_wrapper),
     ));
     let setter_type_annotation = output_type_annotation
         .clone()
@@ -916,12 +1052,13 @@ fn write_getter_and_setter(
     query_type_declaration.push_str(&format!(
         "set {}(value: {}),\n",
         name_or_alias,
-        print_javascript_type_declaration(&setter_type_annotation),
+        print_wrapped_javascript_type_declaration(&setter_type_annotation, nullable_type_wrapper),
     ));
 }
 
 fn get_loadable_field_type_from_arguments<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     arguments: Vec<ValidatedVariableDefinition>,
 ) -> String {
     let mut loadable_field_type = "{".to_string();
@@ -931,12 +1068,16 @@ fn get_loadable_field_type_from_arguments<TNetworkProtocol: NetworkProtocol>(
             loadable_field_type.push_str(", ");
         }
         is_first = false;
-        let is_optional = !matches!(arg.type_, GraphQLTypeAnnotation::NonNull(_));
+        let (is_optional, type_) = apply_optional_parameter_style(
+            options.parameter_optional_field_style(),
+            arg.type_.clone(),
+        );
         loadable_field_type.push_str(&format!(
-            "readonly {}{}: {}",
+            "{}{}{}: {}",
+            options.readonly_modifier(),
             arg.name.item,
             if is_optional { "?" } else { "" },
-            format_type_for_js(schema, arg.type_.clone())
+            format_type_for_js(schema, type_)
         ));
     }
     loadable_field_type.push('}');
@@ -988,20 +1129,70 @@ fn format_type_for_js_inner(
     }
 }
 
+/// Parameters are emitted in the order in which argument_definitions yields them,
+/// which should be the order in which they are declared (i.e. a Vec, not a map),
+/// so that the generated types are stable. If type_aliases is provided, repeated
+/// parameter types reference the aliases in it, whose declarations must then be
+/// emitted alongside the parameters type.
 pub(crate) fn generate_parameters<'a, TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     argument_definitions: impl Iterator<Item = &'a VariableDefinition<ServerEntityId>>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
+    mut type_cache: Option<&mut FormattedTypeCache>,
+    mut type_aliases: Option<&mut TypeAliasBuilder>,
 ) -> String {
+    let parameters = argument_definitions
+        .map(|arg| {
+            let (is_optional, type_) = apply_optional_parameter_style(
+                options.parameter_optional_field_style(),
+                arg.type_.clone(),
+            );
+            (arg, is_optional, type_)
+        })
+        .collect::<Vec<_>>();
+    if let Some(type_aliases) = type_aliases.as_deref_mut() {
+        for (_, _, type_) in parameters.iter() {
+            type_aliases.count(type_);
+        }
+    }
+
     let mut s = "{\n".to_string();
     let indent = "  ";
-    for arg in argument_definitions {
-        let is_optional = !matches!(arg.type_, GraphQLTypeAnnotation::NonNull(_));
+    for (arg, is_optional, type_) in parameters {
+        write_optional_description(
+            arg.description.map(|description| description.item),
+            &mut s,
+            1,
+        );
+        // Deprecated arguments are still emitted (and are still required, if non-null).
+        if let Some(deprecation_reason) = arg.deprecation_reason {
+            s.push_str(&format_deprecation_comment(deprecation_reason.lookup(), 1));
+        }
         s.push_str(&format!(
-            "{indent}readonly {}{}: {},\n",
+            "{indent}{}{}{}: ",
+            options.readonly_modifier(),
             arg.name.item,
             if is_optional { "?" } else { "" },
-            format_parameter_type(schema, arg.type_.clone(), 1)
         ));
+        let alias = type_aliases.as_deref_mut().and_then(|type_aliases| {
+            type_aliases.reference(&type_, || {
+                format_parameter_type(schema, options, type_.clone(), 1, None)
+            })
+        });
+        match alias {
+            Some(alias) => s.push_str(&alias),
+            None => write_parameter_type(
+                &mut s,
+                schema,
+                options,
+                type_,
+                1,
+                None,
+                type_cache.as_deref_mut(),
+            )
+            .expect("Expected writing to a String to succeed"),
+        }
+        s.push_str(",\n");
     }
     s.push_str("};");
     s
@@ -1024,15 +1215,26 @@ fn write_optional_description(
 
 pub(crate) fn print_javascript_type_declaration<T: Display + Ord + Debug>(
     type_annotation: &TypeAnnotation<T>,
+) -> String {
+    print_wrapped_javascript_type_declaration(type_annotation, None)
+}
+
+/// Like print_javascript_type_declaration, but nullable types are wrapped in the
+/// nullable type wrapper (e.g. `Maybe<T>`), if there is one.
+pub(crate) fn print_wrapped_javascript_type_declaration<T: Display + Ord + Debug>(
+    type_annotation: &TypeAnnotation<T>,
+    nullable_type_wrapper: Option<&str>,
 ) -> String {
     let mut s = String::new();
-    print_javascript_type_declaration_impl(type_annotation, &mut s);
+    print_javascript_type_declaration_impl(type_annotation, nullable_type_wrapper, &mut s, false);
     s
 }
 
 fn print_javascript_type_declaration_impl<T: Display + Ord + Debug>(
     type_annotation: &TypeAnnotation<T>,
+    nullable_type_wrapper: Option<&str>,
     s: &mut String,
+    is_nested: bool,
 ) {
     match &type_annotation {
         TypeAnnotation::Scalar(scalar) => {
@@ -1044,7 +1246,19 @@ fn print_javascript_type_declaration_impl<T: Display + Ord + Debug>(
             }
 
             if union_type_annotation.variants.len() > 1 || union_type_annotation.nullable {
-                s.push('(');
+                // A nullable type that is not combined with anything else, e.g.
+                // `string | null`, does not need to be parenthesized.
+                let parenthesize = is_nested
+                    || union_type_annotation.variants.len() > 1
+                    || !union_type_annotation.nullable;
+                match nullable_type_wrapper {
+                    Some(nullable_type_wrapper) if union_type_annotation.nullable => {
+                        s.push_str(nullable_type_wrapper);
+                        s.push('<');
+                    }
+                    _ if parenthesize => s.push('('),
+                    _ => {}
+                }
                 for (index, variant) in union_type_annotation.variants.iter().enumerate() {
                     if index != 0 {
                         s.push_str(" | ");
@@ -1056,15 +1270,25 @@ fn print_javascript_type_declaration_impl<T: Display + Ord + Debug>(
                         }
                         UnionVariant::Plural(type_annotation) => {
                             s.push_str("ReadonlyArray<");
-                            print_javascript_type_declaration_impl(type_annotation, s);
+                            print_javascript_type_declaration_impl(
+                                type_annotation,
+                                nullable_type_wrapper,
+                                s,
+                                true,
+                            );
                             s.push('>');
                         }
                     }
                 }
-                if union_type_annotation.nullable {
-                    s.push_str(" | null");
+                match nullable_type_wrapper {
+                    Some(_) if union_type_annotation.nullable => s.push('>'),
+                    Some(_) => s.push(')'),
+                    None if union_type_annotation.nullable && parenthesize => {
+                        s.push_str(" | null)")
+                    }
+                    None if union_type_annotation.nullable => s.push_str(" | null"),
+                    None => s.push(')'),
                 }
-                s.push(')');
             } else {
                 let variant = union_type_annotation
                     .variants
@@ -1076,7 +1300,12 @@ fn print_javascript_type_declaration_impl<T: Display + Ord + Debug>(
                     }
                     UnionVariant::Plural(type_annotation) => {
                         s.push_str("ReadonlyArray<");
-                        print_javascript_type_declaration_impl(type_annotation, s);
+                        print_javascript_type_declaration_impl(
+                            type_annotation,
+                            nullable_type_wrapper,
+                            s,
+                            true,
+                        );
                         s.push('>');
                     }
                 }
@@ -1084,7 +1313,7 @@ fn print_javascript_type_declaration_impl<T: Display + Ord + Debug>(
         }
         TypeAnnotation::Plural(type_annotation) => {
             s.push_str("ReadonlyArray<");
-            print_javascript_type_declaration_impl(type_annotation, s);
+            print_javascript_type_declaration_impl(type_annotation, nullable_type_wrapper, s, true);
             s.push('>');
         }
     }
@@ -1135,3 +1364,207 @@ pub fn get_provided_arguments<'a>(
         })
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use common_lang_types::{Location, Span, WithLocation, WithSpan};
+    use graphql_lang_types::{
+        GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation, GraphQLTypeAnnotation,
+    };
+    use graphql_network_protocol::GraphqlSchema;
+    use intern::string_key::Intern;
+    use isograph_config::{CompilerConfigOptions, VoidInNullableParameterTypesOption};
+    use isograph_lang_types::{
+        ServerEntityId, TypeAnnotation, UnionTypeAnnotation, UnionVariant, VariableDefinition,
+    };
+
+    use super::{
+        generate_parameters, print_javascript_type_declaration,
+        print_wrapped_javascript_type_declaration,
+    };
+    use crate::format_parameter_type::{TypeAliasBuilder, TypeFormatOptions};
+
+    fn config_options() -> CompilerConfigOptions {
+        CompilerConfigOptions {
+            void_in_nullable_parameter_types:
+                VoidInNullableParameterTypesOption::ExcludeVoidFromNullableParameterTypes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn described_arguments_are_documented() {
+        let schema = GraphqlSchema::new();
+        let string_type = GraphQLNamedTypeAnnotation(WithSpan::new(
+            ServerEntityId::Scalar(schema.server_entity_data.string_type_id),
+            Span::todo_generated(),
+        ));
+        let argument_definitions = [
+            VariableDefinition {
+                description: Some(WithSpan::new(
+                    "The id of the pet".intern().into(),
+                    Span::todo_generated(),
+                )),
+                name: WithLocation::new("id".intern().into(), Location::generated()),
+                type_: GraphQLTypeAnnotation::NonNull(Box::new(
+                    GraphQLNonNullTypeAnnotation::Named(string_type),
+                )),
+                default_value: None,
+                deprecation_reason: None,
+            },
+            VariableDefinition {
+                description: None,
+                name: WithLocation::new("name".intern().into(), Location::generated()),
+                type_: GraphQLTypeAnnotation::Named(string_type),
+                default_value: None,
+                deprecation_reason: None,
+            },
+        ];
+
+        assert_eq!(
+            generate_parameters(
+                &schema,
+                argument_definitions.iter(),
+                &TypeFormatOptions::new(&config_options()),
+                None,
+                None,
+            ),
+            "{\n  /**\nThe id of the pet\n  */\n  readonly id: string,\n  readonly name?: string | null,\n};"
+        );
+    }
+
+    #[test]
+    fn nullable_reader_types_are_only_parenthesized_when_nested() {
+        let nullable_list_of_nullable_strings =
+            TypeAnnotation::Union(UnionTypeAnnotation::new_nullable(UnionVariant::Plural(
+                TypeAnnotation::nullable_scalar("string"),
+            )));
+
+        assert_eq!(
+            print_javascript_type_declaration(&TypeAnnotation::nullable_scalar("string")),
+            "string | null"
+        );
+        assert_eq!(
+            print_javascript_type_declaration(&TypeAnnotation::list_of(
+                TypeAnnotation::nullable_scalar("string")
+            )),
+            "ReadonlyArray<(string | null)>"
+        );
+        assert_eq!(
+            print_javascript_type_declaration(&nullable_list_of_nullable_strings),
+            "ReadonlyArray<(string | null)> | null"
+        );
+        assert_eq!(
+            print_wrapped_javascript_type_declaration(
+                &nullable_list_of_nullable_strings,
+                Some("Maybe")
+            ),
+            "Maybe<ReadonlyArray<Maybe<string>>>"
+        );
+    }
+
+    #[test]
+    fn arguments_are_emitted_in_declaration_order() {
+        let schema = GraphqlSchema::new();
+        // Neither alphabetical, nor the order in which the names are interned, so
+        // sorting by name or by interned id (or collecting into a HashMap with
+        // eight entries) would reorder them.
+        let names = [
+            "zebra", "apple", "mango", "banana", "kiwi", "cherry", "lime", "fig",
+        ];
+        let mut interned_names = names;
+        interned_names.sort();
+        interned_names.reverse();
+        for name in interned_names {
+            name.intern();
+        }
+        let argument_definitions = names.map(|name| VariableDefinition {
+            description: None,
+            name: WithLocation::new(name.intern().into(), Location::generated()),
+            type_: GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::Named(
+                GraphQLNamedTypeAnnotation(WithSpan::new(
+                    ServerEntityId::Scalar(schema.server_entity_data.string_type_id),
+                    Span::todo_generated(),
+                )),
+            ))),
+            default_value: None,
+            deprecation_reason: None,
+        });
+
+        assert_eq!(
+            generate_parameters(
+                &schema,
+                argument_definitions.iter(),
+                &TypeFormatOptions::new(&config_options()),
+                None,
+                None,
+            ),
+            "{\n  readonly zebra: string,\n  readonly apple: string,\n  \
+            readonly mango: string,\n  readonly banana: string,\n  \
+            readonly kiwi: string,\n  readonly cherry: string,\n  \
+            readonly lime: string,\n  readonly fig: string,\n};"
+        );
+    }
+
+    #[test]
+    fn repeated_parameter_types_are_hoisted_into_type_aliases() {
+        let schema = GraphqlSchema::new();
+        let named_type = |entity_id, span_start| {
+            GraphQLNamedTypeAnnotation(WithSpan::new(
+                ServerEntityId::Scalar(entity_id),
+                Span::new(span_start, span_start + 6),
+            ))
+        };
+        let string_id = schema.server_entity_data.string_type_id;
+        let int_id = schema.server_entity_data.int_type_id;
+        // The nullable strings are written in different places, but have the same shape
+        let argument_definitions = [
+            (
+                "name",
+                GraphQLTypeAnnotation::Named(named_type(string_id, 0)),
+            ),
+            (
+                "id",
+                GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::Named(
+                    named_type(string_id, 10),
+                ))),
+            ),
+            (
+                "nickname",
+                GraphQLTypeAnnotation::Named(named_type(string_id, 20)),
+            ),
+            (
+                "count",
+                GraphQLTypeAnnotation::Named(named_type(int_id, 30)),
+            ),
+        ]
+        .map(|(name, type_)| VariableDefinition {
+            description: None,
+            name: WithLocation::new(name.intern().into(), Location::generated()),
+            type_,
+            default_value: None,
+            deprecation_reason: None,
+        });
+
+        let mut type_aliases = TypeAliasBuilder::new();
+        assert_eq!(
+            generate_parameters(
+                &schema,
+                argument_definitions.iter(),
+                &TypeFormatOptions::new(&config_options()),
+                None,
+                Some(&mut type_aliases),
+            ),
+            "{\n  readonly name?: Union0,\n  readonly id: string,\n  \
+            readonly nickname?: Union0,\n  readonly count?: number | null,\n};"
+        );
+        assert_eq!(
+            type_aliases.declarations(false),
+            "type Union0 = string | null;\n"
+        );
+        assert_eq!(
+            type_aliases.declarations(true),
+            "export type Union0 = string | null;\n"
+        );
+    }
+}
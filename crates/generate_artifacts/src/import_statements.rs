@@ -1,7 +1,8 @@
 use std::collections::BTreeSet;
 
-use common_lang_types::ObjectTypeAndFieldName;
+use common_lang_types::{GraphQLScalarTypeName, ObjectTypeAndFieldName};
 use isograph_config::GenerateFileExtensionsOption;
+use isograph_schema::JavascriptImport;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum ImportedFileCategory {
@@ -25,6 +26,14 @@ pub(crate) type ParamTypeImports = BTreeSet<ObjectTypeAndFieldName>;
 pub(crate) type LinkImports = bool;
 pub(crate) type UpdatableImports = bool;
 
+/// The scalars referenced by a generated type whose types are declared elsewhere,
+/// i.e. in another module (see scalar_imports) or in the enums artifact.
+#[derive(Debug, Default)]
+pub(crate) struct ScalarTypeImports {
+    pub scalar_imports: BTreeSet<JavascriptImport>,
+    pub enum_scalars: BTreeSet<GraphQLScalarTypeName>,
+}
+
 pub(crate) fn reader_imports_to_import_statement(
     reader_imports: &ReaderImports,
     file_extensions: GenerateFileExtensionsOption,
@@ -9,4 +9,10 @@ mod normalization_ast_text;
 mod reader_ast;
 mod refetch_reader_artifact;
 
+pub use format_parameter_type::{
+    format_indented_parameter_type, format_parameter_type, format_type_guards,
+    write_parameter_type, CamelCaseFieldNames, FormattedTypeCache, JavascriptTypeFor,
+    NoTypeRenderOverride, SourceMapBuilder, SourceMapEntry, TypeAliasBuilder, TypeFormatOptions,
+    TypeRenderOverride,
+};
 pub use generate_artifacts::get_artifact_path_and_content;
@@ -15,14 +15,17 @@ use isograph_schema::{
 };
 
 use crate::{
+    format_parameter_type::TypeFormatOptions,
     generate_artifacts::{get_serialized_field_arguments, ReaderAst},
     import_statements::{ImportedFileCategory, ReaderImports},
 };
 
 // Can we do this when visiting the client field in when generating entrypoints?
+#[allow(clippy::too_many_arguments)]
 fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
     selection: &WithSpan<ValidatedSelection>,
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     indentation_level: u8,
     reader_imports: &mut ReaderImports,
     // TODO use this to generate usedRefetchQueries
@@ -35,6 +38,7 @@ fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
             match scalar_field_selection.associated_data {
                 DefinitionLocation::Server(_) => server_defined_scalar_field_ast_node(
                     scalar_field_selection,
+                    options,
                     indentation_level,
                     initial_variable_context,
                 ),
@@ -60,6 +64,7 @@ fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
 
                     let inner_reader_ast = generate_reader_ast_with_path(
                         schema,
+                        options,
                         client_pointer.refetch_strategy.refetch_selection_set(),
                         indentation_level + 1,
                         reader_imports,
@@ -70,6 +75,7 @@ fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
 
                     linked_field_ast_node(
                         schema,
+                        options,
                         linked_field_selection,
                         indentation_level,
                         inner_reader_ast,
@@ -110,6 +116,7 @@ fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
 
                     let inner_reader_ast = generate_reader_ast_with_path(
                         schema,
+                        options,
                         &linked_field_selection.selection_set,
                         indentation_level + 1,
                         reader_imports,
@@ -122,6 +129,7 @@ fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
 
                     linked_field_ast_node(
                         schema,
+                        options,
                         linked_field_selection,
                         indentation_level,
                         inner_reader_ast,
@@ -136,6 +144,7 @@ fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
 
 fn linked_field_ast_node<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     linked_field: &ValidatedObjectSelection,
     indentation_level: u8,
     inner_reader_ast: ReaderAst,
@@ -145,7 +154,12 @@ fn linked_field_ast_node<TNetworkProtocol: NetworkProtocol>(
     let name = linked_field.name.item;
     let alias = linked_field
         .reader_alias
-        .map(|x| format!("\"{}\"", x.item))
+        .map(|x| x.item.to_string())
+        .or_else(|| match linked_field.associated_data {
+            DefinitionLocation::Server(_) => options.renamed_server_field(name.into()),
+            DefinitionLocation::Client(_) => None,
+        })
+        .map(|alias| format!("\"{alias}\""))
         .unwrap_or("null".to_string());
 
     let arguments = get_serialized_field_arguments(
@@ -455,6 +469,7 @@ fn loadably_selected_field_ast_node<TNetworkProtocol: NetworkProtocol>(
 
     let (reader_ast, additional_reader_imports) = generate_reader_ast(
         schema,
+        &TypeFormatOptions::default(),
         client_field
             .refetch_strategy
             .as_ref()
@@ -487,15 +502,18 @@ fn loadably_selected_field_ast_node<TNetworkProtocol: NetworkProtocol>(
     )
 }
 
-fn server_defined_scalar_field_ast_node(
+fn server_defined_scalar_field_ast_node<TNetworkProtocol: NetworkProtocol>(
     scalar_field_selection: &ValidatedScalarSelection,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     indentation_level: u8,
     initial_variable_context: &VariableContext,
 ) -> String {
     let field_name = scalar_field_selection.name.item;
     let alias = scalar_field_selection
         .reader_alias
-        .map(|x| format!("\"{}\"", x.item))
+        .map(|x| x.item.to_string())
+        .or_else(|| options.renamed_server_field(field_name.into()))
+        .map(|alias| format!("\"{alias}\""))
         .unwrap_or("null".to_string());
     let arguments = get_serialized_field_arguments(
         &transform_arguments_with_child_context(
@@ -525,8 +543,10 @@ fn server_defined_scalar_field_ast_node(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn generate_reader_ast_with_path<'schema, TNetworkProtocol: NetworkProtocol>(
     schema: &'schema Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     selection_set: &'schema [WithSpan<ValidatedSelection>],
     indentation_level: u8,
     nested_client_field_imports: &mut ReaderImports,
@@ -540,6 +560,7 @@ fn generate_reader_ast_with_path<'schema, TNetworkProtocol: NetworkProtocol>(
         let s = generate_reader_ast_node(
             item,
             schema,
+            options,
             indentation_level + 1,
             nested_client_field_imports,
             root_refetched_paths,
@@ -607,8 +628,13 @@ fn find_imperatively_fetchable_query_index(
         )
 }
 
+/// Server fields that are renamed by options.type_render_override are read under
+/// their new name. Reader ASTs whose data is only read by Isograph (e.g. to refetch
+/// a field) should be generated with the default options, so that no field is
+/// renamed.
 pub(crate) fn generate_reader_ast<'schema, TNetworkProtocol: NetworkProtocol>(
     schema: &'schema Schema<TNetworkProtocol>,
+    options: &TypeFormatOptions<'_, TNetworkProtocol>,
     selection_set: &'schema [WithSpan<ValidatedSelection>],
     indentation_level: u8,
     // N.B. this is not root_refetched_paths when we're generating an entrypoint :(
@@ -619,6 +645,7 @@ pub(crate) fn generate_reader_ast<'schema, TNetworkProtocol: NetworkProtocol>(
     let mut client_field_imports = BTreeSet::new();
     let reader_ast = generate_reader_ast_with_path(
         schema,
+        options,
         selection_set,
         indentation_level,
         &mut client_field_imports,
@@ -8,6 +8,7 @@ use isograph_schema::{
 };
 
 use crate::{
+    format_parameter_type::TypeFormatOptions,
     generate_artifacts::{
         generate_output_type, ClientFieldFunctionImportStatement, REFETCH_READER_FILE_NAME,
         RESOLVER_OUTPUT_TYPE_FILE_NAME,
@@ -32,6 +33,7 @@ pub(crate) fn generate_refetch_reader_artifact<TNetworkProtocol: NetworkProtocol
 
     let (reader_ast, reader_imports) = generate_reader_ast(
         schema,
+        &TypeFormatOptions::default(),
         if was_selected_loadably {
             // TODO model this better
             client_field
@@ -0,0 +1,485 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
+
+use common_lang_types::{AbsolutePathAndRelativePath, TextSource};
+use generate_artifacts::get_artifact_path_and_content;
+use graphql_network_protocol::GraphQLNetworkProtocol;
+use intern::string_key::Intern;
+use isograph_compiler::create_schema;
+use isograph_config::{
+    CompilerConfig, CompilerConfigOptions, EnumEmitStyle, FieldNameTransform, OptionalFieldStyle,
+    ScalarImport, ScalarImports,
+};
+use isograph_lang_types::{IsoLiteralsSource, SchemaSource};
+use pico::Database;
+
+/// Compiles the schema and a single source file containing iso literals, and
+/// returns the content of each generated artifact, keyed by e.g.
+/// `Pet/PetSummary/param_type.ts`.
+fn generate_artifacts_from_sources(
+    sdl: &str,
+    source: &str,
+    options: CompilerConfigOptions,
+) -> HashMap<String, String> {
+    let mut db = Database::new();
+    let schema_relative_path = "schema.graphql".intern().into();
+    let schema_source_id = db.set(SchemaSource {
+        relative_path: schema_relative_path,
+        content: sdl.to_string(),
+        text_source: TextSource {
+            relative_path_to_source_file: schema_relative_path,
+            span: None,
+            current_working_directory: "cwd".intern().into(),
+        },
+    });
+    let source_relative_path = "src/components/PetSummary.tsx".intern().into();
+    let iso_literals_source_id = db.set(IsoLiteralsSource {
+        relative_path: source_relative_path,
+        content: source.to_string(),
+    });
+    let config = CompilerConfig {
+        config_location: PathBuf::from("isograph.config.json"),
+        project_root: PathBuf::from("src"),
+        artifact_directory: AbsolutePathAndRelativePath {
+            absolute_path: PathBuf::from("src/components/__isograph"),
+            relative_path: "src/components/__isograph".intern().into(),
+        },
+        schema: AbsolutePathAndRelativePath {
+            absolute_path: PathBuf::from("schema.graphql"),
+            relative_path: schema_relative_path,
+        },
+        schema_extensions: vec![],
+        options,
+        current_working_directory: "cwd".intern().into(),
+    };
+    let (schema, _) = create_schema::<GraphQLNetworkProtocol>(
+        &db,
+        &(schema_source_id, BTreeMap::new()),
+        &HashMap::from([(source_relative_path, iso_literals_source_id)]),
+        &config,
+    )
+    .expect("Expected schema to be created");
+
+    get_artifact_path_and_content(&schema, &config)
+        .into_iter()
+        .map(|artifact| {
+            let path = match artifact.type_and_field {
+                Some(type_and_field) => format!(
+                    "{}/{}/{}",
+                    type_and_field.type_name, type_and_field.field_name, artifact.file_name
+                ),
+                None => artifact.file_name.to_string(),
+            };
+            (path, artifact.file_content)
+        })
+        .collect()
+}
+
+const PET_SCHEMA: &str = "type Query { pet(id: ID!): Pet }\n\
+    type Pet { id: ID! name: String! nickname: String }";
+
+const PET_SUMMARY_SOURCE: &str = "export const PetSummary = iso(`\n\
+    field Pet.PetSummary($greeting: String, $count: Int!) {\n\
+      name\n\
+      nickname\n\
+    }\n\
+    `)(function PetSummary() {});\n\
+    export const PetDetail = iso(`\n\
+    field Query.PetDetail($id: ID!) {\n\
+      pet(id: $id) {\n\
+        PetSummary(count: 1)\n\
+      }\n\
+    }\n\
+    `)(function PetDetail() {});\n\
+    iso(`entrypoint Query.PetDetail`);\n";
+
+#[test]
+fn readonly_properties_apply_to_reader_and_parameter_types() {
+    let artifacts = generate_artifacts_from_sources(
+        PET_SCHEMA,
+        PET_SUMMARY_SOURCE,
+        CompilerConfigOptions {
+            readonly_properties: false,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        artifacts["Pet/PetSummary/param_type.ts"],
+        "import type { Pet__PetSummary__parameters } from './parameters_type';\n\n\
+        export type Pet__PetSummary__param = {\n  \
+          data: {\n    \
+            name: string,\n    \
+            nickname: string | null,\n  \
+          },\n  \
+          parameters: Pet__PetSummary__parameters,\n\
+        };\n"
+    );
+    assert_eq!(
+        artifacts["Pet/PetSummary/parameters_type.ts"],
+        "export type Pet__PetSummary__parameters = {\n  \
+          greeting?: string | null | void,\n  \
+          count: number,\n\
+        };\n"
+    );
+}
+
+#[test]
+fn optional_field_style_applies_to_reader_and_parameter_types() {
+    let artifacts = generate_artifacts_from_sources(
+        PET_SCHEMA,
+        PET_SUMMARY_SOURCE,
+        CompilerConfigOptions {
+            optional_field_style: Some(OptionalFieldStyle::QuestionMark),
+            ..Default::default()
+        },
+    );
+
+    assert!(artifacts["Pet/PetSummary/param_type.ts"].contains("    readonly nickname?: string,\n"));
+    assert!(
+        artifacts["Pet/PetSummary/parameters_type.ts"].contains("  readonly greeting?: string,\n")
+    );
+    assert!(artifacts["Query/PetDetail/param_type.ts"].contains(
+        "    readonly pet?: {\n      \
+          readonly PetSummary: Pet__PetSummary__output_type,\n    \
+        },\n"
+    ));
+}
+
+#[test]
+fn enums_are_declared_once_and_imported() {
+    let artifacts = generate_artifacts_from_sources(
+        "type Query { pet(id: ID!): Pet }\n\
+        scalar Status\n\
+        type Pet { id: ID! name: String! }",
+        "export const PetSummary = iso(`\n\
+        field Pet.PetSummary($status: Status!, $previousStatus: Status) {\n\
+          name\n\
+        }\n\
+        `)(function PetSummary() {});\n\
+        export const PetDetail = iso(`\n\
+        field Query.PetDetail($id: ID!) {\n\
+          pet(id: $id) {\n\
+            PetSummary(status: \"ACTIVE\")\n\
+          }\n\
+        }\n\
+        `)(function PetDetail() {});\n\
+        iso(`entrypoint Query.PetDetail`);\n",
+        CompilerConfigOptions {
+            scalar_literal_types: HashMap::from([(
+                "Status".intern().into(),
+                vec!["ACTIVE".to_string(), "INACTIVE".to_string()],
+            )]),
+            enum_emit_style: EnumEmitStyle::ConstEnum,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        artifacts["enums.ts"],
+        "export const enum Status {\n  \
+          ACTIVE = \"ACTIVE\",\n  \
+          INACTIVE = \"INACTIVE\",\n\
+        }\n"
+    );
+    assert_eq!(
+        artifacts["Pet/PetSummary/parameters_type.ts"],
+        "import type { Status } from '../../enums';\n\
+        export type Pet__PetSummary__parameters = {\n  \
+          readonly status: Status,\n  \
+          readonly previousStatus?: Status | null | void,\n\
+        };\n"
+    );
+}
+
+#[test]
+fn reader_types_format_scalars_like_parameter_types() {
+    let artifacts = generate_artifacts_from_sources(
+        "type Query { pet(id: ID!): Pet }\n\
+        scalar Status\n\
+        scalar Decimal\n\
+        type Pet { id: ID! status: Status! weight: Decimal }",
+        "export const PetSummary = iso(`\n\
+        field Pet.PetSummary {\n\
+          status\n\
+          weight\n\
+        }\n\
+        `)(function PetSummary() {});\n",
+        CompilerConfigOptions {
+            scalar_literal_types: HashMap::from([(
+                "Status".intern().into(),
+                vec!["ACTIVE".to_string(), "INACTIVE".to_string()],
+            )]),
+            enum_emit_style: EnumEmitStyle::ConstObject,
+            scalar_imports: ScalarImports::from([(
+                "Decimal".intern().into(),
+                ScalarImport {
+                    type_name: "Big".intern().into(),
+                    module_path: "big.js".intern().into(),
+                },
+            )]),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        artifacts["Pet/PetSummary/param_type.ts"],
+        "import type { Big } from 'big.js';\n\
+        import type { Status } from '../../enums';\n\n\
+        export type Pet__PetSummary__param = {\n  \
+          readonly data: {\n    \
+            readonly status: Status,\n    \
+            readonly weight: Big | null,\n  \
+          },\n  \
+          readonly parameters: Record<PropertyKey, never>,\n\
+        };\n"
+    );
+}
+
+#[test]
+fn scalar_map_types_apply_to_reader_types() {
+    let artifacts = generate_artifacts_from_sources(
+        "type Query { pet(id: ID!): Pet }\n\
+        scalar JSONObject\n\
+        type Pet { id: ID! metadata: JSONObject }",
+        "export const PetSummary = iso(`\n\
+        field Pet.PetSummary {\n\
+          metadata\n\
+        }\n\
+        `)(function PetSummary() {});\n",
+        CompilerConfigOptions {
+            scalar_map_types: HashMap::from([(
+                "JSONObject".intern().into(),
+                "unknown".to_string(),
+            )]),
+            ..Default::default()
+        },
+    );
+
+    assert!(artifacts["Pet/PetSummary/param_type.ts"]
+        .contains("    readonly metadata: Record<string, unknown> | null,\n"));
+}
+
+#[test]
+fn nullable_type_wrapper_applies_to_reader_types() {
+    let artifacts = generate_artifacts_from_sources(
+        PET_SCHEMA,
+        PET_SUMMARY_SOURCE,
+        CompilerConfigOptions {
+            nullable_type_wrapper: Some("Maybe".to_string()),
+            ..Default::default()
+        },
+    );
+
+    assert!(artifacts["Pet/PetSummary/param_type.ts"]
+        .contains("    readonly nickname: Maybe<string>,\n"));
+    assert!(artifacts["Pet/PetSummary/parameters_type.ts"]
+        .contains("  readonly greeting?: Maybe<string> | void,\n"));
+    assert!(artifacts["Query/PetDetail/param_type.ts"].contains(
+        "    readonly pet: Maybe<{\n      \
+          readonly PetSummary: Pet__PetSummary__output_type,\n    \
+        }>,\n"
+    ));
+}
+
+#[test]
+fn type_guards_are_emitted_behind_emit_type_guards() {
+    let schema = "type Query { node(id: ID!): Node }\n\
+        interface Node { id: ID! }\n\
+        type Pet implements Node { id: ID! }\n\
+        type User implements Node { id: ID! }";
+    let source = "export const PetSummary = iso(`\n\
+        field Pet.PetSummary {\n\
+          id\n\
+        }\n\
+        `)(function PetSummary() {});\n";
+
+    let artifacts =
+        generate_artifacts_from_sources(schema, source, CompilerConfigOptions::default());
+    assert!(!artifacts.contains_key("type_guards.ts"));
+
+    let artifacts = generate_artifacts_from_sources(
+        schema,
+        source,
+        CompilerConfigOptions {
+            emit_type_guards: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        artifacts["type_guards.ts"],
+        "export type PetShape = { readonly __typename: \"Pet\" };\n\
+        export type UserShape = { readonly __typename: \"User\" };\n\
+        export type NodeUnion = PetShape | UserShape;\n\
+        export function isPet(node: NodeUnion): node is PetShape {\n\
+        \x20 return node.__typename === \"Pet\";\n\
+        }\n\
+        export function isUser(node: NodeUnion): node is UserShape {\n\
+        \x20 return node.__typename === \"User\";\n\
+        }\n"
+    );
+}
+
+#[test]
+fn field_name_transform_renames_reader_output_and_reads_it_under_the_new_name() {
+    let schema = "type Query { pet(id: ID!): Pet }\n\
+        type Pet { id: ID! first_name: String! }";
+    let source = "export const PetSummary = iso(`\n\
+        field Pet.PetSummary {\n\
+          first_name\n\
+        }\n\
+        `)(function PetSummary() {});\n\
+        export const PetDetail = iso(`\n\
+        field Query.PetDetail {\n\
+          pet(id: \"1\") {\n\
+            PetSummary\n\
+          }\n\
+        }\n\
+        `)(function PetDetail() {});\n\
+        iso(`entrypoint Query.PetDetail`);\n";
+
+    let artifacts = generate_artifacts_from_sources(
+        schema,
+        source,
+        CompilerConfigOptions {
+            field_name_transform: FieldNameTransform::CamelCase,
+            ..Default::default()
+        },
+    );
+    assert!(artifacts["Pet/PetSummary/param_type.ts"].contains("readonly firstName: string,"));
+    assert!(artifacts["Pet/PetSummary/resolver_reader.ts"].contains(
+        "    kind: \"Scalar\",\n    \
+        fieldName: \"first_name\",\n    \
+        alias: \"firstName\",\n"
+    ));
+}
+
+#[test]
+fn repeated_parameter_types_are_declared_once_behind_hoist_type_aliases() {
+    let source = "export const PetSummary = iso(`\n\
+        field Pet.PetSummary($greeting: String, $count: Int!, $farewell: String) {\n\
+          name\n\
+        }\n\
+        `)(function PetSummary() {});\n\
+        export const PetDetail = iso(`\n\
+        field Query.PetDetail($id: ID!) {\n\
+          pet(id: $id) {\n\
+            PetSummary(count: 1)\n\
+          }\n\
+        }\n\
+        `)(function PetDetail() {});\n\
+        iso(`entrypoint Query.PetDetail`);\n";
+
+    let artifacts =
+        generate_artifacts_from_sources(PET_SCHEMA, source, CompilerConfigOptions::default());
+    assert_eq!(
+        artifacts["Pet/PetSummary/parameters_type.ts"],
+        "export type Pet__PetSummary__parameters = {\n  \
+          readonly greeting?: string | null | void,\n  \
+          readonly count: number,\n  \
+          readonly farewell?: string | null | void,\n\
+        };\n"
+    );
+
+    let artifacts = generate_artifacts_from_sources(
+        PET_SCHEMA,
+        source,
+        CompilerConfigOptions {
+            hoist_type_aliases: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        artifacts["Pet/PetSummary/parameters_type.ts"],
+        "type Union0 = string | null | void;\n\
+        export type Pet__PetSummary__parameters = {\n  \
+          readonly greeting?: Union0,\n  \
+          readonly count: number,\n  \
+          readonly farewell?: Union0,\n\
+        };\n"
+    );
+}
+
+#[test]
+fn hoisted_type_aliases_are_exported_behind_export_declarations() {
+    let source = "export const PetSummary = iso(`\n\
+        field Pet.PetSummary($greeting: String, $farewell: String) {\n\
+          name\n\
+        }\n\
+        `)(function PetSummary() {});\n\
+        export const PetDetail = iso(`\n\
+        field Query.PetDetail($id: ID!) {\n\
+          pet(id: $id) {\n\
+            PetSummary\n\
+          }\n\
+        }\n\
+        `)(function PetDetail() {});\n\
+        iso(`entrypoint Query.PetDetail`);\n";
+
+    let artifacts = generate_artifacts_from_sources(
+        PET_SCHEMA,
+        source,
+        CompilerConfigOptions {
+            hoist_type_aliases: true,
+            export_declarations: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        artifacts["Pet/PetSummary/parameters_type.ts"],
+        "export type Union0 = string | null | void;\n\
+        export type Pet__PetSummary__parameters = {\n  \
+          readonly greeting?: Union0,\n  \
+          readonly farewell?: Union0,\n\
+        };\n"
+    );
+}
+
+#[test]
+fn id_fields_are_branded_with_their_type_behind_brand_id_types() {
+    let schema = "type Query { user(id: ID!): User users(filter: UserFilter): [User!]! }\n\
+        type User { id: ID! name: String! }\n\
+        input UserFilter { ownerId: ID }";
+    let source = "export const UserSummary = iso(`\n\
+        field User.UserSummary($filter: UserFilter, $id: ID!) {\n\
+          id\n\
+          name\n\
+        }\n\
+        `)(function UserSummary() {});\n\
+        export const UserDetail = iso(`\n\
+        field Query.UserDetail($id: ID!) {\n\
+          user(id: $id) {\n\
+            UserSummary(id: $id)\n\
+          }\n\
+        }\n\
+        `)(function UserDetail() {});\n\
+        iso(`entrypoint Query.UserDetail`);\n";
+
+    let artifacts = generate_artifacts_from_sources(
+        schema,
+        source,
+        CompilerConfigOptions {
+            brand_id_types: true,
+            ..Default::default()
+        },
+    );
+    assert!(artifacts["User/UserSummary/param_type.ts"]
+        .contains("readonly id: string & { readonly __brand: 'UserID' },\n"));
+    // Parameters, including the fields of input objects, are not branded, so
+    // that the id that was read can be passed.
+    assert_eq!(
+        artifacts["User/UserSummary/parameters_type.ts"],
+        "export type User__UserSummary__parameters = {\n  \
+          readonly filter?: {\n    \
+            readonly ownerId?: string | null,\n  \
+          } | null | void,\n  \
+          readonly id: string,\n\
+        };\n"
+    );
+
+    let artifacts =
+        generate_artifacts_from_sources(schema, source, CompilerConfigOptions::default());
+    assert!(artifacts["User/UserSummary/param_type.ts"].contains("readonly id: string,\n"));
+}
@@ -8,6 +8,7 @@ use common_lang_types::{
     GraphQLObjectTypeName, GraphQLScalarTypeName, GraphQLUnionTypeName, InputTypeName,
     InputValueName, ServerSelectableName, UnvalidatedTypeName, WithLocation, WithSpan,
 };
+use intern::{string_key::Intern, Lookup};
 use strum::EnumString;
 
 // also Schema
@@ -96,9 +97,9 @@ pub enum GraphQLTypeSystemExtensionOrDefinition {
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub enum GraphQLTypeSystemExtension {
     ObjectTypeExtension(GraphQLObjectTypeExtension),
+    InterfaceTypeExtension(GraphQLInterfaceTypeExtension),
+    UnionTypeExtension(GraphQLUnionTypeExtension),
     // ScalarTypeExtension
-    // InterfaceTypeExtension
-    // UnionTypeExtension
     // EnumTypeExtension
     // InputObjectTypeExtension
     // SchemaExtension
@@ -110,6 +111,18 @@ impl From<GraphQLObjectTypeExtension> for GraphQLTypeSystemExtension {
     }
 }
 
+impl From<GraphQLInterfaceTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(interface_type_extension: GraphQLInterfaceTypeExtension) -> Self {
+        Self::InterfaceTypeExtension(interface_type_extension)
+    }
+}
+
+impl From<GraphQLUnionTypeExtension> for GraphQLTypeSystemExtension {
+    fn from(union_type_extension: GraphQLUnionTypeExtension) -> Self {
+        Self::UnionTypeExtension(union_type_extension)
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub struct GraphQLObjectTypeDefinition {
     pub description: Option<WithSpan<DescriptionValue>>,
@@ -143,6 +156,14 @@ pub struct GraphQLInterfaceTypeDefinition {
     pub fields: Vec<WithLocation<GraphQLFieldDefinition>>,
 }
 
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct GraphQLInterfaceTypeExtension {
+    pub name: WithLocation<GraphQLInterfaceTypeName>,
+    pub interfaces: Vec<WithLocation<GraphQLInterfaceTypeName>>,
+    pub directives: Vec<GraphQLDirective<GraphQLConstantValue>>,
+    pub fields: Vec<WithLocation<GraphQLFieldDefinition>>,
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub struct GraphQLInputObjectTypeDefinition {
     pub description: Option<WithSpan<DescriptionValue>>,
@@ -217,6 +238,13 @@ pub struct GraphQLUnionTypeDefinition {
     pub union_member_types: Vec<WithLocation<GraphQLObjectTypeName>>,
 }
 
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct GraphQLUnionTypeExtension {
+    pub name: WithLocation<GraphQLUnionTypeName>,
+    pub directives: Vec<GraphQLDirective<GraphQLConstantValue>>,
+    pub union_member_types: Vec<WithLocation<GraphQLObjectTypeName>>,
+}
+
 impl From<GraphQLInputValueDefinition> for GraphQLFieldDefinition {
     fn from(value: GraphQLInputValueDefinition) -> Self {
         Self {
@@ -276,6 +304,26 @@ pub struct GraphQLInputValueDefinition {
     pub directives: Vec<GraphQLDirective<GraphQLConstantValue>>,
 }
 
+impl GraphQLInputValueDefinition {
+    /// If this input value is marked @deprecated, the reason it was deprecated. Per
+    /// the GraphQL spec, the reason defaults to "No longer supported".
+    pub fn deprecation_reason(&self) -> Option<DescriptionValue> {
+        let deprecated_directive = self
+            .directives
+            .iter()
+            .find(|directive| directive.name.item.lookup() == "deprecated")?;
+        let reason = deprecated_directive
+            .arguments
+            .iter()
+            .find(|argument| argument.name.item.lookup() == "reason")
+            .and_then(|argument| argument.value.item.as_string());
+        Some(match reason {
+            Some(reason) => reason.lookup().intern().into(),
+            None => "No longer supported".intern().into(),
+        })
+    }
+}
+
 impl fmt::Display for GraphQLInputValueDefinition {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}: {}", self.name, self.type_)?;
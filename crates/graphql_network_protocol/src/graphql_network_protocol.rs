@@ -1,31 +1,27 @@
-use std::{collections::BTreeMap, error::Error};
+use std::{collections::BTreeMap, error::Error, ops::Deref};
 
-use common_lang_types::{
-    DirectiveName, QueryOperationName, QueryText, RelativePathToSourceFile, WithLocation,
-};
-use graphql_lang_types::{from_graphql_directive, DeserializationError};
-use intern::string_key::Intern;
+use common_lang_types::{QueryOperationName, QueryText, RelativePathToSourceFile};
+use isograph_config::{CompilerConfigOptions, DEFAULT_MAX_ERRORS};
 use isograph_lang_types::SchemaSource;
 use isograph_schema::{
-    CreateAdditionalFieldsError, ExposeAsFieldToInsert, MergedSelectionMap, NetworkProtocol,
-    ProcessTypeSystemDocumentOutcome, RootOperationName, Schema, ValidatedVariableDefinition,
+    ExposeAsFieldToInsert, MergedSelectionMap, NetworkProtocol, ProcessTypeSystemDocumentOutcome,
+    RootOperationName, Schema, SdlKeyword, ServerObjectEntity, ValidatedVariableDefinition,
 };
-use lazy_static::lazy_static;
 use pico::{Database, SourceId};
 
 use crate::{
     parse_graphql_schema,
     process_type_system_definition::{
-        process_graphql_type_extension_document, process_graphql_type_system_document,
-        ProcessGraphqlTypeSystemDefinitionError, QUERY_TYPE,
+        default_query_object, insert_extension_fields, known_field_directives,
+        parse_expose_field_directive, process_graphql_type_extension_document,
+        process_graphql_type_system_document, validate_field_directives,
+        validate_no_input_types_in_output_position, validate_node_implementors_have_id_fields,
+        ExtensionsToInsert, ProcessGraphqlTypeSystemDefinitionError, EXPOSE_FIELD_DIRECTIVE,
+        QUERY_TYPE, REFETCH_FIELD_NAME,
     },
     query_text::generate_query_text,
 };
 
-lazy_static! {
-    static ref EXPOSE_FIELD_DIRECTIVE: DirectiveName = "exposeField".intern().into();
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash, Default)]
 pub struct GraphQLNetworkProtocol {}
 
@@ -40,39 +36,81 @@ impl NetworkProtocol for GraphQLNetworkProtocol {
     fn parse_and_process_type_system_documents(
         db: &Database,
         sources: &Self::Sources,
+        options: &CompilerConfigOptions,
     ) -> Result<ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol>, Box<dyn Error>> {
         let (schema_source_id, schema_extension_sources) = sources;
+        let refetch_field_name = options.refetch_field_name.unwrap_or(*REFETCH_FIELD_NAME);
+        let max_errors = options.max_errors.unwrap_or(DEFAULT_MAX_ERRORS);
 
         let (type_system_document, type_system_extension_documents) =
             parse_graphql_schema(db, *schema_source_id, schema_extension_sources).to_owned()?;
+        let known_field_directives = known_field_directives(
+            &type_system_document,
+            type_system_extension_documents
+                .values()
+                .map(|document| document.deref()),
+        );
 
         let (mut result, mut directives, mut refetch_fields) =
-            process_graphql_type_system_document(type_system_document.to_owned())?;
+            process_graphql_type_system_document(
+                type_system_document.to_owned(),
+                refetch_field_name,
+                max_errors,
+            )?;
+        let mut extensions = ExtensionsToInsert::new();
 
         for type_system_extension_document in type_system_extension_documents.values() {
-            let (outcome, objects_and_directives, new_refetch_fields) =
-                process_graphql_type_extension_document(type_system_extension_document.to_owned())?;
+            let (outcome, objects_and_directives, new_refetch_fields, new_extensions) =
+                process_graphql_type_extension_document(
+                    type_system_extension_document.to_owned(),
+                    refetch_field_name,
+                    max_errors,
+                )?;
 
             for (name, new_directives) in objects_and_directives {
                 directives.entry(name).or_default().extend(new_directives);
             }
+            extensions.extend(new_extensions);
 
             let ProcessTypeSystemDocumentOutcome { scalars, objects } = outcome;
 
             // Note: we process all newly-defined types in schema extensions.
-            // However, we ignore a bunch of things, like newly-defined fields on existing types, etc.
+            // However, we ignore a bunch of things, like newly-defined fields on existing objects, etc.
             // We should probably fix that!
             result.objects.extend(objects);
             result.scalars.extend(scalars);
             refetch_fields.extend(new_refetch_fields);
         }
 
-        let query = result
+        insert_extension_fields(&mut result.objects, extensions)
+            .map_err(|errors| errors.truncate(max_errors))?;
+        validate_node_implementors_have_id_fields(&result.objects, options.on_missing_id_field)?;
+        validate_no_input_types_in_output_position(&result.objects)?;
+        validate_field_directives(
+            &result.objects,
+            &known_field_directives,
+            options.on_unknown_field_directive,
+        )?;
+
+        // Every schema has a query root. If no Query type is defined, an empty one
+        // is used in its place. Renaming the query is not yet supported.
+        let query_index = match result
             .objects
-            .iter_mut()
-            .find(|(object, _)| object.server_object_entity.name == *QUERY_TYPE)
-            .expect("Expected query type to be defined. Renaming the query is not yet supported.");
-        query.0.expose_as_fields_to_insert.extend(refetch_fields);
+            .iter()
+            .position(|(object, _)| object.server_object_entity.name == *QUERY_TYPE)
+        {
+            Some(query_index) => query_index,
+            None => {
+                result
+                    .objects
+                    .push(default_query_object(refetch_field_name));
+                result.objects.len() - 1
+            }
+        };
+        result.objects[query_index]
+            .0
+            .expose_as_fields_to_insert
+            .extend(refetch_fields);
 
         // - in the extension document, you may have added directives to objects, e.g. @exposeAs
         // - we need to transfer those to the original objects.
@@ -88,13 +126,7 @@ impl NetworkProtocol for GraphQLNetworkProtocol {
                 Some((object, _)) => {
                     for directive in directives {
                         if directive.name.item == *EXPOSE_FIELD_DIRECTIVE {
-                            let expose_field_directive = from_graphql_directive(&directive)
-                                .map_err(|err| match err {
-                                    DeserializationError::Custom(err) => WithLocation::new(
-                                        CreateAdditionalFieldsError::FailedToDeserialize(err),
-                                        directive.name.location.into(), // TODO: use location of the entire directive
-                                    ),
-                                })?;
+                            let expose_field_directive = parse_expose_field_directive(&directive)?;
 
                             object
                                 .expose_as_fields_to_insert
@@ -134,11 +166,25 @@ impl NetworkProtocol for GraphQLNetworkProtocol {
             root_operation_name,
         )
     }
+
+    fn sdl_keyword(object: &ServerObjectEntity<Self>) -> SdlKeyword {
+        object
+            .output_associated_data
+            .original_definition_type
+            .sdl_keyword()
+    }
+
+    fn is_one_of(object: &ServerObjectEntity<Self>) -> bool {
+        object.output_associated_data.is_one_of
+    }
 }
 
 #[derive(Debug)]
 pub struct GraphQLSchemaObjectAssociatedData {
     pub original_definition_type: GraphQLSchemaOriginalDefinitionType,
+    /// Whether this is an input object with the @oneOf directive, i.e. exactly
+    /// one of its fields must be provided.
+    pub is_one_of: bool,
 }
 
 #[derive(Debug)]
@@ -150,12 +196,12 @@ pub enum GraphQLSchemaOriginalDefinitionType {
 }
 
 impl GraphQLSchemaOriginalDefinitionType {
-    pub fn sdl_keyword(&self) -> &'static str {
+    pub fn sdl_keyword(&self) -> SdlKeyword {
         match self {
-            GraphQLSchemaOriginalDefinitionType::InputObject => "input",
-            GraphQLSchemaOriginalDefinitionType::Object => "object",
-            GraphQLSchemaOriginalDefinitionType::Interface => "interface",
-            GraphQLSchemaOriginalDefinitionType::Union => "union",
+            GraphQLSchemaOriginalDefinitionType::InputObject => SdlKeyword::Input,
+            GraphQLSchemaOriginalDefinitionType::Object => SdlKeyword::Type,
+            GraphQLSchemaOriginalDefinitionType::Interface => SdlKeyword::Interface,
+            GraphQLSchemaOriginalDefinitionType::Union => SdlKeyword::Union,
         }
     }
 }
@@ -1,23 +1,28 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use common_lang_types::{
-    GraphQLInterfaceTypeName, IsographObjectTypeName, Location, SelectableName,
-    ServerScalarSelectableName, Span, UnvalidatedTypeName, WithLocation, WithSpan,
+    DirectiveArgumentName, DirectiveName, GraphQLInterfaceTypeName, InputValueName,
+    IsographObjectTypeName, Location, SelectableName, ServerScalarSelectableName,
+    ServerSelectableName, Span, UnvalidatedTypeName, WithLocation, WithSpan,
 };
 use graphql_lang_types::{
-    GraphQLConstantValue, GraphQLDirective, GraphQLNamedTypeAnnotation,
-    GraphQLNonNullTypeAnnotation, GraphQLScalarTypeDefinition, GraphQLTypeAnnotation,
-    GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument, GraphQLTypeSystemExtension,
-    GraphQLTypeSystemExtensionDocument, GraphQLTypeSystemExtensionOrDefinition, RootOperationKind,
+    from_graphql_directive, DeserializationError, GraphQLConstantValue, GraphQLDirective,
+    GraphQLFieldDefinition, GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation,
+    GraphQLTypeAnnotation, GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument,
+    GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
+    GraphQLTypeSystemExtensionOrDefinition, RootOperationKind,
 };
-use intern::string_key::Intern;
+use intern::{string_key::Intern, Lookup};
+use isograph_config::OptionalValidationLevel;
 use isograph_schema::{
     CreateAdditionalFieldsError, ExposeAsFieldToInsert, ExposeFieldDirective, FieldMapItem,
-    FieldToInsert, IsographObjectTypeDefinition, ProcessObjectTypeDefinitionOutcome,
-    ProcessTypeSystemDocumentOutcome, RootTypes, ServerObjectEntity, ServerScalarEntity,
-    STRING_JAVASCRIPT_TYPE, TYPENAME_FIELD_NAME,
+    FieldToInsert, IsographObjectTypeDefinition, IsographScalarTypeDefinition,
+    ProcessObjectTypeDefinitionOutcome, ProcessTypeSystemDocumentOutcome, RootTypes,
+    ServerFieldKind, ServerObjectEntity, ServerScalarEntity, STRING_JAVASCRIPT_TYPE,
+    TYPENAME_FIELD_NAME,
 };
 use lazy_static::lazy_static;
+use serde::{de::IntoDeserializer, Deserialize};
 use thiserror::Error;
 
 use crate::{
@@ -27,22 +32,33 @@ use crate::{
 lazy_static! {
     pub static ref QUERY_TYPE: IsographObjectTypeName = "Query".intern().into();
     static ref MUTATION_TYPE: IsographObjectTypeName = "Mutation".intern().into();
+    static ref SUBSCRIPTION_TYPE: IsographObjectTypeName = "Subscription".intern().into();
     static ref ID_FIELD_NAME: ServerScalarSelectableName = "id".intern().into();
     // TODO use schema_data.string_type_id or something
     static ref STRING_TYPE_NAME: UnvalidatedTypeName = "String".intern().into();
     static ref NODE_INTERFACE_NAME: GraphQLInterfaceTypeName = "Node".intern().into();
     pub static ref REFETCH_FIELD_NAME: SelectableName = "__refetch".intern().into();
+    pub(crate) static ref EXPOSE_FIELD_DIRECTIVE: DirectiveName = "exposeField".intern().into();
+    static ref FIELD_MAP_ARGUMENT: DirectiveArgumentName = "fieldMap".intern().into();
+    static ref SPECIFIED_BY_DIRECTIVE: DirectiveName = "specifiedBy".intern().into();
+    static ref DEPRECATED_DIRECTIVE: DirectiveName = "deprecated".intern().into();
+    static ref ONE_OF_DIRECTIVE: DirectiveName = "oneOf".intern().into();
 
 }
 
 #[allow(clippy::type_complexity)]
 pub fn process_graphql_type_system_document(
     type_system_document: GraphQLTypeSystemDocument,
-) -> ProcessGraphqlTypeDefinitionResult<(
-    ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol>,
-    HashMap<IsographObjectTypeName, Vec<GraphQLDirective<GraphQLConstantValue>>>,
-    Vec<ExposeAsFieldToInsert>,
-)> {
+    refetch_field_name: SelectableName,
+    max_errors: usize,
+) -> Result<
+    (
+        ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol>,
+        HashMap<IsographObjectTypeName, Vec<GraphQLDirective<GraphQLConstantValue>>>,
+        Vec<ExposeAsFieldToInsert>,
+    ),
+    ProcessGraphqlTypeSystemDefinitionErrors,
+> {
     // TODO return a vec of errors, not just one
 
     // In the schema, interfaces, unions and objects are the same type of object (SchemaType),
@@ -50,6 +66,9 @@ pub fn process_graphql_type_system_document(
     // concrete objects.
 
     let mut supertype_to_subtype_map = HashMap::new();
+    // Interfaces that implement other interfaces are subtypes of them, but (unlike
+    // objects) are not refinements, so they are only used for validation.
+    let mut interface_subtypes = vec![];
 
     let mut processed_root_types = None;
 
@@ -59,6 +78,8 @@ pub fn process_graphql_type_system_document(
 
     let mut refetch_fields = vec![];
 
+    validate_directive_repetition(&type_system_document)?;
+
     for with_location in type_system_document.0 {
         let WithLocation {
             location,
@@ -84,9 +105,11 @@ pub fn process_graphql_type_system_document(
                     concrete_type,
                     GraphQLSchemaObjectAssociatedData {
                         original_definition_type: GraphQLSchemaOriginalDefinitionType::Object,
+                        is_one_of: false,
                     },
                     GraphQLObjectDefinitionType::Object,
                     &mut refetch_fields,
+                    refetch_field_name,
                 )?;
 
                 directives
@@ -97,11 +120,20 @@ pub fn process_graphql_type_system_document(
                 objects.push((object_definition_outcome, location));
             }
             GraphQLTypeSystemDefinition::ScalarTypeDefinition(scalar_type_definition) => {
-                scalars.push((process_scalar_definition(scalar_type_definition), location));
+                scalars.push((
+                    process_scalar_definition(scalar_type_definition.into()),
+                    location,
+                ));
                 // N.B. we assume that Mutation will be an object, not a scalar
             }
             GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface_type_definition) => {
                 let interface_name = interface_type_definition.name.item.unchecked_conversion();
+                for implemented_interface in interface_type_definition.interfaces.iter() {
+                    interface_subtypes.push((
+                        implemented_interface.item.into(),
+                        interface_type_definition.name.item.into(),
+                    ));
+                }
                 let (process_object_type_definition_outcome, new_directives) =
                     process_object_type_definition(
                         interface_type_definition.into(),
@@ -109,9 +141,11 @@ pub fn process_graphql_type_system_document(
                         GraphQLSchemaObjectAssociatedData {
                             original_definition_type:
                                 GraphQLSchemaOriginalDefinitionType::Interface,
+                            is_one_of: false,
                         },
                         GraphQLObjectDefinitionType::Interface,
                         &mut refetch_fields,
+                        refetch_field_name,
                     )?;
                 objects.push((process_object_type_definition_outcome, location));
 
@@ -129,6 +163,10 @@ pub fn process_graphql_type_system_document(
                     .name
                     .item
                     .unchecked_conversion();
+                let is_one_of = input_object_type_definition
+                    .directives
+                    .iter()
+                    .any(|directive| directive.name.item == *ONE_OF_DIRECTIVE);
                 let (process_object_type_definition_outcome, new_directives) =
                     process_object_type_definition(
                         input_object_type_definition.into(),
@@ -137,9 +175,11 @@ pub fn process_graphql_type_system_document(
                         GraphQLSchemaObjectAssociatedData {
                             original_definition_type:
                                 GraphQLSchemaOriginalDefinitionType::InputObject,
+                            is_one_of,
                         },
                         GraphQLObjectDefinitionType::InputObject,
                         &mut refetch_fields,
+                        refetch_field_name,
                     )?;
                 objects.push((process_object_type_definition_outcome, location));
                 directives
@@ -153,14 +193,7 @@ pub fn process_graphql_type_system_document(
             }
             GraphQLTypeSystemDefinition::EnumDefinition(enum_definition) => {
                 // TODO Do not do this
-                scalars.push((
-                    process_scalar_definition(GraphQLScalarTypeDefinition {
-                        description: enum_definition.description,
-                        name: enum_definition.name.map(|x| x.unchecked_conversion()),
-                        directives: enum_definition.directives,
-                    }),
-                    location,
-                ));
+                scalars.push((process_scalar_definition(enum_definition.into()), location));
             }
             GraphQLTypeSystemDefinition::UnionTypeDefinition(union_definition) => {
                 // TODO do something reasonable here, once we add support for type refinements.
@@ -176,9 +209,11 @@ pub fn process_graphql_type_system_document(
                         None,
                         GraphQLSchemaObjectAssociatedData {
                             original_definition_type: GraphQLSchemaOriginalDefinitionType::Union,
+                            is_one_of: false,
                         },
                         GraphQLObjectDefinitionType::Union,
                         &mut refetch_fields,
+                        refetch_field_name,
                     )?;
                 objects.push((process_object_type_definition_outcome, location));
                 directives
@@ -199,7 +234,8 @@ pub fn process_graphql_type_system_document(
                     return Err(WithLocation::new(
                         ProcessGraphqlTypeSystemDefinitionError::DuplicateSchemaDefinition,
                         location,
-                    ));
+                    )
+                    .into());
                 }
                 processed_root_types = Some(RootTypes {
                     query: schema_definition.query,
@@ -210,44 +246,30 @@ pub fn process_graphql_type_system_document(
         }
     }
 
-    // For each supertype (e.g. Node) and a subtype (e.g. Pet), we need to add an asConcreteType field.
-    for (supertype_name, subtypes) in supertype_to_subtype_map.iter() {
-        if let Some((object_outcome, _)) = objects.iter_mut().find(|obj| {
-            let supertype_name: IsographObjectTypeName = supertype_name.unchecked_conversion();
+    // The schema definition may rename the subscription root, in which case a type
+    // named Subscription is not a root.
+    let subscription_type_name = processed_root_types
+        .and_then(|root_types| root_types.subscription)
+        .map(|subscription| subscription.item.into())
+        .unwrap_or(*SUBSCRIPTION_TYPE);
+    for (object_definition_outcome, _) in objects.iter_mut() {
+        if object_definition_outcome.server_object_entity.name == subscription_type_name {
+            object_definition_outcome.encountered_root_kind = Some(RootOperationKind::Subscription);
+        }
+    }
 
-            obj.0.server_object_entity.name == supertype_name
-        }) {
-            for subtype_name in subtypes.iter() {
-                object_outcome.fields_to_insert.push(WithLocation::new(
-                    FieldToInsert {
-                        description: Some(WithSpan::new(
-                            format!("A client pointer for the {} type.", subtype_name)
-                                .intern()
-                                .into(),
-                            Span::todo_generated(),
-                        )),
-                        name: WithLocation::new(
-                            format!("as{}", subtype_name).intern().into(),
-                            Location::generated(),
-                        ),
-                        type_: GraphQLTypeAnnotation::Named(GraphQLNamedTypeAnnotation(
-                            WithSpan::new(*subtype_name, Span::todo_generated()),
-                        )),
-                        arguments: vec![],
-                        is_inline_fragment: true,
-                    },
-                    Location::generated(),
-                ));
-            }
-        } else {
-            return Err(WithLocation::new(
-                ProcessGraphqlTypeSystemDefinitionError::AttemptedToImplementNonExistentType {
-                subtype_name: *subtypes.first().expect("Expected subtypes not to be empty. This is indicative of a bug in Isograph."),
-                    supertype_name: *supertype_name,
-                },
-                Location::generated(),
-            ));
-        };
+    // Schemas without interfaces or unions (e.g. those of small apps) have no
+    // refinements, so there is nothing to validate or insert.
+    if !supertype_to_subtype_map.is_empty() || !interface_subtypes.is_empty() {
+        let mut validation_map = supertype_to_subtype_map.clone();
+        for (interface, subtype) in interface_subtypes {
+            insert_into_type_refinement_map(interface, subtype, &mut validation_map);
+        }
+        validate_interface_implementations(&objects, &validation_map)
+            .map_err(|errors| errors.truncate(max_errors))?;
+
+        insert_refinement_fields(&mut objects, &supertype_to_subtype_map)
+            .map_err(|errors| errors.truncate(max_errors))?;
     }
 
     Ok((
@@ -260,11 +282,17 @@ pub fn process_graphql_type_system_document(
 #[allow(clippy::type_complexity)]
 pub fn process_graphql_type_extension_document(
     extension_document: GraphQLTypeSystemExtensionDocument,
-) -> ProcessGraphqlTypeDefinitionResult<(
-    ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol>,
-    HashMap<IsographObjectTypeName, Vec<GraphQLDirective<GraphQLConstantValue>>>,
-    Vec<ExposeAsFieldToInsert>,
-)> {
+    refetch_field_name: SelectableName,
+    max_errors: usize,
+) -> Result<
+    (
+        ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol>,
+        HashMap<IsographObjectTypeName, Vec<GraphQLDirective<GraphQLConstantValue>>>,
+        Vec<ExposeAsFieldToInsert>,
+        ExtensionsToInsert,
+    ),
+    ProcessGraphqlTypeSystemDefinitionErrors,
+> {
     let mut definitions = Vec::with_capacity(extension_document.0.len());
     let mut extensions = Vec::with_capacity(extension_document.0.len());
 
@@ -280,29 +308,552 @@ pub fn process_graphql_type_extension_document(
         }
     }
 
-    let (outcome, mut directives, refetch_fields) =
-        process_graphql_type_system_document(GraphQLTypeSystemDocument(definitions))?;
+    let (outcome, mut directives, refetch_fields) = process_graphql_type_system_document(
+        GraphQLTypeSystemDocument(definitions),
+        refetch_field_name,
+        max_errors,
+    )?;
 
+    let mut extensions_to_insert = ExtensionsToInsert::new();
+    let mut errors = vec![];
     for extension in extensions.into_iter() {
-        // TODO collect errors into vec
         // TODO we can encounter new interface implementations; we should account for that
 
-        for (name, new_directives) in process_graphql_type_system_extension(extension) {
-            directives.entry(name).or_default().extend(new_directives);
+        match process_graphql_type_system_extension(extension) {
+            Ok((name, new_directives, extension_to_insert)) => {
+                directives.entry(name).or_default().extend(new_directives);
+                extensions_to_insert.extend(extension_to_insert);
+            }
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(
+            ProcessGraphqlTypeSystemDefinitionErrors { messages: errors }.truncate(max_errors),
+        );
+    }
+
+    Ok((outcome, directives, refetch_fields, extensions_to_insert))
+}
+
+/// The kind of type that an extension extends, which is determined by whether
+/// the extension was written as extend interface or as extend union.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionKind {
+    Interface,
+    Union,
+}
+
+/// The fields that an extension adds to an existing type, e.g.
+/// extend interface Node { createdAt: DateTime }, or the asConcreteType fields
+/// of the members added by e.g. extend union SearchResult = Comment
+#[derive(Debug)]
+pub struct ExtensionToInsert {
+    pub type_name: IsographObjectTypeName,
+    pub kind: ExtensionKind,
+    pub fields_to_insert: Vec<WithLocation<FieldToInsert>>,
+    /// The interfaces that an extended interface implements, e.g. Entity in
+    /// extend interface Node implements Entity
+    pub interfaces: Vec<WithLocation<IsographObjectTypeName>>,
+}
+
+pub type ExtensionsToInsert = Vec<WithLocation<ExtensionToInsert>>;
+
+/// Adds the fields from interface and union extensions to the types they extend, and
+/// the interfaces that interface extensions implement. Since implementors must define
+/// every field of their interfaces, this re-validates the implementations of every
+/// interface.
+pub(crate) fn insert_extension_fields(
+    objects: &mut [(
+        ProcessObjectTypeDefinitionOutcome<GraphQLNetworkProtocol>,
+        Location,
+    )],
+    extensions: ExtensionsToInsert,
+) -> Result<(), ProcessGraphqlTypeSystemDefinitionErrors> {
+    if extensions.is_empty() {
+        return Ok(());
+    }
+
+    let mut implemented_interfaces = vec![];
+    for WithLocation {
+        location,
+        item: extension,
+    } in extensions
+    {
+        let type_name = extension.type_name;
+        let Some((extended_type, _)) = objects
+            .iter_mut()
+            .find(|(object, _)| object.server_object_entity.name == type_name)
+        else {
+            return Err(WithLocation::new(
+                ProcessGraphqlTypeSystemDefinitionError::AttemptedToExtendUndefinedType {
+                    type_name,
+                },
+                location,
+            )
+            .into());
+        };
+        let original_definition_type = &extended_type
+            .server_object_entity
+            .output_associated_data
+            .original_definition_type;
+        match extension.kind {
+            ExtensionKind::Interface => {
+                if !matches!(
+                    original_definition_type,
+                    GraphQLSchemaOriginalDefinitionType::Interface
+                ) {
+                    return Err(WithLocation::new(
+                        ProcessGraphqlTypeSystemDefinitionError::ExtendedTypeIsNotAnInterface {
+                            type_name,
+                        },
+                        location,
+                    )
+                    .into());
+                }
+            }
+            ExtensionKind::Union => {
+                if !matches!(
+                    original_definition_type,
+                    GraphQLSchemaOriginalDefinitionType::Union
+                ) {
+                    return Err(WithLocation::new(
+                        ProcessGraphqlTypeSystemDefinitionError::ExtendedTypeIsNotAUnion {
+                            type_name,
+                        },
+                        location,
+                    )
+                    .into());
+                }
+            }
+        }
+        extended_type
+            .fields_to_insert
+            .extend(extension.fields_to_insert);
+        implemented_interfaces.extend(
+            extension
+                .interfaces
+                .into_iter()
+                .map(|interface| (type_name, interface)),
+        );
+    }
+
+    // The asConcreteType fields on each supertype tell us its subtypes.
+    let mut supertype_to_subtype_map = HashMap::new();
+    for (object, _) in objects.iter() {
+        for field in object.fields_to_insert.iter() {
+            if field.item.is_inline_fragment {
+                insert_into_type_refinement_map(
+                    object.server_object_entity.name.into(),
+                    *field.item.type_.inner(),
+                    &mut supertype_to_subtype_map,
+                );
+            }
+        }
+    }
+
+    // An extended interface that implements another interface is a subtype of it,
+    // and must define its fields. The implementors of the extended interface are
+    // defined elsewhere (and do not list the other interface), so they become
+    // implementors of the other interface, too. Refinements are only generated for
+    // concrete types, so the extended interface itself is only added to the map
+    // (for validation) once every implementor has been added.
+    let mut interface_subtypes = vec![];
+    for (type_name, interface) in implemented_interfaces {
+        if interface.item == type_name {
+            return Err(WithLocation::new(
+                ProcessGraphqlTypeSystemDefinitionError::SelfImplementation { type_name },
+                interface.location,
+            )
+            .into());
+        }
+        let Some((interface_outcome, _)) = objects
+            .iter_mut()
+            .find(|(object, _)| object.server_object_entity.name == interface.item)
+        else {
+            return Err(WithLocation::new(
+                ProcessGraphqlTypeSystemDefinitionError::AttemptedToImplementNonExistentType {
+                    subtype_name: type_name.into(),
+                    supertype_name: interface.item.into(),
+                },
+                interface.location,
+            )
+            .into());
+        };
+        if !matches!(
+            interface_outcome
+                .server_object_entity
+                .output_associated_data
+                .original_definition_type,
+            GraphQLSchemaOriginalDefinitionType::Interface
+        ) {
+            return Err(WithLocation::new(
+                ProcessGraphqlTypeSystemDefinitionError::ImplementedTypeIsNotAnInterface {
+                    type_name,
+                    implemented_type_name: interface.item,
+                },
+                interface.location,
+            )
+            .into());
+        }
+
+        let implementors = supertype_to_subtype_map
+            .get(&type_name.into())
+            .cloned()
+            .unwrap_or_default();
+        for implementor in implementors {
+            let existing_implementors = supertype_to_subtype_map
+                .entry(interface.item.into())
+                .or_default();
+            if !existing_implementors.contains(&implementor) {
+                existing_implementors.push(implementor);
+                interface_outcome
+                    .fields_to_insert
+                    .push(refinement_field_to_insert(implementor, interface.location));
+            }
+        }
+        interface_subtypes.push((interface.item, type_name));
+    }
+    for (interface, type_name) in interface_subtypes {
+        insert_into_type_refinement_map(
+            interface.into(),
+            type_name.into(),
+            &mut supertype_to_subtype_map,
+        );
+    }
+
+    validate_interface_implementations(objects, &supertype_to_subtype_map)
+}
+
+/// The root query object of schemas that do not define a Query type. It has no
+/// fields other than __typename, and a Query type that is defined takes its place.
+pub(crate) fn default_query_object(
+    refetch_field_name: SelectableName,
+) -> (
+    ProcessObjectTypeDefinitionOutcome<GraphQLNetworkProtocol>,
+    Location,
+) {
+    let (outcome, _directives) = process_object_type_definition(
+        IsographObjectTypeDefinition {
+            description: None,
+            name: WithLocation::new(*QUERY_TYPE, Location::generated()),
+            interfaces: vec![],
+            directives: vec![],
+            fields: vec![],
+        },
+        Some(*QUERY_TYPE),
+        GraphQLSchemaObjectAssociatedData {
+            original_definition_type: GraphQLSchemaOriginalDefinitionType::Object,
+            is_one_of: false,
+        },
+        GraphQLObjectDefinitionType::Object,
+        &mut vec![],
+        refetch_field_name,
+    )
+    .expect("Expected the default query object to be valid");
+    (outcome, Location::generated())
+}
+
+/// Types that implement Node can be refetched, and therefore should have an id field.
+/// Since the asConcreteType fields on Node point to its implementors, this should be
+/// called after all documents (including extensions) have been processed.
+pub(crate) fn validate_node_implementors_have_id_fields(
+    objects: &[(
+        ProcessObjectTypeDefinitionOutcome<GraphQLNetworkProtocol>,
+        Location,
+    )],
+    on_missing_id_field: OptionalValidationLevel,
+) -> ProcessGraphqlTypeDefinitionResult<()> {
+    let node_interface_name: IsographObjectTypeName = (*NODE_INTERFACE_NAME).into();
+    let find_object = |name: IsographObjectTypeName| {
+        objects
+            .iter()
+            .find(|(object, _)| object.server_object_entity.name == name)
+    };
+    let Some((node, _)) = find_object(node_interface_name) else {
+        return Ok(());
+    };
+
+    for refinement in node
+        .fields_to_insert
+        .iter()
+        .filter(|field| field.item.is_inline_fragment)
+    {
+        let Some((implementor, implementor_location)) =
+            find_object((*refinement.item.type_.inner()).unchecked_conversion())
+        else {
+            continue;
+        };
+        if !implementor
+            .fields_to_insert
+            .iter()
+            .any(|field| field.item.kind == ServerFieldKind::IdField)
+        {
+            on_missing_id_field.on_failure(|| {
+                WithLocation::new(
+                    ProcessGraphqlTypeSystemDefinitionError::MissingIdField {
+                        parent_type: implementor.server_object_entity.name,
+                    },
+                    *implementor_location,
+                )
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Input objects can only be used as arguments (or as fields of other input
+/// objects), so a field of an output type cannot have an input object type.
+pub(crate) fn validate_no_input_types_in_output_position(
+    objects: &[(
+        ProcessObjectTypeDefinitionOutcome<GraphQLNetworkProtocol>,
+        Location,
+    )],
+) -> ProcessGraphqlTypeDefinitionResult<()> {
+    let is_input_object = |object: &ProcessObjectTypeDefinitionOutcome<GraphQLNetworkProtocol>| {
+        matches!(
+            object
+                .server_object_entity
+                .output_associated_data
+                .original_definition_type,
+            GraphQLSchemaOriginalDefinitionType::InputObject
+        )
+    };
+    let input_object_names = objects
+        .iter()
+        .filter(|(object, _)| is_input_object(object))
+        .map(|(object, _)| UnvalidatedTypeName::from(object.server_object_entity.name))
+        .collect::<HashSet<_>>();
+
+    for (object, _) in objects
+        .iter()
+        .filter(|(object, _)| !is_input_object(object))
+    {
+        for field in object.fields_to_insert.iter() {
+            let type_name = *field.item.type_.inner();
+            if input_object_names.contains(&type_name) {
+                return Err(WithLocation::new(
+                    ProcessGraphqlTypeSystemDefinitionError::InputTypeInOutputPosition {
+                        parent_type: object.server_object_entity.name,
+                        field_name: field.item.name.item,
+                        type_name,
+                    },
+                    field.location,
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The directives that may appear on fields without being flagged by
+/// on_unknown_field_directive: the built-in @deprecated directive, plus every
+/// directive defined in the schema or in a schema extension.
+pub(crate) fn known_field_directives<'a>(
+    type_system_document: &GraphQLTypeSystemDocument,
+    type_system_extension_documents: impl Iterator<Item = &'a GraphQLTypeSystemExtensionDocument>,
+) -> HashSet<DirectiveName> {
+    let mut known_field_directives = HashSet::from([*DEPRECATED_DIRECTIVE]);
+    let extension_definitions = type_system_extension_documents
+        .flat_map(|document| document.0.iter())
+        .filter_map(|definition| match &definition.item {
+            GraphQLTypeSystemExtensionOrDefinition::Definition(definition) => Some(definition),
+            GraphQLTypeSystemExtensionOrDefinition::Extension(_) => None,
+        });
+    for definition in type_system_document
+        .0
+        .iter()
+        .map(|definition| &definition.item)
+        .chain(extension_definitions)
+    {
+        if let GraphQLTypeSystemDefinition::DirectiveDefinition(directive_definition) = definition {
+            known_field_directives.insert(directive_definition.name.item);
+        }
+    }
+    known_field_directives
+}
+
+pub(crate) fn validate_field_directives(
+    objects: &[(
+        ProcessObjectTypeDefinitionOutcome<GraphQLNetworkProtocol>,
+        Location,
+    )],
+    known_field_directives: &HashSet<DirectiveName>,
+    on_unknown_field_directive: OptionalValidationLevel,
+) -> ProcessGraphqlTypeDefinitionResult<()> {
+    for (object, _) in objects {
+        for field in object.fields_to_insert.iter() {
+            for directive in field.item.directives.iter() {
+                if known_field_directives.contains(&directive.name.item) {
+                    continue;
+                }
+                on_unknown_field_directive.on_failure(|| {
+                    WithLocation::new(
+                        ProcessGraphqlTypeSystemDefinitionError::UnknownFieldDirective {
+                            parent_type: object.server_object_entity.name,
+                            field_name: field.item.name.item,
+                            directive_name: directive.name.item,
+                        },
+                        Location::Embedded(directive.name.location),
+                    )
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A non-repeatable directive (i.e. a built-in directive, or one whose definition
+/// does not include the repeatable keyword) can be applied at most once to each type
+/// and field. Directives without definitions are not validated here.
+fn validate_directive_repetition(
+    type_system_document: &GraphQLTypeSystemDocument,
+) -> ProcessGraphqlTypeDefinitionResult<()> {
+    let mut is_repeatable = HashMap::from([
+        (*DEPRECATED_DIRECTIVE, false),
+        (*SPECIFIED_BY_DIRECTIVE, false),
+        (*ONE_OF_DIRECTIVE, false),
+    ]);
+    for definition in type_system_document.0.iter() {
+        if let GraphQLTypeSystemDefinition::DirectiveDefinition(directive_definition) =
+            &definition.item
+        {
+            is_repeatable.insert(
+                directive_definition.name.item,
+                directive_definition.repeatable.is_some(),
+            );
+        }
+    }
+
+    for definition in type_system_document.0.iter() {
+        let (type_directives, field_directives) = match &definition.item {
+            GraphQLTypeSystemDefinition::ObjectTypeDefinition(object_type_definition) => (
+                &object_type_definition.directives,
+                object_type_definition
+                    .fields
+                    .iter()
+                    .map(|field| &field.item.directives)
+                    .collect(),
+            ),
+            GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface_type_definition) => (
+                &interface_type_definition.directives,
+                interface_type_definition
+                    .fields
+                    .iter()
+                    .map(|field| &field.item.directives)
+                    .collect(),
+            ),
+            GraphQLTypeSystemDefinition::InputObjectTypeDefinition(
+                input_object_type_definition,
+            ) => (
+                &input_object_type_definition.directives,
+                input_object_type_definition
+                    .fields
+                    .iter()
+                    .map(|field| &field.item.directives)
+                    .collect(),
+            ),
+            GraphQLTypeSystemDefinition::ScalarTypeDefinition(scalar_type_definition) => {
+                (&scalar_type_definition.directives, vec![])
+            }
+            GraphQLTypeSystemDefinition::EnumDefinition(enum_definition) => (
+                &enum_definition.directives,
+                enum_definition
+                    .enum_value_definitions
+                    .iter()
+                    .map(|enum_value| &enum_value.item.directives)
+                    .collect(),
+            ),
+            GraphQLTypeSystemDefinition::UnionTypeDefinition(union_definition) => {
+                (&union_definition.directives, vec![])
+            }
+            GraphQLTypeSystemDefinition::SchemaDefinition(schema_definition) => {
+                (&schema_definition.directives, vec![])
+            }
+            GraphQLTypeSystemDefinition::DirectiveDefinition(_) => continue,
+        };
+
+        for directives in std::iter::once(type_directives).chain(field_directives) {
+            let mut applied_directives = HashSet::new();
+            for directive in directives {
+                if is_repeatable.get(&directive.name.item) == Some(&false)
+                    && !applied_directives.insert(directive.name.item)
+                {
+                    return Err(WithLocation::new(
+                        ProcessGraphqlTypeSystemDefinitionError::DuplicateDirective {
+                            directive_name: directive.name.item,
+                        },
+                        Location::Embedded(directive.name.location),
+                    ));
+                }
+            }
         }
     }
 
-    Ok((outcome, directives, refetch_fields))
+    Ok(())
 }
 
 pub(crate) type ProcessGraphqlTypeDefinitionResult<T> =
     Result<T, WithLocation<ProcessGraphqlTypeSystemDefinitionError>>;
 
+#[derive(Error, Eq, PartialEq, Debug)]
+#[error(
+    "{}{}",
+    if messages.len() == 1 {
+        "Error when processing the GraphQL schema:"
+    } else {
+        "Errors when processing the GraphQL schema:"
+    },
+    messages.iter().fold(String::new(), |mut output, x| {
+        output.push_str(&format!("\n\n{}", x));
+        output
+    })
+)]
+pub struct ProcessGraphqlTypeSystemDefinitionErrors {
+    pub messages: Vec<WithLocation<ProcessGraphqlTypeSystemDefinitionError>>,
+}
+
+impl From<WithLocation<ProcessGraphqlTypeSystemDefinitionError>>
+    for ProcessGraphqlTypeSystemDefinitionErrors
+{
+    fn from(error: WithLocation<ProcessGraphqlTypeSystemDefinitionError>) -> Self {
+        ProcessGraphqlTypeSystemDefinitionErrors {
+            messages: vec![error],
+        }
+    }
+}
+
+impl ProcessGraphqlTypeSystemDefinitionErrors {
+    /// Keep at most max_errors errors, and replace the rest with a single error
+    /// noting how many were suppressed.
+    pub fn truncate(mut self, max_errors: usize) -> Self {
+        if self.messages.len() > max_errors {
+            let count = self.messages.len() - max_errors;
+            self.messages.truncate(max_errors);
+            self.messages.push(WithLocation::new(
+                ProcessGraphqlTypeSystemDefinitionError::AdditionalErrorsSuppressed { count },
+                Location::generated(),
+            ));
+        }
+        self
+    }
+}
+
 #[derive(Error, Eq, PartialEq, Debug)]
 pub enum ProcessGraphqlTypeSystemDefinitionError {
     #[error("Duplicate schema definition")]
     DuplicateSchemaDefinition,
 
+    #[error(
+        "{count} additional error(s) were suppressed. \
+        This limit can be changed using the \"max_errors\" config parameter."
+    )]
+    AdditionalErrorsSuppressed { count: usize },
+
     #[error("{0}")]
     CreateAdditionalFieldsError(#[from] CreateAdditionalFieldsError),
 
@@ -314,6 +865,131 @@ pub enum ProcessGraphqlTypeSystemDefinitionError {
         subtype_name: UnvalidatedTypeName,
         supertype_name: UnvalidatedTypeName,
     },
+
+    #[error(
+        "Type {object} implements {interface}, but does not define the field {interface}.{field}."
+    )]
+    MissingInterfaceField {
+        object: IsographObjectTypeName,
+        interface: IsographObjectTypeName,
+        field: ServerSelectableName,
+    },
+
+    #[error(
+        "Type {object} implements {interface}, but the field {object}.{field} has type \
+        {object_field_type}, which is not compatible with the type {interface_field_type} \
+        of {interface}.{field}."
+    )]
+    IncompatibleInterfaceFieldType {
+        object: IsographObjectTypeName,
+        interface: IsographObjectTypeName,
+        field: ServerSelectableName,
+        interface_field_type: String,
+        object_field_type: String,
+    },
+
+    #[error(
+        "Type {object} implements {interface}, but the field {object}.{field} does not \
+        accept the argument {argument} of {interface}.{field}."
+    )]
+    MissingInterfaceFieldArgument {
+        object: IsographObjectTypeName,
+        interface: IsographObjectTypeName,
+        field: ServerSelectableName,
+        argument: InputValueName,
+    },
+
+    #[error(
+        "Type {object} implements {interface}, but the argument {argument} of \
+        {object}.{field} has type {object_argument_type}, which is not the type \
+        {interface_argument_type} of the same argument of {interface}.{field}."
+    )]
+    InterfaceFieldArgumentTypeMismatch {
+        object: IsographObjectTypeName,
+        interface: IsographObjectTypeName,
+        field: ServerSelectableName,
+        argument: InputValueName,
+        interface_argument_type: String,
+        object_argument_type: String,
+    },
+
+    #[error(
+        "Type {object} implements {interface}, but the field {object}.{field} has a \
+        required argument {argument}, which {interface}.{field} does not have. \
+        Additional arguments must be nullable or have a default value."
+    )]
+    RequiredArgumentNotInInterfaceField {
+        object: IsographObjectTypeName,
+        interface: IsographObjectTypeName,
+        field: ServerSelectableName,
+        argument: InputValueName,
+    },
+
+    #[error(
+        "Type {type_name} implements Node, so the compiler generates a refetch field named \
+        {refetch_field_name} on it. However, {type_name}.{refetch_field_name} is already defined. \
+        Set options.refetch_field_name in your Isograph config to a different name."
+    )]
+    RefetchFieldNameCollision {
+        type_name: IsographObjectTypeName,
+        refetch_field_name: SelectableName,
+    },
+
+    #[error("The mutation type {type_name} must define at least one field.")]
+    EmptyMutationType { type_name: IsographObjectTypeName },
+
+    #[error("Type {parent_type} implements Node, but does not have an id field.")]
+    MissingIdField { parent_type: IsographObjectTypeName },
+
+    #[error("Type {type_name} cannot implement itself.")]
+    SelfImplementation { type_name: IsographObjectTypeName },
+
+    #[error(
+        "The argument {argument_name} is defined multiple times on {parent_type}.{field_name}."
+    )]
+    DuplicateArgument {
+        parent_type: IsographObjectTypeName,
+        field_name: ServerSelectableName,
+        argument_name: InputValueName,
+    },
+
+    #[error(
+        "The field {parent_type}.{field_name} has type {type_name}, which is an input object. \
+        Input objects can only be used as the types of arguments and of input object fields."
+    )]
+    InputTypeInOutputPosition {
+        parent_type: IsographObjectTypeName,
+        field_name: ServerSelectableName,
+        type_name: UnvalidatedTypeName,
+    },
+
+    #[error(
+        "The field {parent_type}.{field_name} has the directive @{directive_name}, \
+        which is not defined in the schema."
+    )]
+    UnknownFieldDirective {
+        parent_type: IsographObjectTypeName,
+        field_name: ServerSelectableName,
+        directive_name: DirectiveName,
+    },
+
+    #[error("The directive @{directive_name} is not repeatable, but was applied more than once")]
+    DuplicateDirective { directive_name: DirectiveName },
+
+    #[error("Attempted to extend {type_name} as an interface, but it is not an interface")]
+    ExtendedTypeIsNotAnInterface { type_name: IsographObjectTypeName },
+
+    #[error("Attempted to extend {type_name} as a union, but it is not a union")]
+    ExtendedTypeIsNotAUnion { type_name: IsographObjectTypeName },
+
+    #[error(
+        "{type_name} cannot implement {implemented_type_name}, because \
+        {implemented_type_name} is not an interface"
+    )]
+    ImplementedTypeIsNotAnInterface {
+        type_name: IsographObjectTypeName,
+        implemented_type_name: IsographObjectTypeName,
+    },
 }
 
 fn process_object_type_definition(
@@ -322,14 +998,57 @@ fn process_object_type_definition(
     associated_data: GraphQLSchemaObjectAssociatedData,
     type_definition_type: GraphQLObjectDefinitionType,
     refetch_fields: &mut Vec<ExposeAsFieldToInsert>,
+    refetch_field_name: SelectableName,
 ) -> ProcessGraphqlTypeDefinitionResult<(
     ProcessObjectTypeDefinitionOutcome<GraphQLNetworkProtocol>,
     Vec<GraphQLDirective<GraphQLConstantValue>>,
 )> {
+    // A mutation type without fields would otherwise cause confusing errors
+    // when processing @exposeField directives.
+    if object_type_definition.name.item == *MUTATION_TYPE
+        && object_type_definition.fields.is_empty()
+    {
+        return Err(WithLocation::new(
+            ProcessGraphqlTypeSystemDefinitionError::EmptyMutationType {
+                type_name: object_type_definition.name.item,
+            },
+            object_type_definition.name.location,
+        ));
+    }
+
+    // Otherwise, the type would be a refinement of itself.
+    if let Some(interface) = object_type_definition.interfaces.iter().find(|interface| {
+        IsographObjectTypeName::from(interface.item) == object_type_definition.name.item
+    }) {
+        return Err(WithLocation::new(
+            ProcessGraphqlTypeSystemDefinitionError::SelfImplementation {
+                type_name: object_type_definition.name.item,
+            },
+            interface.location,
+        ));
+    }
+
+    for field in object_type_definition.fields.iter() {
+        let mut argument_names = HashSet::new();
+        for argument in field.item.arguments.iter() {
+            if !argument_names.insert(argument.item.name.item) {
+                return Err(WithLocation::new(
+                    ProcessGraphqlTypeSystemDefinitionError::DuplicateArgument {
+                        parent_type: object_type_definition.name.item,
+                        field_name: field.item.name.item,
+                        argument_name: argument.item.name.item,
+                    },
+                    argument.item.name.location,
+                ));
+            }
+        }
+    }
+
     let object_implements_node = implements_node(&object_type_definition);
     let server_object_entity = ServerObjectEntity {
         description: object_type_definition.description.map(|d| d.item),
         name: object_type_definition.name.item,
+        name_location: object_type_definition.name.location,
         concrete_type,
         output_associated_data: associated_data,
     };
@@ -337,18 +1056,7 @@ fn process_object_type_definition(
     let mut fields_to_insert: Vec<_> = object_type_definition
         .fields
         .into_iter()
-        .map(|field_definition| {
-            WithLocation::new(
-                FieldToInsert {
-                    description: field_definition.item.description,
-                    name: field_definition.item.name,
-                    type_: field_definition.item.type_,
-                    arguments: field_definition.item.arguments,
-                    is_inline_fragment: field_definition.item.is_inline_fragment,
-                },
-                field_definition.location,
-            )
-        })
+        .map(field_to_insert)
         .collect();
 
     // We need to define a typename field for objects and interfaces, but not unions or input objects
@@ -364,16 +1072,31 @@ fn process_object_type_definition(
                     ))),
                 )),
                 arguments: vec![],
+                directives: vec![],
                 is_inline_fragment: false,
+                kind: ServerFieldKind::TypenameField,
             },
             Location::generated(),
         ));
     }
 
     if object_implements_node {
+        if let Some(colliding_field) = fields_to_insert
+            .iter()
+            .find(|field| field.item.name.item == refetch_field_name)
+        {
+            return Err(WithLocation::new(
+                ProcessGraphqlTypeSystemDefinitionError::RefetchFieldNameCollision {
+                    type_name: object_type_definition.name.item,
+                    refetch_field_name,
+                },
+                colliding_field.location,
+            ));
+        }
+
         refetch_fields.push(ExposeAsFieldToInsert {
             expose_field_directive: ExposeFieldDirective {
-                expose_as: Some(*REFETCH_FIELD_NAME),
+                expose_as: Some(refetch_field_name),
                 field_map: vec![FieldMapItem {
                     from: (*ID_FIELD_NAME).unchecked_conversion(),
                     to: (*ID_FIELD_NAME).unchecked_conversion(),
@@ -399,7 +1122,8 @@ fn process_object_type_definition(
     } else if object_type_definition.name.item == *MUTATION_TYPE {
         Some(RootOperationKind::Mutation)
     } else {
-        // TODO subscription
+        // The subscription root is identified once the schema definition (if any)
+        // has been processed.
         None
     };
 
@@ -414,39 +1138,202 @@ fn process_object_type_definition(
     ))
 }
 
-// TODO this should accept an IsographScalarTypeDefinition
+fn field_to_insert(
+    field_definition: WithLocation<GraphQLFieldDefinition>,
+) -> WithLocation<FieldToInsert> {
+    WithLocation::new(
+        FieldToInsert {
+            description: field_definition.item.description,
+            name: field_definition.item.name,
+            type_: field_definition.item.type_,
+            arguments: field_definition.item.arguments,
+            directives: field_definition.item.directives,
+            is_inline_fragment: field_definition.item.is_inline_fragment,
+            kind: if field_definition.item.name.item == *ID_FIELD_NAME {
+                ServerFieldKind::IdField
+            } else {
+                ServerFieldKind::ActualField
+            },
+        },
+        field_definition.location,
+    )
+}
+
 fn process_scalar_definition(
-    scalar_type_definition: GraphQLScalarTypeDefinition,
+    scalar_type_definition: IsographScalarTypeDefinition,
 ) -> ServerScalarEntity<GraphQLNetworkProtocol> {
+    let specified_by_url = scalar_type_definition
+        .directives
+        .iter()
+        .find(|directive| directive.name.item == *SPECIFIED_BY_DIRECTIVE)
+        .and_then(|directive| {
+            directive
+                .arguments
+                .iter()
+                .find(|argument| argument.name.item.lookup() == "url")
+        })
+        .and_then(|argument| argument.value.item.as_string());
+
     ServerScalarEntity {
         description: scalar_type_definition.description,
         name: scalar_type_definition.name,
         javascript_name: *STRING_JAVASCRIPT_TYPE,
+        specified_by_url,
+        javascript_import: None,
         output_format: std::marker::PhantomData,
     }
 }
 
+/// Returns the name of the extended type, the directives added to it and,
+/// for interface extensions, the fields added to it. For union extensions, the
+/// fields are the asConcreteType fields of the new members.
+#[allow(clippy::type_complexity)]
 fn process_graphql_type_system_extension(
     extension: WithLocation<GraphQLTypeSystemExtension>,
-) -> HashMap<IsographObjectTypeName, Vec<GraphQLDirective<GraphQLConstantValue>>> {
-    let mut types_and_directives = HashMap::new();
-    match extension.item {
-        GraphQLTypeSystemExtension::ObjectTypeExtension(object_extension) => {
-            types_and_directives.insert(
-                object_extension.name.item.into(),
-                object_extension.directives,
-            );
+) -> ProcessGraphqlTypeDefinitionResult<(
+    IsographObjectTypeName,
+    Vec<GraphQLDirective<GraphQLConstantValue>>,
+    Option<WithLocation<ExtensionToInsert>>,
+)> {
+    let WithLocation { location, item } = extension;
+    let (type_name, directives, kind_fields_and_interfaces) = match item {
+        // TODO we ignore fields added to objects in extensions
+        GraphQLTypeSystemExtension::ObjectTypeExtension(object_extension) => (
+            object_extension.name.item.into(),
+            object_extension.directives,
+            None,
+        ),
+        GraphQLTypeSystemExtension::InterfaceTypeExtension(interface_extension) => (
+            interface_extension.name.item.into(),
+            interface_extension.directives,
+            Some((
+                ExtensionKind::Interface,
+                interface_extension
+                    .fields
+                    .into_iter()
+                    .map(field_to_insert)
+                    .collect(),
+                interface_extension
+                    .interfaces
+                    .into_iter()
+                    .map(|interface| interface.map(IsographObjectTypeName::from))
+                    .collect(),
+            )),
+        ),
+        GraphQLTypeSystemExtension::UnionTypeExtension(union_extension) => (
+            union_extension.name.item.into(),
+            union_extension.directives,
+            Some((
+                ExtensionKind::Union,
+                union_extension
+                    .union_member_types
+                    .into_iter()
+                    .map(|union_member_type| {
+                        refinement_field_to_insert(
+                            union_member_type.item.into(),
+                            union_member_type.location,
+                        )
+                    })
+                    .collect(),
+                vec![],
+            )),
+        ),
+    };
+
+    for directive in directives.iter() {
+        if directive.name.item == *EXPOSE_FIELD_DIRECTIVE {
+            parse_expose_field_directive(directive).map_err(|error| {
+                error.map(ProcessGraphqlTypeSystemDefinitionError::CreateAdditionalFieldsError)
+            })?;
         }
     }
 
-    types_and_directives
+    Ok((
+        type_name,
+        directives,
+        kind_fields_and_interfaces.map(|(kind, fields_to_insert, interfaces)| {
+            WithLocation::new(
+                ExtensionToInsert {
+                    type_name,
+                    kind,
+                    fields_to_insert,
+                    interfaces,
+                },
+                location,
+            )
+        }),
+    ))
 }
 
-#[derive(Clone, Copy)]
-enum GraphQLObjectDefinitionType {
-    InputObject,
-    Union,
-    Object,
+pub(crate) fn parse_expose_field_directive(
+    directive: &GraphQLDirective<GraphQLConstantValue>,
+) -> Result<ExposeFieldDirective, WithLocation<CreateAdditionalFieldsError>> {
+    ExposeFieldDirective::validate_arguments(directive)
+        .map_err(|err| WithLocation::new(err, directive.name.location.into()))?;
+    // Check the field map on its own, so that we can report the value that failed
+    // to deserialize, and its location.
+    if let Some(field_map) = directive
+        .arguments
+        .iter()
+        .find(|argument| argument.name.item == *FIELD_MAP_ARGUMENT)
+    {
+        let field_map_items = Vec::<FieldMapItem>::deserialize(
+            (&field_map.value.item).into_deserializer(),
+        )
+        .map_err(|_| {
+            WithLocation::new(
+                CreateAdditionalFieldsError::InvalidFieldMap {
+                    value: field_map.value.item.to_string(),
+                },
+                field_map.value.location,
+            )
+        })?;
+        validate_field_map_targets(&field_map_items, &field_map.value)?;
+    }
+    from_graphql_directive(directive).map_err(|err| match err {
+        DeserializationError::Custom(err) => WithLocation::new(
+            CreateAdditionalFieldsError::FailedToDeserialize(err),
+            directive.name.location.into(), // TODO: use location of the entire directive
+        ),
+    })
+}
+
+/// Two field map items with the same target would silently overwrite each other
+/// when the field map is applied.
+fn validate_field_map_targets(
+    field_map_items: &[FieldMapItem],
+    field_map: &WithLocation<GraphQLConstantValue>,
+) -> Result<(), WithLocation<CreateAdditionalFieldsError>> {
+    let item_location = |index: usize| match &field_map.item {
+        GraphQLConstantValue::List(items) => items
+            .get(index)
+            .map(|item| item.location)
+            .unwrap_or(field_map.location),
+        _ => field_map.location,
+    };
+
+    let mut targets = HashMap::new();
+    for (index, field_map_item) in field_map_items.iter().enumerate() {
+        let split_to_arg = field_map_item.split_to_arg();
+        let target = (split_to_arg.to_argument_name, split_to_arg.to_field_names);
+        if let Some(previous_index) = targets.insert(target, index) {
+            return Err(WithLocation::new(
+                CreateAdditionalFieldsError::DuplicateFieldMapTarget {
+                    path: field_map_item.to,
+                    previous_location: item_location(previous_index),
+                },
+                item_location(index),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum GraphQLObjectDefinitionType {
+    InputObject,
+    Union,
+    Object,
     Interface,
 }
 
@@ -461,6 +1348,91 @@ impl GraphQLObjectDefinitionType {
     }
 }
 
+/// For each supertype (e.g. Node) and a subtype (e.g. Pet), we need to add an asConcreteType
+/// field. Every entry is processed, so that one invalid refinement does not hide the others.
+pub(crate) fn insert_refinement_fields(
+    objects: &mut [(
+        ProcessObjectTypeDefinitionOutcome<GraphQLNetworkProtocol>,
+        Location,
+    )],
+    supertype_to_subtype_map: &UnvalidatedTypeRefinementMap,
+) -> Result<(), ProcessGraphqlTypeSystemDefinitionErrors> {
+    // The refinement map is a HashMap, so sort supertypes and subtypes by name, so that
+    // the refinement fields (and any errors) are generated in a stable order.
+    let mut supertype_to_subtype_map = supertype_to_subtype_map
+        .iter()
+        .map(|(supertype_name, subtypes)| {
+            let mut subtypes = subtypes.clone();
+            subtypes.sort_by_key(|subtype_name| subtype_name.lookup());
+            (supertype_name, subtypes)
+        })
+        .collect::<Vec<_>>();
+    supertype_to_subtype_map.sort_by_key(|(supertype_name, _)| supertype_name.lookup());
+
+    let mut errors = vec![];
+    for (supertype_name, subtypes) in supertype_to_subtype_map {
+        if let Some((object_outcome, _)) = objects.iter_mut().find(|obj| {
+            let supertype_name: IsographObjectTypeName = supertype_name.unchecked_conversion();
+
+            obj.0.server_object_entity.name == supertype_name
+        }) {
+            for subtype_name in subtypes.iter() {
+                object_outcome
+                    .fields_to_insert
+                    .push(refinement_field_to_insert(
+                        *subtype_name,
+                        Location::generated(),
+                    ));
+            }
+        } else {
+            for subtype_name in subtypes.iter() {
+                errors.push(WithLocation::new(
+                    ProcessGraphqlTypeSystemDefinitionError::AttemptedToImplementNonExistentType {
+                        subtype_name: *subtype_name,
+                        supertype_name: *supertype_name,
+                    },
+                    Location::generated(),
+                ));
+            }
+        };
+    }
+
+    if !errors.is_empty() {
+        return Err(ProcessGraphqlTypeSystemDefinitionErrors { messages: errors });
+    }
+    Ok(())
+}
+
+/// The asConcreteType field (e.g. asPet) through which a supertype is refined to a subtype.
+fn refinement_field_to_insert(
+    subtype_name: UnvalidatedTypeName,
+    location: Location,
+) -> WithLocation<FieldToInsert> {
+    WithLocation::new(
+        FieldToInsert {
+            description: Some(WithSpan::new(
+                format!("A client pointer for the {} type.", subtype_name)
+                    .intern()
+                    .into(),
+                Span::todo_generated(),
+            )),
+            name: WithLocation::new(
+                format!("as{}", subtype_name).intern().into(),
+                Location::generated(),
+            ),
+            type_: GraphQLTypeAnnotation::Named(GraphQLNamedTypeAnnotation(WithSpan::new(
+                subtype_name,
+                Span::todo_generated(),
+            ))),
+            arguments: vec![],
+            directives: vec![],
+            is_inline_fragment: true,
+            kind: ServerFieldKind::ActualField,
+        },
+        location,
+    )
+}
+
 fn insert_into_type_refinement_map(
     supertype_name: UnvalidatedTypeName,
     subtype_name: UnvalidatedTypeName, // aka the concrete type or union member
@@ -474,9 +1446,2035 @@ fn insert_into_type_refinement_map(
 
 type UnvalidatedTypeRefinementMap = HashMap<UnvalidatedTypeName, Vec<UnvalidatedTypeName>>;
 
+/// GraphQL requires that an object implementing an interface define every field
+/// of that interface, with a compatible type. Every implementation is validated,
+/// in name-sorted order, so that the errors are reported together and in a stable
+/// order.
+fn validate_interface_implementations(
+    objects: &[(
+        ProcessObjectTypeDefinitionOutcome<GraphQLNetworkProtocol>,
+        Location,
+    )],
+    supertype_to_subtype_map: &UnvalidatedTypeRefinementMap,
+) -> Result<(), ProcessGraphqlTypeSystemDefinitionErrors> {
+    let find_object = |name: UnvalidatedTypeName| {
+        let name: IsographObjectTypeName = name.unchecked_conversion();
+        objects
+            .iter()
+            .find(|(object, _)| object.server_object_entity.name == name)
+    };
+
+    let mut supertype_names = supertype_to_subtype_map.keys().collect::<Vec<_>>();
+    supertype_names.sort_by_key(|supertype_name| supertype_name.lookup());
+
+    let mut errors = vec![];
+    for supertype_name in supertype_names {
+        let mut subtypes = supertype_to_subtype_map[supertype_name].clone();
+        subtypes.sort_by_key(|subtype_name| subtype_name.lookup());
+        // If the supertype does not exist, we report that error elsewhere.
+        let Some((interface, _)) = find_object(*supertype_name) else {
+            continue;
+        };
+        if !matches!(
+            interface
+                .server_object_entity
+                .output_associated_data
+                .original_definition_type,
+            GraphQLSchemaOriginalDefinitionType::Interface
+        ) {
+            continue;
+        }
+
+        for subtype_name in subtypes.iter() {
+            let Some((object, object_location)) = find_object(*subtype_name) else {
+                continue;
+            };
+
+            for interface_field in interface.fields_to_insert.iter() {
+                let interface_field = &interface_field.item;
+                // asConcreteType fields are generated, and are not required of implementors
+                if interface_field.is_inline_fragment {
+                    continue;
+                }
+                let error = match object
+                    .fields_to_insert
+                    .iter()
+                    .find(|object_field| object_field.item.name.item == interface_field.name.item)
+                {
+                    Some(object_field) => {
+                        if !is_valid_implementation_field_type(
+                            &object_field.item.type_,
+                            &interface_field.type_,
+                            supertype_to_subtype_map,
+                        ) {
+                            ProcessGraphqlTypeSystemDefinitionError::IncompatibleInterfaceFieldType {
+                                object: object.server_object_entity.name,
+                                interface: interface.server_object_entity.name,
+                                field: interface_field.name.item,
+                                interface_field_type: interface_field.type_.to_string(),
+                                object_field_type: object_field.item.type_.to_string(),
+                            }
+                        } else if let Some(error) = validate_implementation_field_arguments(
+                            object.server_object_entity.name,
+                            interface.server_object_entity.name,
+                            &object_field.item,
+                            interface_field,
+                        ) {
+                            error
+                        } else {
+                            continue;
+                        }
+                    }
+                    None => ProcessGraphqlTypeSystemDefinitionError::MissingInterfaceField {
+                        object: object.server_object_entity.name,
+                        interface: interface.server_object_entity.name,
+                        field: interface_field.name.item,
+                    },
+                };
+                errors.push(WithLocation::new(error, *object_location));
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(ProcessGraphqlTypeSystemDefinitionErrors { messages: errors });
+    }
+    Ok(())
+}
+
+/// Field types are covariant: an implementing field may be non-null where the
+/// interface field is nullable, and may have a type that implements (or is a
+/// member of) the interface field's type. The list wrapping must match exactly,
+/// and this applies at each level, e.g. `[Pet!]!` satisfies `[Node]`, but `[Pet]`
+/// does not satisfy `[Node!]!`.
+fn is_valid_implementation_field_type(
+    object_field_type: &GraphQLTypeAnnotation<UnvalidatedTypeName>,
+    interface_field_type: &GraphQLTypeAnnotation<UnvalidatedTypeName>,
+    supertype_to_subtype_map: &UnvalidatedTypeRefinementMap,
+) -> bool {
+    match (object_field_type, interface_field_type) {
+        (
+            GraphQLTypeAnnotation::NonNull(object_inner),
+            GraphQLTypeAnnotation::NonNull(interface_inner),
+        ) => is_valid_implementation_field_type(
+            &non_null_inner_type(object_inner),
+            &non_null_inner_type(interface_inner),
+            supertype_to_subtype_map,
+        ),
+        (GraphQLTypeAnnotation::NonNull(object_inner), _) => is_valid_implementation_field_type(
+            &non_null_inner_type(object_inner),
+            interface_field_type,
+            supertype_to_subtype_map,
+        ),
+        (
+            GraphQLTypeAnnotation::List(object_inner),
+            GraphQLTypeAnnotation::List(interface_inner),
+        ) => is_valid_implementation_field_type(
+            &object_inner.0,
+            &interface_inner.0,
+            supertype_to_subtype_map,
+        ),
+        (
+            GraphQLTypeAnnotation::Named(object_named),
+            GraphQLTypeAnnotation::Named(interface_named),
+        ) => {
+            object_named.item == interface_named.item
+                || is_subtype(
+                    object_named.item,
+                    interface_named.item,
+                    supertype_to_subtype_map,
+                )
+        }
+        _ => false,
+    }
+}
+
+/// Whether subtype implements (or is a member of) supertype, either directly or via
+/// an interface that implements supertype.
+fn is_subtype(
+    subtype: UnvalidatedTypeName,
+    supertype: UnvalidatedTypeName,
+    supertype_to_subtype_map: &UnvalidatedTypeRefinementMap,
+) -> bool {
+    let mut visited = HashSet::new();
+    let mut to_visit = vec![supertype];
+    while let Some(type_name) = to_visit.pop() {
+        if !visited.insert(type_name) {
+            continue;
+        }
+        for direct_subtype in supertype_to_subtype_map
+            .get(&type_name)
+            .into_iter()
+            .flatten()
+        {
+            if *direct_subtype == subtype {
+                return true;
+            }
+            to_visit.push(*direct_subtype);
+        }
+    }
+    false
+}
+
+/// The implementing field must accept every argument of the interface field, with
+/// the same type. It may accept additional arguments, as long as they are optional.
+fn validate_implementation_field_arguments(
+    object: IsographObjectTypeName,
+    interface: IsographObjectTypeName,
+    object_field: &FieldToInsert,
+    interface_field: &FieldToInsert,
+) -> Option<ProcessGraphqlTypeSystemDefinitionError> {
+    let field = interface_field.name.item;
+    for interface_argument in interface_field.arguments.iter() {
+        let interface_argument = &interface_argument.item;
+        let argument = interface_argument.name.item;
+        let Some(object_argument) = object_field
+            .arguments
+            .iter()
+            .find(|object_argument| object_argument.item.name.item == argument)
+        else {
+            return Some(
+                ProcessGraphqlTypeSystemDefinitionError::MissingInterfaceFieldArgument {
+                    object,
+                    interface,
+                    field,
+                    argument,
+                },
+            );
+        };
+        // Types are compared as strings, since their spans differ.
+        let interface_argument_type = interface_argument.type_.to_string();
+        let object_argument_type = object_argument.item.type_.to_string();
+        if interface_argument_type != object_argument_type {
+            return Some(
+                ProcessGraphqlTypeSystemDefinitionError::InterfaceFieldArgumentTypeMismatch {
+                    object,
+                    interface,
+                    field,
+                    argument,
+                    interface_argument_type,
+                    object_argument_type,
+                },
+            );
+        }
+    }
+
+    object_field
+        .arguments
+        .iter()
+        .find(|object_argument| {
+            matches!(
+                object_argument.item.type_,
+                GraphQLTypeAnnotation::NonNull(_)
+            ) && object_argument.item.default_value.is_none()
+                && !interface_field.arguments.iter().any(|interface_argument| {
+                    interface_argument.item.name.item == object_argument.item.name.item
+                })
+        })
+        .map(|object_argument| {
+            ProcessGraphqlTypeSystemDefinitionError::RequiredArgumentNotInInterfaceField {
+                object,
+                interface,
+                field,
+                argument: object_argument.item.name.item,
+            }
+        })
+}
+
+fn non_null_inner_type(
+    non_null_type: &GraphQLNonNullTypeAnnotation<UnvalidatedTypeName>,
+) -> GraphQLTypeAnnotation<UnvalidatedTypeName> {
+    match non_null_type.clone() {
+        GraphQLNonNullTypeAnnotation::Named(named) => GraphQLTypeAnnotation::Named(named),
+        GraphQLNonNullTypeAnnotation::List(list) => GraphQLTypeAnnotation::List(Box::new(list)),
+    }
+}
+
 fn implements_node(object_type_definition: &IsographObjectTypeDefinition) -> bool {
     object_type_definition
         .interfaces
         .iter()
         .any(|x| x.item == *NODE_INTERFACE_NAME)
 }
+
+#[cfg(test)]
+mod test {
+    use common_lang_types::{
+        RelativePathToSourceFile, SelectableName, Span, TextSource, UnvalidatedTypeName,
+        WithLocation, WithSpan,
+    };
+    use graphql_lang_types::GraphQLTypeSystemDocument;
+    use graphql_schema_parser::{parse_schema, parse_schema_extensions};
+    use intern::string_key::Intern;
+    use pico::Database;
+    use std::{
+        collections::{BTreeMap, HashMap},
+        marker::PhantomData,
+    };
+
+    use graphql_lang_types::RootOperationKind;
+    use isograph_config::{CompilerConfigOptions, OptionalValidationLevel, DEFAULT_MAX_ERRORS};
+    use isograph_lang_types::{
+        ClientFieldDeclaration, ClientFieldDirectiveSet, EmptyDirectiveSet, SchemaSource,
+        SelectionType, ServerObjectEntityId, TypeAnnotation,
+    };
+    use isograph_schema::{
+        CreateAdditionalFieldsError, NetworkProtocol, ProcessClientFieldDeclarationError,
+        ProcessTypeSystemDocumentOutcome, RootOperationName, Schema,
+        SchemaServerObjectSelectableVariant, ServerObjectSelectable, ServerScalarSelectable,
+    };
+
+    use super::{
+        insert_refinement_fields, known_field_directives, process_graphql_type_extension_document,
+        process_graphql_type_system_document, validate_field_directives,
+        validate_interface_implementations, validate_no_input_types_in_output_position,
+        validate_node_implementors_have_id_fields, ProcessGraphqlTypeSystemDefinitionError,
+        ProcessGraphqlTypeSystemDefinitionErrors, REFETCH_FIELD_NAME,
+    };
+    use crate::GraphQLNetworkProtocol;
+
+    fn parse(source: &str) -> GraphQLTypeSystemDocument {
+        let text_source = TextSource {
+            relative_path_to_source_file: "dummy".intern().into(),
+            span: None,
+            current_working_directory: "cwd".intern().into(),
+        };
+        parse_schema(source, text_source).expect("Expected schema to parse")
+    }
+
+    fn process(
+        source: &str,
+    ) -> Result<
+        ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol>,
+        WithLocation<ProcessGraphqlTypeSystemDefinitionError>,
+    > {
+        process_graphql_type_system_document(parse(source), *REFETCH_FIELD_NAME, DEFAULT_MAX_ERRORS)
+            .map(|(outcome, _, _)| outcome)
+            .map_err(|errors| {
+                errors
+                    .messages
+                    .into_iter()
+                    .next()
+                    .expect("Expected at least one error")
+            })
+    }
+
+    fn insert_into_schema(schema: &mut Schema<GraphQLNetworkProtocol>, source: &str) {
+        let ProcessTypeSystemDocumentOutcome { scalars, objects } =
+            process(source).expect("Expected schema to be processed");
+        for (scalar, location) in scalars {
+            schema
+                .server_entity_data
+                .insert_server_scalar_entity(scalar, location)
+                .expect("Expected scalar to be inserted");
+        }
+        let mut field_queue = vec![];
+        for (outcome, location) in objects {
+            let object_entity_id = schema
+                .server_entity_data
+                .insert_server_object_entity(outcome.server_object_entity, location)
+                .expect("Expected object to be inserted");
+            if let Some(root_operation_kind) = outcome.encountered_root_kind {
+                let root_operation_name = match root_operation_kind {
+                    RootOperationKind::Query => "query",
+                    RootOperationKind::Mutation => "mutation",
+                    RootOperationKind::Subscription => "subscription",
+                };
+                schema.fetchable_types.insert(
+                    object_entity_id,
+                    RootOperationName(root_operation_name.to_string()),
+                );
+            }
+            field_queue.push((object_entity_id, outcome.fields_to_insert));
+        }
+
+        // Selectables are inserted without arguments.
+        for (parent_object_entity_id, fields_to_insert) in field_queue {
+            for field_to_insert in fields_to_insert {
+                let target_entity_id =
+                    schema.server_entity_data.defined_entities[field_to_insert.item.type_.inner()];
+                if let SelectionType::Scalar(target_scalar_entity_id) = target_entity_id {
+                    schema
+                        .insert_server_scalar_selectable(
+                            ServerScalarSelectable {
+                                description: None,
+                                name: field_to_insert.item.name.map(|x| x.unchecked_conversion()),
+                                target_scalar_entity: TypeAnnotation::from_graphql_type_annotation(
+                                    field_to_insert.item.type_.clone(),
+                                )
+                                .map(&mut |_| target_scalar_entity_id),
+                                kind: field_to_insert.item.kind,
+                                parent_object_entity_id,
+                                arguments: vec![],
+                                phantom_data: PhantomData,
+                            },
+                            &CompilerConfigOptions::default(),
+                            field_to_insert.item.type_.inner_non_null_named_type(),
+                        )
+                        .expect("Expected scalar selectable to be inserted");
+                } else if let SelectionType::Object(target_object_entity_id) = target_entity_id {
+                    schema
+                        .insert_server_object_selectable(ServerObjectSelectable {
+                            description: None,
+                            name: field_to_insert.item.name.map(|x| x.unchecked_conversion()),
+                            target_object_entity: TypeAnnotation::from_graphql_type_annotation(
+                                field_to_insert.item.type_.clone(),
+                            )
+                            .map(&mut |_| target_object_entity_id),
+                            object_selectable_variant: if field_to_insert.item.is_inline_fragment {
+                                SchemaServerObjectSelectableVariant::InlineFragment
+                            } else {
+                                SchemaServerObjectSelectableVariant::LinkedField
+                            },
+                            parent_object_entity_id,
+                            arguments: vec![],
+                            phantom_data: PhantomData,
+                        })
+                        .expect("Expected object selectable to be inserted");
+                }
+            }
+        }
+    }
+
+    fn object_names(
+        schema: &Schema<GraphQLNetworkProtocol>,
+        object_entity_ids: Vec<ServerObjectEntityId>,
+    ) -> Vec<String> {
+        let mut names = object_entity_ids
+            .into_iter()
+            .map(|object_entity_id| {
+                schema
+                    .server_entity_data
+                    .server_object_entity(object_entity_id)
+                    .name
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn implementors_of_interface_are_returned() {
+        let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+        insert_into_schema(
+            &mut schema,
+            "interface Node { id: ID! }
+            type Query { node: Node }
+            type Pet implements Node { id: ID! owner: User }
+            type User implements Node { id: ID! }",
+        );
+        let object_entity_id = |name: &str| {
+            let name: UnvalidatedTypeName = name.intern().into();
+            *schema.server_entity_data.defined_entities[&name]
+                .as_object()
+                .expect("Expected type to be an object")
+        };
+
+        assert_eq!(
+            object_names(&schema, schema.implementors_of(object_entity_id("Node"))),
+            vec!["Pet", "User"]
+        );
+        assert!(schema.implementors_of(object_entity_id("Pet")).is_empty());
+    }
+
+    #[test]
+    fn fields_returning_an_object_are_returned() {
+        let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+        insert_into_schema(
+            &mut schema,
+            "interface Node { id: ID! }
+            type Query { node: Node viewer: User! }
+            type Pet implements Node { id: ID! owners: [User!] }
+            type User implements Node { id: ID! }",
+        );
+        let object_entity_id = |name: &str| {
+            let name: UnvalidatedTypeName = name.intern().into();
+            *schema.server_entity_data.defined_entities[&name]
+                .as_object()
+                .expect("Expected type to be an object")
+        };
+
+        let mut fields = schema
+            .fields_returning(object_entity_id("User"))
+            .into_iter()
+            .map(|(parent_object_entity_id, server_object_selectable_id)| {
+                format!(
+                    "{}.{}",
+                    schema
+                        .server_entity_data
+                        .server_object_entity(parent_object_entity_id)
+                        .name,
+                    schema
+                        .server_object_selectable(server_object_selectable_id)
+                        .name
+                        .item
+                )
+            })
+            .collect::<Vec<_>>();
+        fields.sort();
+        // Node.asUser is an inline fragment, not a field
+        assert_eq!(fields, vec!["Pet.owners", "Query.viewer"]);
+        assert!(schema
+            .fields_returning(object_entity_id("Query"))
+            .is_empty());
+    }
+
+    #[test]
+    fn client_field_cannot_shadow_server_field() {
+        let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+        insert_into_schema(
+            &mut schema,
+            "type Query { viewer: User }
+            type User { id: ID! }",
+        );
+        let text_source = TextSource {
+            relative_path_to_source_file: "dummy".intern().into(),
+            span: None,
+            current_working_directory: "cwd".intern().into(),
+        };
+
+        let error = schema
+            .process_client_field_declaration(
+                WithSpan::new(
+                    ClientFieldDeclaration {
+                        const_export_name: "Viewer".intern().into(),
+                        parent_type: WithSpan::new("Query".intern().into(), Span::todo_generated()),
+                        client_field_name: WithSpan::new(
+                            "viewer".intern().into(),
+                            Span::todo_generated(),
+                        ),
+                        description: None,
+                        selection_set: vec![],
+                        client_field_directive_set: ClientFieldDirectiveSet::None(
+                            EmptyDirectiveSet {},
+                        ),
+                        variable_definitions: vec![],
+                        definition_path: "dummy".intern().into(),
+                        field_keyword: WithSpan::new((), Span::todo_generated()),
+                        dot: WithSpan::new((), Span::todo_generated()),
+                    },
+                    Span::todo_generated(),
+                ),
+                text_source,
+            )
+            .map(|_| ())
+            .expect_err("Expected error");
+
+        match error.item {
+            ProcessClientFieldDeclarationError::ClientFieldShadowsServerField {
+                parent_type_name,
+                client_field_name,
+            } => {
+                assert_eq!(parent_type_name.to_string(), "Query");
+                assert_eq!(client_field_name.to_string(), "viewer");
+            }
+            other => panic!("Unexpected error {other}"),
+        }
+    }
+
+    #[test]
+    fn selectables_are_looked_up_by_name() {
+        let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+        insert_into_schema(
+            &mut schema,
+            "type Query { viewer: User }
+            type User { id: ID! name: String }",
+        );
+        let text_source = TextSource {
+            relative_path_to_source_file: "dummy".intern().into(),
+            span: None,
+            current_working_directory: "cwd".intern().into(),
+        };
+        schema
+            .process_client_field_declaration(
+                WithSpan::new(
+                    ClientFieldDeclaration {
+                        const_export_name: "Greeting".intern().into(),
+                        parent_type: WithSpan::new("User".intern().into(), Span::todo_generated()),
+                        client_field_name: WithSpan::new(
+                            "greeting".intern().into(),
+                            Span::todo_generated(),
+                        ),
+                        description: None,
+                        selection_set: vec![],
+                        client_field_directive_set: ClientFieldDirectiveSet::None(
+                            EmptyDirectiveSet {},
+                        ),
+                        variable_definitions: vec![],
+                        definition_path: "dummy".intern().into(),
+                        field_keyword: WithSpan::new((), Span::todo_generated()),
+                        dot: WithSpan::new((), Span::todo_generated()),
+                    },
+                    Span::todo_generated(),
+                ),
+                text_source,
+            )
+            .expect("Expected client field to be processed");
+        let user_name: UnvalidatedTypeName = "User".intern().into();
+        let user_object_entity_id = *schema.server_entity_data.defined_entities[&user_name]
+            .as_object()
+            .expect("Expected User to be an object");
+
+        let name = schema
+            .server_selectable_by_name(user_object_entity_id, "name".intern().into())
+            .expect("Expected name to be a server selectable");
+        assert_eq!(
+            name.as_scalar()
+                .expect("Expected name to be a scalar")
+                .name
+                .item,
+            "name"
+        );
+        let greeting = schema
+            .client_selectable_by_name(user_object_entity_id, "greeting".intern().into())
+            .expect("Expected greeting to be a client selectable");
+        assert_eq!(
+            greeting
+                .as_scalar()
+                .expect("Expected greeting to be a client field")
+                .name,
+            "greeting"
+        );
+
+        assert!(schema
+            .server_selectable_by_name(user_object_entity_id, "greeting".intern().into())
+            .is_none());
+        assert!(schema
+            .client_selectable_by_name(user_object_entity_id, "name".intern().into())
+            .is_none());
+        assert!(schema
+            .server_selectable_by_name(user_object_entity_id, "missing".intern().into())
+            .is_none());
+        assert!(schema
+            .client_selectable_by_name(user_object_entity_id, "missing".intern().into())
+            .is_none());
+    }
+
+    #[test]
+    fn max_object_depth_of_nested_objects() {
+        let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+        insert_into_schema(
+            &mut schema,
+            "type Query { viewer: User }
+            type User { id: ID! pet: Pet }
+            type Pet { name: String owner: User toy: Toy }
+            type Toy { name: String }",
+        );
+        let object_entity_id = |name: &str| {
+            let name: UnvalidatedTypeName = name.intern().into();
+            *schema.server_entity_data.defined_entities[&name]
+                .as_object()
+                .expect("Expected type to be an object")
+        };
+
+        assert_eq!(schema.max_object_depth(object_entity_id("Query")), 4);
+        assert_eq!(schema.max_object_depth(object_entity_id("Pet")), 2);
+        assert_eq!(schema.max_object_depth(object_entity_id("Toy")), 1);
+    }
+
+    #[test]
+    fn max_object_depth_does_not_reuse_depths_computed_within_a_cycle() {
+        let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+        insert_into_schema(
+            &mut schema,
+            "type Query { a: P b: Q }
+            type P { x: X }
+            type X { p: P }
+            type Q { x: X }",
+        );
+        let object_entity_id = |name: &str| {
+            let name: UnvalidatedTypeName = name.intern().into();
+            *schema.server_entity_data.defined_entities[&name]
+                .as_object()
+                .expect("Expected type to be an object")
+        };
+
+        // Query -> Q -> X -> P. When X is reached via P, P is being traversed, so
+        // the depth of X computed then must not be reused when X is reached via Q.
+        assert_eq!(schema.max_object_depth(object_entity_id("Query")), 4);
+        assert_eq!(schema.max_object_depth(object_entity_id("X")), 2);
+    }
+
+    #[test]
+    fn content_hash_is_stable_and_reflects_changes() {
+        let content_hash = |source: &str| {
+            let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+            insert_into_schema(&mut schema, source);
+            schema.content_hash()
+        };
+        let source = "interface Node { id: ID! }
+            type Query { node: Node viewer: User }
+            type User implements Node { id: ID! pet: Pet }
+            type Pet { name: String }";
+
+        assert_eq!(content_hash(source), content_hash(source));
+        assert_eq!(
+            content_hash(source),
+            content_hash(
+                "type Pet { name: String }
+                type User implements Node { id: ID! pet: Pet }
+                type Query { viewer: User node: Node }
+                interface Node { id: ID! }"
+            )
+        );
+        assert_ne!(
+            content_hash(source),
+            content_hash(
+                "interface Node { id: ID! }
+                type Query { node: Node viewer: User }
+                type User implements Node { id: ID! pet: Pet bestFriend: User }
+                type Pet { name: String }"
+            )
+        );
+    }
+
+    #[test]
+    fn schema_to_sdl() {
+        let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+        insert_into_schema(
+            &mut schema,
+            "type Query { pets: [Pet!]! node: Node searchResult: SearchResult }
+            union SearchResult = Toy | Pet
+            scalar DateTime
+            type Toy { name: String }
+            type Pet implements Node { name: String! id: ID! born: DateTime }
+            interface Node { id: ID! }",
+        );
+
+        assert_eq!(
+            schema.to_sdl(),
+            "scalar Boolean
+
+scalar DateTime
+
+scalar Float
+
+scalar ID
+
+scalar Int
+
+scalar String
+
+interface Node {
+  id: ID!
+}
+
+type Pet implements Node {
+  born: DateTime
+  id: ID!
+  name: String!
+}
+
+type Query {
+  node: Node
+  pets: [Pet!]!
+  searchResult: SearchResult
+}
+
+union SearchResult = Pet | Toy
+
+type Toy {
+  name: String
+}
+"
+        );
+    }
+
+    #[test]
+    fn unreachable_objects_are_reported() {
+        let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+        insert_into_schema(
+            &mut schema,
+            "interface Node { id: ID! }
+            type Query { node: Node }
+            type Mutation { createUser: User }
+            type Pet implements Node { id: ID! owner: Owner }
+            type Owner { id: ID! }
+            type User { id: ID! }
+            type Dangling { id: ID! }",
+        );
+
+        assert_eq!(
+            object_names(&schema, schema.unreachable_objects(false)),
+            vec!["Dangling", "Mutation", "User"]
+        );
+        assert_eq!(
+            object_names(&schema, schema.unreachable_objects(true)),
+            vec!["Dangling"]
+        );
+    }
+
+    fn is_defined(schema: &Schema<GraphQLNetworkProtocol>, name: &str) -> bool {
+        let name: UnvalidatedTypeName = name.intern().into();
+        schema
+            .server_entity_data
+            .defined_entities
+            .contains_key(&name)
+    }
+
+    #[test]
+    fn cleared_schema_can_be_reprocessed() {
+        let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+        let string_type_id = schema.server_entity_data.string_type_id;
+        let schema_defined_scalar_count = schema.server_entity_data.server_scalars.len();
+
+        insert_into_schema(
+            &mut schema,
+            "scalar Date
+            type Query { pet: Pet }
+            type Pet { id: ID! birthday: Date }",
+        );
+        schema.clear();
+
+        assert!(schema.server_entity_data.server_objects.is_empty());
+        assert!(schema.fetchable_types.is_empty());
+        assert_eq!(
+            schema.server_entity_data.server_scalars.len(),
+            schema_defined_scalar_count
+        );
+        assert!(!is_defined(&schema, "Pet"));
+        assert!(!is_defined(&schema, "Date"));
+        assert!(is_defined(&schema, "String"));
+        assert_eq!(schema.server_entity_data.string_type_id, string_type_id);
+
+        // Reprocessing a document that redefines a previously defined type
+        // must not produce a duplicate definition error.
+        insert_into_schema(
+            &mut schema,
+            "scalar Date
+            type Query { user: User }
+            type User { id: ID! }",
+        );
+
+        assert_eq!(schema.server_entity_data.server_objects.len(), 2);
+        assert!(!is_defined(&schema, "Pet"));
+        assert!(is_defined(&schema, "User"));
+        assert!(is_defined(&schema, "Date"));
+        assert_eq!(
+            schema.query_id(),
+            *schema.server_entity_data.defined_entities[&"Query".intern().into()]
+                .as_object()
+                .expect("Expected Query to be an object")
+        );
+    }
+
+    #[test]
+    fn defined_type_names_includes_schema_defined_and_processed_types() {
+        let mut schema = Schema::<GraphQLNetworkProtocol>::new();
+        insert_into_schema(
+            &mut schema,
+            "scalar Date
+            type Query { pet: Pet }
+            type Pet { id: ID! birthday: Date }",
+        );
+
+        let mut defined_type_names = schema
+            .defined_type_names()
+            .map(|(name, entity_id)| {
+                let kind = match entity_id {
+                    SelectionType::Scalar(_) => "scalar",
+                    SelectionType::Object(_) => "object",
+                };
+                (name.to_string(), kind)
+            })
+            .collect::<Vec<_>>();
+        defined_type_names.sort();
+
+        assert_eq!(
+            defined_type_names,
+            vec![
+                ("Boolean".to_string(), "scalar"),
+                ("Date".to_string(), "scalar"),
+                ("Float".to_string(), "scalar"),
+                ("ID".to_string(), "scalar"),
+                ("Int".to_string(), "scalar"),
+                (
+                    "NullDoesNotExistIfThisIsPrintedThisIsABug".to_string(),
+                    "scalar"
+                ),
+                ("Pet".to_string(), "object"),
+                ("Query".to_string(), "object"),
+                ("String".to_string(), "scalar"),
+            ]
+        );
+    }
+
+    #[test]
+    fn scalars_and_enums_are_processed_as_scalars() {
+        let ProcessTypeSystemDocumentOutcome { scalars, .. } = process(
+            "\"A calendar date\"
+            scalar Date
+            \"A color\"
+            enum Color { RED GREEN }
+            type Query { today: Date }",
+        )
+        .expect("Expected schema to be processed");
+
+        let scalars = scalars
+            .into_iter()
+            .map(|(scalar, _)| {
+                (
+                    scalar.name.item.to_string(),
+                    scalar
+                        .description
+                        .map(|description| description.item.to_string()),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            scalars,
+            vec![
+                ("Date".to_string(), Some("A calendar date".to_string())),
+                ("Color".to_string(), Some("A color".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn implementor_with_all_interface_fields_is_valid() {
+        let result = process(
+            "interface Node { id: ID! }
+            type Query { node: Node }
+            type Pet implements Node { id: ID! name: String }",
+        )
+        .map(|_| ());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn implementor_missing_interface_field() {
+        let error = process(
+            "interface Named { id: ID! name: String }
+            type Query { named: Named }
+            type Pet implements Named { id: ID! }",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        match error.item {
+            ProcessGraphqlTypeSystemDefinitionError::MissingInterfaceField {
+                object,
+                interface,
+                field,
+            } => {
+                assert_eq!(object.to_string(), "Pet");
+                assert_eq!(interface.to_string(), "Named");
+                assert_eq!(field.to_string(), "name");
+            }
+            other => panic!("Unexpected error {other}"),
+        }
+    }
+
+    #[test]
+    fn every_invalid_implementation_is_reported_in_name_order() {
+        let source = "interface Named { id: ID! name: String }
+            interface Aged { age: Int }
+            type Query { named: Named aged: Aged }
+            type User implements Named & Aged { id: ID! }
+            type Pet implements Named & Aged { name: String }";
+
+        for _ in 0..10 {
+            let errors = process_graphql_type_system_document(
+                parse(source),
+                *REFETCH_FIELD_NAME,
+                DEFAULT_MAX_ERRORS,
+            )
+            .map(|_| ())
+            .expect_err("Expected errors");
+
+            assert_eq!(
+                errors
+                    .messages
+                    .iter()
+                    .map(|error| error.item.to_string())
+                    .collect::<Vec<_>>(),
+                [
+                    "Type Pet implements Aged, but does not define the field Aged.age.",
+                    "Type User implements Aged, but does not define the field Aged.age.",
+                    "Type Pet implements Named, but does not define the field Named.id.",
+                    "Type User implements Named, but does not define the field Named.name.",
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn implementor_with_covariant_field_types() {
+        process(
+            "interface Node { id: ID! }
+            interface Named { name: String friends: [Named] }
+            type Query { node: Node }
+            type Pet implements Node & Named { id: ID! name: String! friends: [Pet!]! }",
+        )
+        .map(|_| ())
+        .expect("Expected covariant field types to be accepted");
+    }
+
+    #[test]
+    fn implementor_may_narrow_interface_field_to_an_implementor() {
+        process(
+            "interface Node { id: ID! }
+            interface HasOwner { owner: Node }
+            type Query { node: Node }
+            type User implements Node { id: ID! }
+            type Pet implements Node & HasOwner { id: ID! owner: User }",
+        )
+        .map(|_| ())
+        .expect("Expected narrowing Node to User to be accepted");
+    }
+
+    #[test]
+    fn implementor_may_not_narrow_interface_field_to_an_unrelated_type() {
+        let error = process(
+            "interface Node { id: ID! }
+            interface HasOwner { owner: Node }
+            type Query { node: Node }
+            type Viewer { name: String }
+            type Pet implements Node & HasOwner { id: ID! owner: Viewer }",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::IncompatibleInterfaceFieldType {
+                object: "Pet".intern().into(),
+                interface: "HasOwner".intern().into(),
+                field: "owner".intern().into(),
+                interface_field_type: "Node".to_string(),
+                object_field_type: "Viewer".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn implementor_list_fields_must_match_interface_list_nullability() {
+        process(
+            "interface Node { id: ID! }
+            interface HasFriends { friends: [Node] }
+            type Query { node: Node }
+            type Pet implements Node & HasFriends { id: ID! friends: [Pet!]! }",
+        )
+        .map(|_| ())
+        .expect("Expected non-null lists and items to satisfy nullable ones");
+
+        process(
+            "interface Node { id: ID! }
+            interface HasFriends { friends: [Node!]! }
+            type Query { node: Node }
+            type Pet implements Node & HasFriends { id: ID! friends: [Node!]! }",
+        )
+        .map(|_| ())
+        .expect("Expected an identical list type to satisfy the interface");
+
+        for (object_field_type, error_message) in [
+            (
+                "[Node]",
+                "Expected a nullable list of nullable items to fail",
+            ),
+            ("[Pet]", "Expected a nullable list to fail"),
+            ("[Pet]!", "Expected nullable items to fail"),
+            ("Pet!", "Expected a non-list to fail"),
+            ("[[Pet!]!]!", "Expected a nested list to fail"),
+        ] {
+            let error = process(&format!(
+                "interface Node {{ id: ID! }}
+                interface HasFriends {{ friends: [Node!]! }}
+                type Query {{ node: Node }}
+                type Pet implements Node & HasFriends {{ id: ID! friends: {object_field_type} }}"
+            ))
+            .map(|_| ())
+            .expect_err(error_message);
+
+            assert_eq!(
+                error.item,
+                ProcessGraphqlTypeSystemDefinitionError::IncompatibleInterfaceFieldType {
+                    object: "Pet".intern().into(),
+                    interface: "HasFriends".intern().into(),
+                    field: "friends".intern().into(),
+                    interface_field_type: "[Node!]!".to_string(),
+                    object_field_type: object_field_type.to_string(),
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn implementor_may_narrow_interface_field_to_a_transitive_subtype() {
+        process(
+            "interface Thing { name: String }
+            interface Entity implements Thing { name: String }
+            interface Node implements Entity { id: ID! name: String }
+            interface HasOwner { owner: Thing }
+            type Query { node: Node }
+            type Pet implements Node { id: ID! name: String }
+            type Toy implements HasOwner { owner: Node }",
+        )
+        .map(|_| ())
+        .expect("Expected narrowing Thing to Node to be accepted");
+    }
+
+    #[test]
+    fn interface_must_define_fields_of_implemented_interfaces() {
+        let error = process(
+            "interface Entity { name: String }
+            interface Node implements Entity { id: ID! }
+            type Query { node: Node }",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::MissingInterfaceField {
+                object: "Node".intern().into(),
+                interface: "Entity".intern().into(),
+                field: "name".intern().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn implementor_must_accept_interface_field_arguments() {
+        process(
+            "interface Named { name(format: String): String }
+            type Query { named: Named }
+            type Pet implements Named { name(format: String, locale: String = \"en\"): String }",
+        )
+        .map(|_| ())
+        .expect("Expected additional optional arguments to be accepted");
+
+        let error = process(
+            "interface Named { name(format: String): String }
+            type Query { named: Named }
+            type Pet implements Named { name: String }",
+        )
+        .map(|_| ())
+        .expect_err("Expected a missing argument to fail");
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::MissingInterfaceFieldArgument {
+                object: "Pet".intern().into(),
+                interface: "Named".intern().into(),
+                field: "name".intern().into(),
+                argument: "format".intern().into(),
+            }
+        );
+
+        let error = process(
+            "interface Named { name(format: String): String }
+            type Query { named: Named }
+            type Pet implements Named { name(format: String!): String }",
+        )
+        .map(|_| ())
+        .expect_err("Expected an argument with a different type to fail");
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::InterfaceFieldArgumentTypeMismatch {
+                object: "Pet".intern().into(),
+                interface: "Named".intern().into(),
+                field: "name".intern().into(),
+                argument: "format".intern().into(),
+                interface_argument_type: "String".to_string(),
+                object_argument_type: "String!".to_string(),
+            }
+        );
+
+        let error = process(
+            "interface Named { name: String }
+            type Query { named: Named }
+            type Pet implements Named { name(locale: String!): String }",
+        )
+        .map(|_| ())
+        .expect_err("Expected an additional required argument to fail");
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::RequiredArgumentNotInInterfaceField {
+                object: "Pet".intern().into(),
+                interface: "Named".intern().into(),
+                field: "name".intern().into(),
+                argument: "locale".intern().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn implementor_with_incompatible_field_type() {
+        let error = process(
+            "interface Node { id: ID! }
+            type Query { node: Node }
+            type Pet implements Node { id: String }",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert!(matches!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::IncompatibleInterfaceFieldType { .. }
+        ));
+    }
+
+    #[test]
+    fn custom_refetch_field_name() {
+        let refetch_field_name: SelectableName = "__reload".intern().into();
+        let (_, _, refetch_fields) = process_graphql_type_system_document(
+            parse(
+                "interface Node { id: ID! }
+                type Query { node: Node }
+                type Pet implements Node { id: ID! }",
+            ),
+            refetch_field_name,
+            DEFAULT_MAX_ERRORS,
+        )
+        .expect("Expected schema to be processed");
+
+        assert_eq!(refetch_fields.len(), 1);
+        assert_eq!(
+            refetch_fields[0].expose_field_directive.expose_as,
+            Some(refetch_field_name)
+        );
+    }
+
+    #[test]
+    fn refetch_field_name_collision() {
+        let error = process(
+            "interface Node { id: ID! }
+            type Query { node: Node }
+            type Pet implements Node { id: ID! __refetch: String }",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        match error.item {
+            ProcessGraphqlTypeSystemDefinitionError::RefetchFieldNameCollision {
+                type_name,
+                refetch_field_name,
+            } => {
+                assert_eq!(type_name.to_string(), "Pet");
+                assert_eq!(refetch_field_name, *REFETCH_FIELD_NAME);
+            }
+            other => panic!("Unexpected error {other}"),
+        }
+    }
+
+    #[test]
+    fn all_failing_extensions_are_reported() {
+        let text_source = TextSource {
+            relative_path_to_source_file: "dummy".intern().into(),
+            span: None,
+            current_working_directory: "cwd".intern().into(),
+        };
+        let extension_document = parse_schema_extensions(
+            "extend type Mutation @exposeField(fieldMap: [{ from: \"id\", to: \"id\" }])
+            extend type Query @exposeField(field: \"node.asPet\", as: \"refetch_pet\")
+            extend type Subscription @exposeField(as: \"watch_pet\")",
+            text_source,
+        )
+        .expect("Expected extensions to parse");
+
+        let errors = process_graphql_type_extension_document(
+            extension_document,
+            *REFETCH_FIELD_NAME,
+            DEFAULT_MAX_ERRORS,
+        )
+        .map(|_| ())
+        .expect_err("Expected errors");
+
+        assert_eq!(errors.messages.len(), 2);
+        for error in errors.messages {
+            assert!(matches!(
+                error.item,
+                ProcessGraphqlTypeSystemDefinitionError::CreateAdditionalFieldsError(
+                    CreateAdditionalFieldsError::MissingExposeFieldDirectiveArguments { .. }
+                )
+            ));
+        }
+    }
+
+    #[test]
+    fn colliding_field_map_targets_are_reported() {
+        let text_source = TextSource {
+            relative_path_to_source_file: "dummy".intern().into(),
+            span: None,
+            current_working_directory: "cwd".intern().into(),
+        };
+        let extension_document = parse_schema_extensions(
+            "extend type Mutation @exposeField(field: \"setName\", fieldMap: [\
+            { from: \"id\", to: \"input.user.id\" }, \
+            { from: \"name\", to: \"input.user.name\" }, \
+            { from: \"userId\", to: \"input.user.id\" }])",
+            text_source,
+        )
+        .expect("Expected extensions to parse");
+
+        let errors = process_graphql_type_extension_document(
+            extension_document,
+            *REFETCH_FIELD_NAME,
+            DEFAULT_MAX_ERRORS,
+        )
+        .map(|_| ())
+        .expect_err("Expected errors");
+
+        assert_eq!(errors.messages.len(), 1);
+        let error = &errors.messages[0];
+        match &error.item {
+            ProcessGraphqlTypeSystemDefinitionError::CreateAdditionalFieldsError(
+                CreateAdditionalFieldsError::DuplicateFieldMapTarget {
+                    path,
+                    previous_location,
+                },
+            ) => {
+                assert_eq!(*path, "input.user.id");
+                // The first and third items of the field map
+                assert_eq!(previous_location.span().map(|span| span.start), Some(63));
+                assert_eq!(error.location.span().map(|span| span.start), Some(141));
+            }
+            other => panic!("Unexpected error {other}"),
+        }
+    }
+
+    #[test]
+    fn malformed_field_map_is_reported_with_its_value() {
+        let text_source = TextSource {
+            relative_path_to_source_file: "dummy".intern().into(),
+            span: None,
+            current_working_directory: "cwd".intern().into(),
+        };
+        let extension_document = parse_schema_extensions(
+            "extend type Mutation @exposeField(field: \"setName\", fieldMap: [{ from: \"id\" }])",
+            text_source,
+        )
+        .expect("Expected extensions to parse");
+
+        let errors = process_graphql_type_extension_document(
+            extension_document,
+            *REFETCH_FIELD_NAME,
+            DEFAULT_MAX_ERRORS,
+        )
+        .map(|_| ())
+        .expect_err("Expected errors");
+
+        assert_eq!(errors.messages.len(), 1);
+        match &errors.messages[0].item {
+            ProcessGraphqlTypeSystemDefinitionError::CreateAdditionalFieldsError(
+                error @ CreateAdditionalFieldsError::InvalidFieldMap { value },
+            ) => {
+                assert_eq!(value, "[{from: \"id\"}]");
+                assert!(error.to_string().contains(value));
+            }
+            other => panic!("Unexpected error {other}"),
+        }
+    }
+
+    #[test]
+    fn empty_mutation_type() {
+        let error = process(
+            "type Query { id: ID! }
+            type Mutation",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::EmptyMutationType {
+                type_name: "Mutation".intern().into()
+            }
+        );
+    }
+
+    #[test]
+    fn refinement_pass_is_skipped_without_refinements() {
+        let source = "type Query { viewer: User }
+            type User { id: ID!, name: String }";
+        let skipped = process(source).expect("Expected schema to be processed");
+
+        let mut objects = process(source)
+            .expect("Expected schema to be processed")
+            .objects;
+        let supertype_to_subtype_map = HashMap::new();
+        validate_interface_implementations(&objects, &supertype_to_subtype_map)
+            .expect("Expected implementations to be valid");
+        insert_refinement_fields(&mut objects, &supertype_to_subtype_map)
+            .map_err(|_| ())
+            .expect("Expected refinement fields to be inserted");
+
+        assert_eq!(format!("{:?}", skipped.objects), format!("{objects:?}"));
+    }
+
+    #[test]
+    fn subscription_root_is_detected() {
+        let subscription_root = |source: &str| {
+            process(source)
+                .expect("Expected schema to be processed")
+                .objects
+                .into_iter()
+                .filter(|(outcome, _)| {
+                    matches!(
+                        outcome.encountered_root_kind,
+                        Some(RootOperationKind::Subscription)
+                    )
+                })
+                .map(|(outcome, _)| outcome.server_object_entity.name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            subscription_root(
+                "type Query { id: ID! }
+                type Subscription { petAdded: ID! }"
+            ),
+            vec!["Subscription"]
+        );
+        assert_eq!(
+            subscription_root(
+                "schema { query: Query, subscription: Events }
+                type Query { id: ID! }
+                type Subscription { petAdded: ID! }
+                type Events { petAdded: ID! }"
+            ),
+            vec!["Events"]
+        );
+        assert!(subscription_root("type Query { id: ID! }").is_empty());
+    }
+
+    /// Processes a schema and an extension of it the way the compiler does, i.e. with
+    /// GraphQLNetworkProtocol::parse_and_process_type_system_documents.
+    fn process_with_extension(
+        source: &str,
+        extension_source: &str,
+    ) -> Result<
+        ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol>,
+        WithLocation<ProcessGraphqlTypeSystemDefinitionError>,
+    > {
+        let mut db = Database::new();
+        let mut schema_source = |relative_path: RelativePathToSourceFile, content: &str| {
+            db.set(SchemaSource {
+                relative_path,
+                content: content.to_string(),
+                text_source: TextSource {
+                    relative_path_to_source_file: relative_path,
+                    span: None,
+                    current_working_directory: "cwd".intern().into(),
+                },
+            })
+        };
+        let schema_source_id = schema_source("schema.graphql".intern().into(), source);
+        let extension_path = "extension.graphql".intern().into();
+        let extension_source_id = schema_source(extension_path, extension_source);
+
+        GraphQLNetworkProtocol::parse_and_process_type_system_documents(
+            &db,
+            &(
+                schema_source_id,
+                BTreeMap::from([(extension_path, extension_source_id)]),
+            ),
+            &CompilerConfigOptions::default(),
+        )
+        .map_err(|error| {
+            error
+                .downcast::<ProcessGraphqlTypeSystemDefinitionErrors>()
+                .expect("Expected ProcessGraphqlTypeSystemDefinitionErrors")
+                .messages
+                .into_iter()
+                .next()
+                .expect("Expected at least one error")
+        })
+    }
+
+    #[test]
+    fn extend_interface_adds_fields() {
+        let outcome = process_with_extension(
+            "interface Node { id: ID! }
+            type Query { node: Node }",
+            "extend interface Node { createdAt: String }",
+        )
+        .expect("Expected schema to be processed");
+
+        let (node, _) = outcome
+            .objects
+            .iter()
+            .find(|(object, _)| object.server_object_entity.name == "Node")
+            .expect("Expected Node to be defined");
+        let field_names = node
+            .fields_to_insert
+            .iter()
+            .map(|field| field.item.name.item.to_string())
+            .collect::<Vec<_>>();
+        assert!(field_names.contains(&"createdAt".to_string()));
+    }
+
+    #[test]
+    fn extend_interface_requires_field_on_implementors() {
+        let error = process_with_extension(
+            "interface Node { id: ID! }
+            type Pet implements Node { id: ID! }
+            type Query { node: Node }",
+            "extend interface Node { createdAt: String }",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::MissingInterfaceField {
+                object: "Pet".intern().into(),
+                interface: "Node".intern().into(),
+                field: "createdAt".intern().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn extend_interface_merges_implemented_interfaces() {
+        let outcome = process_with_extension(
+            "interface Entity { name: String }
+            interface Node { id: ID! name: String! }
+            type Pet implements Node { id: ID! name: String! }
+            type Query { node: Node }",
+            "extend interface Node implements Entity",
+        )
+        .expect("Expected schema to be processed");
+
+        let (entity, _) = outcome
+            .objects
+            .iter()
+            .find(|(object, _)| object.server_object_entity.name == "Entity")
+            .expect("Expected Entity to be defined");
+        let refinements = entity
+            .fields_to_insert
+            .iter()
+            .filter(|field| field.item.is_inline_fragment)
+            .map(|field| field.item.type_.inner().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(refinements, ["Pet"]);
+    }
+
+    #[test]
+    fn extend_interface_does_not_refine_to_interfaces() {
+        let outcome = process_with_extension(
+            "interface Thing { name: String }
+            interface Entity { name: String }
+            interface Node { id: ID! name: String! }
+            type Pet implements Node { id: ID! name: String! }
+            type Query { node: Node }",
+            "extend interface Node implements Entity
+            extend interface Entity implements Thing",
+        )
+        .expect("Expected schema to be processed");
+
+        let (thing, _) = outcome
+            .objects
+            .iter()
+            .find(|(object, _)| object.server_object_entity.name == "Thing")
+            .expect("Expected Thing to be defined");
+        let refinements = thing
+            .fields_to_insert
+            .iter()
+            .filter(|field| field.item.is_inline_fragment)
+            .map(|field| field.item.type_.inner().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(refinements, ["Pet"]);
+    }
+
+    #[test]
+    fn extend_interface_requires_fields_of_implemented_interfaces() {
+        let error = |extension_source: &str| {
+            process_with_extension(
+                "interface Entity { name: String }
+                interface Node { id: ID! }
+                type Pet implements Node { id: ID! name: String }
+                type Query { node: Node }",
+                extension_source,
+            )
+            .map(|_| ())
+            .expect_err("Expected error")
+            .item
+        };
+
+        assert_eq!(
+            error("extend interface Node implements Entity"),
+            ProcessGraphqlTypeSystemDefinitionError::MissingInterfaceField {
+                object: "Node".intern().into(),
+                interface: "Entity".intern().into(),
+                field: "name".intern().into(),
+            }
+        );
+        assert_eq!(
+            error("extend interface Node implements Pet"),
+            ProcessGraphqlTypeSystemDefinitionError::ImplementedTypeIsNotAnInterface {
+                type_name: "Node".intern().into(),
+                implemented_type_name: "Pet".intern().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn extend_interface_on_object_is_an_error() {
+        let error = process_with_extension(
+            "type Query { id: ID! }",
+            "extend interface Query { createdAt: String }",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::ExtendedTypeIsNotAnInterface {
+                type_name: "Query".intern().into()
+            }
+        );
+    }
+
+    #[test]
+    fn extend_interface_without_fields_is_an_interface_extension() {
+        process_with_extension(
+            "interface Node { id: ID! }
+            type Query { node: Node }",
+            "extend interface Node @live",
+        )
+        .expect("Expected schema to be processed");
+
+        let error = process_with_extension(
+            "type Post { id: ID! }
+            union SearchResult = Post
+            type Query { search: SearchResult }",
+            "extend interface SearchResult @live",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::ExtendedTypeIsNotAnInterface {
+                type_name: "SearchResult".intern().into()
+            }
+        );
+    }
+
+    #[test]
+    fn extend_union_adds_refinements() {
+        let outcome = process_with_extension(
+            "type Post { id: ID! }
+            type Comment { id: ID! }
+            union SearchResult = Post
+            type Query { search: SearchResult }",
+            "extend union SearchResult = Comment",
+        )
+        .expect("Expected schema to be processed");
+
+        let (search_result, _) = outcome
+            .objects
+            .iter()
+            .find(|(object, _)| object.server_object_entity.name == "SearchResult")
+            .expect("Expected SearchResult to be defined");
+        let refinements = search_result
+            .fields_to_insert
+            .iter()
+            .filter(|field| field.item.is_inline_fragment)
+            .map(|field| field.item.type_.inner().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(refinements, ["Post", "Comment"]);
+    }
+
+    #[test]
+    fn extend_union_on_interface_is_an_error() {
+        let error = process_with_extension(
+            "interface Node { id: ID! }
+            type Pet implements Node { id: ID! }
+            type Query { node: Node }",
+            "extend union Node = Pet",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::ExtendedTypeIsNotAUnion {
+                type_name: "Node".intern().into()
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_refinement_does_not_prevent_valid_refinements() {
+        let mut outcome = process(
+            "interface Node { id: ID! }
+            type Pet { id: ID! }
+            scalar Foo
+            type Query { node: Node }",
+        )
+        .expect("Expected schema to be processed");
+        let supertype_to_subtype_map = [
+            ("Node".intern().into(), vec!["Pet".intern().into()]),
+            ("Foo".intern().into(), vec!["Pet".intern().into()]),
+        ]
+        .into_iter()
+        .collect();
+
+        let errors = insert_refinement_fields(&mut outcome.objects, &supertype_to_subtype_map)
+            .expect_err("Expected error");
+
+        assert_eq!(errors.messages.len(), 1);
+        assert_eq!(
+            errors.messages[0].item,
+            ProcessGraphqlTypeSystemDefinitionError::AttemptedToImplementNonExistentType {
+                subtype_name: "Pet".intern().into(),
+                supertype_name: "Foo".intern().into(),
+            }
+        );
+        let (node, _) = outcome
+            .objects
+            .iter()
+            .find(|(object, _)| object.server_object_entity.name == "Node")
+            .expect("Expected Node to be defined");
+        assert!(node
+            .fields_to_insert
+            .iter()
+            .any(|field| { field.item.is_inline_fragment && field.item.name.item == "asPet" }));
+    }
+
+    #[test]
+    fn errors_are_truncated_at_max_errors() {
+        let source = (0..5)
+            .map(|index| format!("type Pet{index} implements Missing{index} {{ id: ID! }}"))
+            .chain(std::iter::once("type Query { id: ID! }".to_string()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let errors = process_graphql_type_system_document(parse(&source), *REFETCH_FIELD_NAME, 3)
+            .map(|_| ())
+            .expect_err("Expected errors");
+
+        assert_eq!(errors.messages.len(), 4);
+        assert!(errors.messages[..3].iter().all(|error| matches!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::AttemptedToImplementNonExistentType { .. }
+        )));
+        assert_eq!(
+            errors.messages[3].item,
+            ProcessGraphqlTypeSystemDefinitionError::AdditionalErrorsSuppressed { count: 2 }
+        );
+
+        let errors = process_graphql_type_system_document(parse(&source), *REFETCH_FIELD_NAME, 5)
+            .map(|_| ())
+            .expect_err("Expected errors");
+        assert_eq!(errors.messages.len(), 5);
+    }
+
+    #[test]
+    fn refinements_are_inserted_in_a_stable_order() {
+        let refinement_names = || {
+            let outcome = process(
+                "interface Node { id: ID! }
+                union SearchResult = Toy | Pet | Owner
+                type Toy implements Node { id: ID! }
+                type Pet implements Node { id: ID! }
+                type Owner implements Node { id: ID! }
+                type Query { node: Node search: SearchResult }",
+            )
+            .expect("Expected schema to be processed");
+            outcome
+                .objects
+                .iter()
+                .map(|(object, _)| {
+                    (
+                        object.server_object_entity.name.to_string(),
+                        object
+                            .fields_to_insert
+                            .iter()
+                            .filter(|field| field.item.is_inline_fragment)
+                            .map(|field| field.item.name.item.to_string())
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .filter(|(_, refinements)| !refinements.is_empty())
+                .collect::<Vec<_>>()
+        };
+
+        let expected_refinement_names = vec![
+            (
+                "Node".to_string(),
+                vec![
+                    "asOwner".to_string(),
+                    "asPet".to_string(),
+                    "asToy".to_string(),
+                ],
+            ),
+            (
+                "SearchResult".to_string(),
+                vec![
+                    "asOwner".to_string(),
+                    "asPet".to_string(),
+                    "asToy".to_string(),
+                ],
+            ),
+        ];
+        for _ in 0..10 {
+            assert_eq!(refinement_names(), expected_refinement_names);
+        }
+    }
+
+    #[test]
+    fn deprecated_arguments_have_a_deprecation_reason() {
+        let outcome = process(
+            "type Query {
+                pet(id: ID @deprecated(reason: \"Use petId\"), name: String @deprecated, petId: ID): String
+            }",
+        )
+        .expect("Expected schema to be processed");
+
+        let (query, _) = outcome
+            .objects
+            .iter()
+            .find(|(object, _)| object.server_object_entity.name == "Query")
+            .expect("Expected Query to be defined");
+        let pet_field = query
+            .fields_to_insert
+            .iter()
+            .find(|field| field.item.name.item == "pet")
+            .expect("Expected pet field to exist");
+        let deprecation_reasons = pet_field
+            .item
+            .arguments
+            .iter()
+            .map(|argument| {
+                argument
+                    .item
+                    .deprecation_reason()
+                    .map(|reason| reason.to_string())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            deprecation_reasons,
+            vec![
+                Some("Use petId".to_string()),
+                Some("No longer supported".to_string()),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn self_implementation() {
+        let error = process(
+            "type Query { node: Node }
+            type Node implements Node { id: ID! }",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::SelfImplementation {
+                type_name: "Node".intern().into()
+            }
+        );
+    }
+
+    #[test]
+    fn duplicate_argument() {
+        let error = process("type Query { pets(first: Int, first: Int): [ID] }")
+            .map(|_| ())
+            .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::DuplicateArgument {
+                parent_type: "Query".intern().into(),
+                field_name: "pets".intern().into(),
+                argument_name: "first".intern().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn specified_by_url_is_extracted() {
+        let outcome = process(
+            "type Query { now: DateTime }
+            scalar DateTime @specifiedBy(url: \"https://scalars.graphql.org/andimarek/date-time\")
+            scalar Json",
+        )
+        .expect("Expected schema to be processed");
+
+        let specified_by_urls = outcome
+            .scalars
+            .iter()
+            .map(|(scalar, _)| {
+                (
+                    scalar.name.item.to_string(),
+                    scalar.specified_by_url.map(|url| url.to_string()),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            specified_by_urls,
+            vec![
+                (
+                    "DateTime".to_string(),
+                    Some("https://scalars.graphql.org/andimarek/date-time".to_string())
+                ),
+                ("Json".to_string(), None)
+            ]
+        );
+    }
+
+    #[test]
+    fn node_implementor_without_id_field() {
+        let outcome = process(
+            "interface Node { name: String }
+            type Pet implements Node { name: String }
+            type User implements Node { id: ID! name: String }
+            type Query { node: Node }",
+        )
+        .expect("Expected schema to be processed");
+
+        validate_node_implementors_have_id_fields(
+            &outcome.objects,
+            OptionalValidationLevel::Ignore,
+        )
+        .expect("Expected missing id fields to be ignored by default");
+        let error = validate_node_implementors_have_id_fields(
+            &outcome.objects,
+            OptionalValidationLevel::Error,
+        )
+        .expect_err("Expected error");
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::MissingIdField {
+                parent_type: "Pet".intern().into()
+            }
+        );
+    }
+
+    #[test]
+    fn input_type_in_output_position() {
+        let outcome = process(
+            "input UserInput { name: String, address: AddressInput }
+            input AddressInput { city: String }
+            type User { name: String }
+            type Query { user(input: UserInput): User, lastInput: UserInput }",
+        )
+        .expect("Expected schema to be processed");
+
+        let error = validate_no_input_types_in_output_position(&outcome.objects)
+            .expect_err("Expected error");
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::InputTypeInOutputPosition {
+                parent_type: "Query".intern().into(),
+                field_name: "lastInput".intern().into(),
+                type_name: "UserInput".intern().into(),
+            }
+        );
+
+        let outcome = process(
+            "input UserInput { name: String, address: AddressInput }
+            input AddressInput { city: String }
+            type User { name: String }
+            type Query { user(input: UserInput): User }",
+        )
+        .expect("Expected schema to be processed");
+        validate_no_input_types_in_output_position(&outcome.objects)
+            .expect("Expected input objects in input positions to be valid");
+    }
+
+    #[test]
+    fn one_of_input_objects_are_detected() {
+        let outcome = process(
+            "input UserBy @oneOf { id: ID, email: String }
+            input UserInput { name: String }
+            type Query { name: String }",
+        )
+        .expect("Expected schema to be processed");
+
+        let is_one_of = outcome
+            .objects
+            .iter()
+            .map(|(object, _)| {
+                (
+                    object.server_object_entity.name.to_string(),
+                    object.server_object_entity.output_associated_data.is_one_of,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            is_one_of,
+            vec![
+                ("UserBy".to_string(), true),
+                ("UserInput".to_string(), false),
+                ("Query".to_string(), false)
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_field_directive() {
+        let source = "directive @auth on FIELD_DEFINITION
+            type Query {
+                name: String @deprecated
+                email: String @auth
+                age: Int @unknown
+            }";
+        let outcome = process(source).expect("Expected schema to be processed");
+        let known_field_directives = known_field_directives(&parse(source), std::iter::empty());
+
+        validate_field_directives(
+            &outcome.objects,
+            &known_field_directives,
+            OptionalValidationLevel::Ignore,
+        )
+        .expect("Expected unknown field directives to be ignored by default");
+        let error = validate_field_directives(
+            &outcome.objects,
+            &known_field_directives,
+            OptionalValidationLevel::Error,
+        )
+        .expect_err("Expected error");
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::UnknownFieldDirective {
+                parent_type: "Query".intern().into(),
+                field_name: "age".intern().into(),
+                directive_name: "unknown".intern().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn non_repeatable_directives_cannot_be_applied_twice() {
+        process(
+            "directive @tag(name: String) repeatable on FIELD_DEFINITION
+            type Query {
+                name: String @tag(name: \"a\") @tag(name: \"b\")
+            }",
+        )
+        .expect("Expected repeatable directives to be allowed more than once");
+
+        let error = process(
+            "directive @auth on FIELD_DEFINITION | OBJECT
+            type Query @auth {
+                name: String @auth
+                email: String @auth @auth
+            }",
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::DuplicateDirective {
+                directive_name: "auth".intern().into(),
+            }
+        );
+
+        let error = process("type Query { name: String @deprecated @deprecated }")
+            .map(|_| ())
+            .expect_err("Expected error");
+        assert_eq!(
+            error.item,
+            ProcessGraphqlTypeSystemDefinitionError::DuplicateDirective {
+                directive_name: "deprecated".intern().into(),
+            }
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use common_lang_types::{QueryOperationName, QueryText, UnvalidatedTypeName};
 use graphql_lang_types::GraphQLTypeAnnotation;
-use isograph_lang_types::{ArgumentKeyAndValue, NonConstantValue};
+use intern::string_key::Lookup;
+use isograph_lang_types::{quote_string_literal, ArgumentKeyAndValue, NonConstantValue};
 use isograph_schema::{
     MergedSelectionMap, MergedServerSelection, RootOperationName, ServerScalarOrObjectEntity,
     ValidatedVariableDefinition,
@@ -148,8 +149,7 @@ fn serialize_non_constant_value_for_graphql(value: &NonConstantValue) -> String
         NonConstantValue::Variable(variable_name) => format!("${}", variable_name),
         NonConstantValue::Integer(int_value) => int_value.to_string(),
         NonConstantValue::Boolean(bool) => bool.to_string(),
-        // This clearly isn't correct — the string might have quotes in it and such
-        NonConstantValue::String(s) => format!("\"{}\"", s),
+        NonConstantValue::String(s) => quote_string_literal(s.lookup()),
         NonConstantValue::Float(f) => f.as_float().to_string(),
         NonConstantValue::Null => "null".to_string(),
         NonConstantValue::Enum(e) => e.to_string(),
@@ -14,12 +14,12 @@ use graphql_lang_types::{
     DirectiveLocation, GraphQLConstantValue, GraphQLDirective, GraphQLDirectiveDefinition,
     GraphQLEnumDefinition, GraphQLEnumValueDefinition, GraphQLFieldDefinition,
     GraphQLInputObjectTypeDefinition, GraphQLInputValueDefinition, GraphQLInterfaceTypeDefinition,
-    GraphQLListTypeAnnotation, GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation,
-    GraphQLObjectTypeDefinition, GraphQLObjectTypeExtension, GraphQLScalarTypeDefinition,
-    GraphQLSchemaDefinition, GraphQLTypeAnnotation, GraphQLTypeSystemDefinition,
-    GraphQLTypeSystemDocument, GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
-    GraphQLTypeSystemExtensionOrDefinition, GraphQLUnionTypeDefinition, NameValuePair,
-    RootOperationKind,
+    GraphQLInterfaceTypeExtension, GraphQLListTypeAnnotation, GraphQLNamedTypeAnnotation,
+    GraphQLNonNullTypeAnnotation, GraphQLObjectTypeDefinition, GraphQLObjectTypeExtension,
+    GraphQLScalarTypeDefinition, GraphQLSchemaDefinition, GraphQLTypeAnnotation,
+    GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument, GraphQLTypeSystemExtension,
+    GraphQLTypeSystemExtensionDocument, GraphQLTypeSystemExtensionOrDefinition,
+    GraphQLUnionTypeDefinition, GraphQLUnionTypeExtension, NameValuePair, RootOperationKind,
 };
 
 use crate::ParseResult;
@@ -109,6 +109,10 @@ fn parse_type_system_extension(
         match identifier.item {
             "type" => parse_object_type_extension(tokens, text_source)
                 .map(GraphQLTypeSystemExtension::from),
+            "interface" => parse_interface_type_extension(tokens, text_source)
+                .map(GraphQLTypeSystemExtension::from),
+            "union" => parse_union_type_extension(tokens, text_source)
+                .map(GraphQLTypeSystemExtension::from),
             _ => Err(WithSpan::new(
                 SchemaParseError::TopLevelSchemaDeclarationExpected {
                     found_text: identifier.to_string(),
@@ -205,6 +209,28 @@ fn parse_object_type_extension(
     })
 }
 
+/// The state of the PeekableLexer is that it has processed the "interface" keyword
+fn parse_interface_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLInterfaceTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map(|with_span| with_span.to_with_location(text_source))
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?;
+
+    let interfaces = parse_implements_interfaces_if_present(tokens, text_source)?;
+    let directives = parse_constant_directives(tokens, text_source)?;
+    let fields = parse_optional_fields(tokens, text_source)?;
+
+    Ok(GraphQLInterfaceTypeExtension {
+        name,
+        interfaces,
+        directives,
+        fields,
+    })
+}
+
 /// The state of the PeekableLexer is that it has processed the "interface" keyword
 fn parse_interface_type_definition(
     tokens: &mut PeekableLexer,
@@ -427,6 +453,33 @@ fn parse_union_definition(
     })
 }
 
+/// The state of the PeekableLexer is that it has processed the "union" keyword.
+/// Unlike in union definitions, the member types are optional, since an extension
+/// may only add directives.
+fn parse_union_type_extension(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResult<GraphQLUnionTypeExtension> {
+    let name = tokens
+        .parse_string_key_type(TokenKind::Identifier)
+        .map_err(|with_span| with_span.map(SchemaParseError::from))?
+        .to_with_location(text_source);
+
+    let directives = parse_constant_directives(tokens, text_source)?;
+
+    let union_member_types = if tokens.parse_token_of_kind(TokenKind::Equals).is_ok() {
+        parse_union_member_types(tokens, text_source)?
+    } else {
+        vec![]
+    };
+
+    Ok(GraphQLUnionTypeExtension {
+        name,
+        directives,
+        union_member_types,
+    })
+}
+
 fn parse_union_member_types(
     tokens: &mut PeekableLexer,
     text_source: TextSource,
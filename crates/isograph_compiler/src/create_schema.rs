@@ -18,11 +18,13 @@ use isograph_lang_types::{
     TypeAnnotation, VariableDefinition,
 };
 use isograph_schema::{
-    validate_entrypoints, CreateAdditionalFieldsError, FieldToInsert, NetworkProtocol,
-    ProcessObjectTypeDefinitionOutcome, ProcessTypeSystemDocumentOutcome, RootOperationName,
-    Schema, SchemaServerObjectSelectableVariant, ServerObjectSelectable, ServerScalarSelectable,
+    validate_entrypoints, CreateAdditionalFieldsError, ExposeAsFieldToInsert, FieldToInsert,
+    JavascriptImport, NetworkProtocol, ProcessObjectTypeDefinitionOutcome,
+    ProcessTypeSystemDocumentOutcome, RootOperationName, Schema, SchemaCapacityHint,
+    SchemaServerObjectSelectableVariant, ServerObjectSelectable, ServerScalarSelectable,
 };
 use pico::{Database, SourceId};
+use tracing::warn;
 
 use crate::{
     add_selection_sets::add_selection_sets_to_client_selectables,
@@ -36,56 +38,10 @@ pub fn create_schema<TNetworkProtocol: NetworkProtocol>(
     iso_literals: &HashMap<RelativePathToSourceFile, SourceId<IsoLiteralsSource>>,
     config: &CompilerConfig,
 ) -> Result<(Schema<TNetworkProtocol>, ContainsIsoStats), Box<dyn Error>> {
-    let ProcessTypeSystemDocumentOutcome { scalars, objects } =
-        TNetworkProtocol::parse_and_process_type_system_documents(db, sources)?;
-
-    let mut unvalidated_isograph_schema = Schema::<TNetworkProtocol>::new();
-    for (server_scalar_entity, name_location) in scalars {
-        unvalidated_isograph_schema
-            .server_entity_data
-            .insert_server_scalar_entity(server_scalar_entity, name_location)?;
-    }
-
-    let mut field_queue = HashMap::new();
-    let mut expose_as_field_queue = HashMap::new();
-    for (
-        ProcessObjectTypeDefinitionOutcome {
-            encountered_root_kind,
-            server_object_entity,
-            fields_to_insert,
-            expose_as_fields_to_insert,
-        },
-        name_location,
-    ) in objects
-    {
-        let new_object_id = unvalidated_isograph_schema
-            .server_entity_data
-            .insert_server_object_entity(server_object_entity, name_location)?;
-        field_queue.insert(new_object_id, fields_to_insert);
-
-        match encountered_root_kind {
-            Some(RootOperationKind::Query) => {
-                unvalidated_isograph_schema
-                    .fetchable_types
-                    .insert(new_object_id, RootOperationName("query".to_string()));
-            }
-            Some(RootOperationKind::Mutation) => {
-                unvalidated_isograph_schema
-                    .fetchable_types
-                    .insert(new_object_id, RootOperationName("mutation".to_string()));
-            }
-            // TODO handle Subscription
-            _ => {}
-        }
-
-        expose_as_field_queue.insert(new_object_id, expose_as_fields_to_insert);
-    }
-
-    process_field_queue(
-        &mut unvalidated_isograph_schema,
-        field_queue,
-        &config.options,
-    )?;
+    let outcome =
+        TNetworkProtocol::parse_and_process_type_system_documents(db, sources, &config.options)?;
+    let (mut unvalidated_isograph_schema, expose_as_field_queue) =
+        create_server_schema(outcome, &config.options)?;
 
     // Step one: we can create client selectables. However, we must create all
     // client selectables before being able to create their selection sets, because
@@ -96,8 +52,9 @@ pub fn create_schema<TNetworkProtocol: NetworkProtocol>(
 
     for (parent_object_entity_id, expose_as_fields_to_insert) in expose_as_field_queue {
         for expose_as_field in expose_as_fields_to_insert {
-            let unprocessed_scalar_item = unvalidated_isograph_schema
-                .create_new_exposed_field(expose_as_field, parent_object_entity_id)?;
+            let (unprocessed_scalar_item, _implementor_object_entity_ids) =
+                unvalidated_isograph_schema
+                    .create_new_exposed_field(expose_as_field, parent_object_entity_id)?;
 
             unprocessed_items.push(SelectionType::Scalar(unprocessed_scalar_item));
         }
@@ -141,6 +98,88 @@ pub fn create_schema<TNetworkProtocol: NetworkProtocol>(
     Ok((unvalidated_isograph_schema, contains_iso_stats))
 }
 
+type ExposeAsFieldQueue = HashMap<ServerObjectEntityId, Vec<ExposeAsFieldToInsert>>;
+
+/// Insert the processed scalars and objects, and then their fields, into a new schema.
+fn create_server_schema<TNetworkProtocol: NetworkProtocol>(
+    outcome: ProcessTypeSystemDocumentOutcome<TNetworkProtocol>,
+    options: &CompilerConfigOptions,
+) -> Result<(Schema<TNetworkProtocol>, ExposeAsFieldQueue), Box<dyn Error>> {
+    let ProcessTypeSystemDocumentOutcome { scalars, objects } = outcome;
+
+    let mut unvalidated_isograph_schema =
+        Schema::<TNetworkProtocol>::with_capacity_hint(SchemaCapacityHint {
+            server_scalars: scalars.len(),
+            server_objects: objects.len(),
+            ..Default::default()
+        });
+    if let Some(refetch_field_name) = options.refetch_field_name {
+        unvalidated_isograph_schema.refetch_field_name = refetch_field_name;
+    }
+    for (server_scalar_entity, name_location) in scalars {
+        unvalidated_isograph_schema
+            .server_entity_data
+            .insert_server_scalar_entity(server_scalar_entity, name_location)?;
+    }
+    // This includes the built-in scalars (e.g. ID), which are not in the outcome.
+    for scalar in unvalidated_isograph_schema
+        .server_entity_data
+        .server_scalars
+        .iter_mut()
+    {
+        if let Some(scalar_import) = options.scalar_imports.get(&scalar.name.item) {
+            scalar.javascript_import = Some(JavascriptImport {
+                type_name: scalar_import.type_name,
+                module_path: scalar_import.module_path,
+            });
+        }
+    }
+
+    let mut field_queue = HashMap::new();
+    let mut expose_as_field_queue = HashMap::new();
+    for (
+        ProcessObjectTypeDefinitionOutcome {
+            encountered_root_kind,
+            server_object_entity,
+            fields_to_insert,
+            expose_as_fields_to_insert,
+        },
+        name_location,
+    ) in objects
+    {
+        let new_object_id = unvalidated_isograph_schema
+            .server_entity_data
+            .insert_server_object_entity(server_object_entity, name_location)?;
+        field_queue.insert(new_object_id, fields_to_insert);
+
+        match encountered_root_kind {
+            Some(RootOperationKind::Query) => {
+                unvalidated_isograph_schema
+                    .fetchable_types
+                    .insert(new_object_id, RootOperationName("query".to_string()));
+            }
+            Some(RootOperationKind::Mutation) => {
+                unvalidated_isograph_schema
+                    .fetchable_types
+                    .insert(new_object_id, RootOperationName("mutation".to_string()));
+            }
+            Some(RootOperationKind::Subscription) => {
+                unvalidated_isograph_schema.subscription_type_id = Some(new_object_id);
+            }
+            None => {}
+        }
+
+        expose_as_field_queue.insert(new_object_id, expose_as_fields_to_insert);
+    }
+
+    let warnings = process_field_queue(&mut unvalidated_isograph_schema, field_queue, options)?;
+    for warning in warnings {
+        warn!("{warning}");
+    }
+
+    Ok((unvalidated_isograph_schema, expose_as_field_queue))
+}
+
 fn parse_iso_literals(
     db: &Database,
     iso_literals_sources: &HashMap<RelativePathToSourceFile, SourceId<IsoLiteralsSource>>,
@@ -229,11 +268,32 @@ pub struct ContainsIsoStats {
 /// - insert it into to the parent object's encountered_fields
 /// - append it to schema.server_fields
 /// - if it is an id field, modify the parent object
+///
+/// In partial schema validation mode, references to undefined types are returned
+/// as warnings instead of errors.
 fn process_field_queue<TNetworkProtocol: NetworkProtocol>(
     schema: &mut Schema<TNetworkProtocol>,
     field_queue: HashMap<ServerObjectEntityId, Vec<WithLocation<FieldToInsert>>>,
     options: &CompilerConfigOptions,
-) -> Result<(), WithLocation<CreateAdditionalFieldsError>> {
+) -> Result<Vec<WithLocation<CreateAdditionalFieldsError>>, WithLocation<CreateAdditionalFieldsError>>
+{
+    // Now that all entities have been inserted, we know which fields point to scalars
+    // and which point to objects, and can reserve space for both.
+    let mut capacity_hint = SchemaCapacityHint::default();
+    for server_field_to_insert in field_queue.values().flatten() {
+        match schema
+            .server_entity_data
+            .defined_entities
+            .get(server_field_to_insert.item.type_.inner())
+        {
+            Some(SelectionType::Scalar(_)) => capacity_hint.server_scalar_selectables += 1,
+            Some(SelectionType::Object(_)) => capacity_hint.server_object_selectables += 1,
+            None => {}
+        }
+    }
+    schema.reserve(capacity_hint);
+
+    let mut warnings = vec![];
     for (parent_object_entity_id, field_definitions_to_insert) in field_queue {
         for server_field_to_insert in field_definitions_to_insert.into_iter() {
             let parent_object_entity = schema
@@ -242,34 +302,51 @@ fn process_field_queue<TNetworkProtocol: NetworkProtocol>(
 
             let target_entity_type_name = server_field_to_insert.item.type_.inner();
 
-            let selection_type = schema
+            let selection_type = match schema
                 .server_entity_data
                 .defined_entities
                 .get(target_entity_type_name)
-                .ok_or_else(|| {
-                    WithLocation::new(
+            {
+                Some(selection_type) => selection_type,
+                None => {
+                    let error = WithLocation::new(
                         CreateAdditionalFieldsError::FieldTypenameDoesNotExist {
                             target_entity_type_name: *target_entity_type_name,
                         },
                         server_field_to_insert.item.name.location,
-                    )
-                })?;
+                    );
+                    if options.partial_schema_validation {
+                        warnings.push(error);
+                        continue;
+                    }
+                    return Err(error);
+                }
+            };
 
-            let arguments = server_field_to_insert
-                .item
-                .arguments
-                // TODO don't clone
-                .clone()
-                .into_iter()
-                .map(|input_value_definition| {
-                    graphql_input_value_definition_to_variable_definition(
-                        &schema.server_entity_data.defined_entities,
-                        input_value_definition,
-                        parent_object_entity.name,
-                        server_field_to_insert.item.name.item.into(),
-                    )
-                })
-                .collect::<Result<Vec<_>, _>>()?;
+            let mut arguments = vec![];
+            // TODO don't clone
+            for input_value_definition in server_field_to_insert.item.arguments.clone() {
+                match graphql_input_value_definition_to_variable_definition(
+                    &schema.server_entity_data.defined_entities,
+                    input_value_definition,
+                    parent_object_entity.name,
+                    server_field_to_insert.item.name.item.into(),
+                ) {
+                    Ok(argument) => arguments.push(argument),
+                    Err(error) => {
+                        if options.partial_schema_validation
+                            && matches!(
+                                error.item,
+                                CreateAdditionalFieldsError::FieldArgumentTypeDoesNotExist { .. }
+                            )
+                        {
+                            warnings.push(error);
+                            continue;
+                        }
+                        return Err(error);
+                    }
+                }
+            }
             let description = server_field_to_insert.item.description.map(|d| d.item);
 
             match selection_type {
@@ -286,6 +363,7 @@ fn process_field_queue<TNetworkProtocol: NetworkProtocol>(
                                     server_field_to_insert.item.type_.clone(),
                                 )
                                 .map(&mut |_| *scalar_entity_id),
+                                kind: server_field_to_insert.item.kind,
                                 parent_object_entity_id,
                                 arguments,
                                 phantom_data: std::marker::PhantomData,
@@ -324,7 +402,7 @@ fn process_field_queue<TNetworkProtocol: NetworkProtocol>(
         }
     }
 
-    Ok(())
+    Ok(warnings)
 }
 
 pub fn graphql_input_value_definition_to_variable_definition(
@@ -336,6 +414,7 @@ pub fn graphql_input_value_definition_to_variable_definition(
     WithLocation<VariableDefinition<ServerEntityId>>,
     WithLocation<CreateAdditionalFieldsError>,
 > {
+    let deprecation_reason = input_value_definition.item.deprecation_reason();
     let default_value = input_value_definition
         .item
         .default_value
@@ -372,9 +451,11 @@ pub fn graphql_input_value_definition_to_variable_definition(
 
     Ok(WithLocation::new(
         VariableDefinition {
+            description: input_value_definition.item.description,
             name: input_value_definition.item.name.map(VariableName::from),
             type_,
             default_value,
+            deprecation_reason,
         },
         input_value_definition.location,
     ))
@@ -419,3 +500,910 @@ fn convert_graphql_constant_value_to_isograph_constant_value(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        marker::PhantomData,
+    };
+
+    use common_lang_types::{
+        Location, ObjectTypeAndFieldName, Span, TextSource, UnvalidatedTypeName, WithLocation,
+        WithSpan,
+    };
+    use graphql_lang_types::{GraphQLNamedTypeAnnotation, GraphQLTypeAnnotation};
+    use graphql_network_protocol::{
+        GraphQLNetworkProtocol, GraphQLSchemaObjectAssociatedData,
+        GraphQLSchemaOriginalDefinitionType, GraphqlSchema,
+    };
+    use intern::{string_key::Intern, Lookup};
+    use isograph_config::{CompilerConfigOptions, ScalarImport, ScalarImports};
+    use isograph_lang_types::{
+        ConstantValue, DefinitionLocation, SchemaSource, SelectionType, TypeAnnotation,
+    };
+    use isograph_schema::{
+        selection_map_wrapped, ClientFieldVariant, ClientScalarSelectable,
+        CreateAdditionalFieldsError, FieldArgument, FieldToInsert, JavascriptImport,
+        MergedScalarFieldSelection, MergedSelectionMap, MergedServerSelection, NameAndArguments,
+        NetworkProtocol, NormalizationKey, ProcessObjectTypeDefinitionOutcome,
+        ProcessTypeSystemDocumentOutcome, RootOperationName, ServerFieldKind, ServerObjectEntity,
+        ServerScalarEntity,
+    };
+    use pico::Database;
+
+    use super::{create_server_schema, process_field_queue};
+
+    /// Processes a Query type with a viewer field, whose type (User) is not defined
+    fn process_forward_reference(
+        options: &CompilerConfigOptions,
+    ) -> Result<
+        (
+            GraphqlSchema,
+            Vec<WithLocation<CreateAdditionalFieldsError>>,
+        ),
+        WithLocation<CreateAdditionalFieldsError>,
+    > {
+        let mut schema = GraphqlSchema::new();
+        let query_object_entity_id = schema
+            .server_entity_data
+            .insert_server_object_entity(
+                ServerObjectEntity {
+                    description: None,
+                    name: "Query".intern().into(),
+                    name_location: Location::generated(),
+                    concrete_type: Some("Query".intern().into()),
+                    output_associated_data: GraphQLSchemaObjectAssociatedData {
+                        original_definition_type: GraphQLSchemaOriginalDefinitionType::Object,
+                        is_one_of: false,
+                    },
+                },
+                Location::generated(),
+            )
+            .expect("Expected object to be inserted");
+        let viewer_field = WithLocation::new(
+            FieldToInsert {
+                description: None,
+                name: WithLocation::new("viewer".intern().into(), Location::generated()),
+                type_: GraphQLTypeAnnotation::Named(GraphQLNamedTypeAnnotation(WithSpan::new(
+                    "User".intern().into(),
+                    Span::todo_generated(),
+                ))),
+                arguments: vec![],
+                directives: vec![],
+                is_inline_fragment: false,
+                kind: ServerFieldKind::ActualField,
+            },
+            Location::generated(),
+        );
+
+        let warnings = process_field_queue(
+            &mut schema,
+            HashMap::from([(query_object_entity_id, vec![viewer_field])]),
+            options,
+        )?;
+        Ok((schema, warnings))
+    }
+
+    #[test]
+    fn forward_reference_is_an_error() {
+        let error = process_forward_reference(&CompilerConfigOptions::default())
+            .map(|_| ())
+            .expect_err("Expected error");
+
+        assert!(matches!(
+            error.item,
+            CreateAdditionalFieldsError::FieldTypenameDoesNotExist { .. }
+        ));
+    }
+
+    #[test]
+    fn forward_reference_is_a_warning_in_partial_schema_validation() {
+        let (schema, warnings) = process_forward_reference(&CompilerConfigOptions {
+            partial_schema_validation: true,
+            ..Default::default()
+        })
+        .expect("Expected forward reference not to be an error");
+
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0].item,
+            CreateAdditionalFieldsError::FieldTypenameDoesNotExist { .. }
+        ));
+        assert!(schema.server_object_selectables.is_empty());
+    }
+
+    fn field_to_insert(name: &str, type_name: &str) -> WithLocation<FieldToInsert> {
+        WithLocation::new(
+            FieldToInsert {
+                description: None,
+                name: WithLocation::new(name.intern().into(), Location::generated()),
+                type_: GraphQLTypeAnnotation::Named(GraphQLNamedTypeAnnotation(WithSpan::new(
+                    type_name.intern().into(),
+                    Span::todo_generated(),
+                ))),
+                arguments: vec![],
+                directives: vec![],
+                is_inline_fragment: false,
+                kind: ServerFieldKind::ActualField,
+            },
+            Location::generated(),
+        )
+    }
+
+    #[test]
+    fn built_in_scalars_cannot_be_redefined() {
+        let redefinition_error = |sdl: &str| {
+            let mut db = Database::new();
+            let relative_path = "schema.graphql".intern().into();
+            let schema_source_id = db.set(SchemaSource {
+                relative_path,
+                content: sdl.to_string(),
+                text_source: TextSource {
+                    relative_path_to_source_file: relative_path,
+                    span: None,
+                    current_working_directory: "cwd".intern().into(),
+                },
+            });
+            let options = CompilerConfigOptions::default();
+            let outcome = GraphQLNetworkProtocol::parse_and_process_type_system_documents(
+                &db,
+                &(schema_source_id, BTreeMap::new()),
+                &options,
+            )
+            .expect("Expected schema to be processed");
+            create_server_schema(outcome, &options)
+                .map(|_| ())
+                .expect_err("Expected error")
+                .downcast_ref::<WithLocation<CreateAdditionalFieldsError>>()
+                .expect("Expected a CreateAdditionalFieldsError")
+                .item
+                .clone()
+        };
+
+        assert_eq!(
+            redefinition_error(
+                "type Query { name: String }
+                scalar String"
+            ),
+            CreateAdditionalFieldsError::RedefinedBuiltinScalar {
+                name: "String".intern().into()
+            }
+        );
+        assert_eq!(
+            redefinition_error(
+                "type Query { name: String }
+                type ID { value: String }"
+            ),
+            CreateAdditionalFieldsError::RedefinedBuiltinScalar {
+                name: "ID".intern().into()
+            }
+        );
+        assert_eq!(
+            redefinition_error(
+                "type Query { name: String }
+                scalar Date
+                scalar Date"
+            ),
+            CreateAdditionalFieldsError::DuplicateTypeDefinition {
+                type_definition_type: "scalar",
+                type_name: "Date".intern().into()
+            }
+        );
+    }
+
+    #[test]
+    fn duplicate_field_reports_both_definitions() {
+        let text_source = TextSource {
+            relative_path_to_source_file: "schema.graphql".intern().into(),
+            span: None,
+            current_working_directory: "cwd".intern().into(),
+        };
+        let name_field = |start: u32| {
+            let mut field = field_to_insert("name", "String");
+            field.item.name.location = Location::new(text_source, Span::new(start, start + 4));
+            field.location = Location::new(text_source, Span::new(start, start + 12));
+            field
+        };
+
+        let mut schema = GraphqlSchema::new();
+        let query_object_entity_id = schema
+            .server_entity_data
+            .insert_server_object_entity(
+                ServerObjectEntity {
+                    description: None,
+                    name: "Query".intern().into(),
+                    name_location: Location::generated(),
+                    concrete_type: Some("Query".intern().into()),
+                    output_associated_data: GraphQLSchemaObjectAssociatedData {
+                        original_definition_type: GraphQLSchemaOriginalDefinitionType::Object,
+                        is_one_of: false,
+                    },
+                },
+                Location::generated(),
+            )
+            .expect("Expected object to be inserted");
+        let error = process_field_queue(
+            &mut schema,
+            HashMap::from([(query_object_entity_id, vec![name_field(13), name_field(26)])]),
+            &CompilerConfigOptions::default(),
+        )
+        .map(|_| ())
+        .expect_err("Expected duplicate field to be an error");
+
+        assert_eq!(
+            error.location,
+            Location::new(text_source, Span::new(26, 38))
+        );
+        assert_eq!(
+            error.item,
+            CreateAdditionalFieldsError::DuplicateField {
+                field_name: "name".intern().into(),
+                parent_type: "Query".intern().into(),
+                previous_location: Location::new(text_source, Span::new(13, 17)),
+            }
+        );
+    }
+
+    /// A schema with many objects, each of which has scalar fields (including
+    /// of custom scalars) and an object field pointing to the next object.
+    fn large_synthetic_outcome(
+        object_count: usize,
+    ) -> ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol> {
+        let scalars = (0..object_count / 10)
+            .map(|index| {
+                (
+                    ServerScalarEntity {
+                        description: None,
+                        name: WithLocation::new(
+                            format!("Scalar{index}").intern().into(),
+                            Location::generated(),
+                        ),
+                        javascript_name: "string".intern().into(),
+                        specified_by_url: None,
+                        javascript_import: None,
+                        output_format: PhantomData,
+                    },
+                    Location::generated(),
+                )
+            })
+            .collect();
+        let objects = (0..object_count)
+            .map(|index| {
+                let name = format!("Type{index}").intern();
+                (
+                    ProcessObjectTypeDefinitionOutcome {
+                        encountered_root_kind: None,
+                        server_object_entity: ServerObjectEntity {
+                            description: None,
+                            name: name.into(),
+                            name_location: Location::generated(),
+                            concrete_type: Some(name.into()),
+                            output_associated_data: GraphQLSchemaObjectAssociatedData {
+                                original_definition_type:
+                                    GraphQLSchemaOriginalDefinitionType::Object,
+                                is_one_of: false,
+                            },
+                        },
+                        fields_to_insert: vec![
+                            field_to_insert("name", "String"),
+                            field_to_insert("count", "Int"),
+                            field_to_insert("custom", &format!("Scalar{}", index / 10)),
+                            field_to_insert("next", &format!("Type{}", (index + 1) % object_count)),
+                        ],
+                        expose_as_fields_to_insert: vec![],
+                    },
+                    Location::generated(),
+                )
+            })
+            .collect();
+        ProcessTypeSystemDocumentOutcome { scalars, objects }
+    }
+
+    #[test]
+    fn pre_sized_schema_is_identical_to_incrementally_grown_schema() {
+        let object_count = 2_000;
+        let options = CompilerConfigOptions::default();
+
+        let (pre_sized_schema, _) =
+            create_server_schema(large_synthetic_outcome(object_count), &options)
+                .expect("Expected schema to be created");
+
+        let ProcessTypeSystemDocumentOutcome { scalars, objects } =
+            large_synthetic_outcome(object_count);
+        let mut incrementally_grown_schema = GraphqlSchema::new();
+        for (server_scalar_entity, name_location) in scalars {
+            incrementally_grown_schema
+                .server_entity_data
+                .insert_server_scalar_entity(server_scalar_entity, name_location)
+                .expect("Expected scalar to be inserted");
+        }
+        let mut field_queue = HashMap::new();
+        for (outcome, name_location) in objects {
+            let object_entity_id = incrementally_grown_schema
+                .server_entity_data
+                .insert_server_object_entity(outcome.server_object_entity, name_location)
+                .expect("Expected object to be inserted");
+            field_queue.insert(object_entity_id, outcome.fields_to_insert);
+        }
+        process_field_queue(&mut incrementally_grown_schema, field_queue, &options)
+            .expect("Expected fields to be inserted");
+
+        assert_eq!(
+            pre_sized_schema.server_object_selectables.len(),
+            object_count
+        );
+        assert_eq!(
+            pre_sized_schema.to_sdl(),
+            incrementally_grown_schema.to_sdl()
+        );
+        assert_eq!(
+            pre_sized_schema.content_hash(),
+            incrementally_grown_schema.content_hash()
+        );
+    }
+
+    /// Creates a schema from GraphQL SDL, including the exposed fields (such as
+    /// refetch fields) that are generated from it.
+    fn create_schema_from_sdl(sdl: &str, options: &CompilerConfigOptions) -> GraphqlSchema {
+        try_create_schema_from_sdl(sdl, options).expect("Expected exposed fields to be created")
+    }
+
+    fn try_create_schema_from_sdl(
+        sdl: &str,
+        options: &CompilerConfigOptions,
+    ) -> Result<GraphqlSchema, WithLocation<CreateAdditionalFieldsError>> {
+        let mut db = Database::new();
+        let relative_path = "schema.graphql".intern().into();
+        let schema_source_id = db.set(SchemaSource {
+            relative_path,
+            content: sdl.to_string(),
+            text_source: TextSource {
+                relative_path_to_source_file: relative_path,
+                span: None,
+                current_working_directory: "cwd".intern().into(),
+            },
+        });
+        let outcome = GraphQLNetworkProtocol::parse_and_process_type_system_documents(
+            &db,
+            &(schema_source_id, BTreeMap::new()),
+            options,
+        )
+        .expect("Expected schema to be processed");
+        let (mut schema, expose_as_field_queue) =
+            create_server_schema(outcome, options).expect("Expected schema to be created");
+        for (parent_object_entity_id, expose_as_fields_to_insert) in expose_as_field_queue {
+            for expose_as_field in expose_as_fields_to_insert {
+                schema.create_new_exposed_field(expose_as_field, parent_object_entity_id)?;
+            }
+        }
+        Ok(schema)
+    }
+
+    #[test]
+    fn refetch_field_is_only_found_on_refetchable_objects() {
+        let options = CompilerConfigOptions {
+            refetch_field_name: Some("refetchMe".intern().into()),
+            ..Default::default()
+        };
+        let schema = create_schema_from_sdl(
+            "type Query { node(id: ID!): Node, viewer: Viewer }
+            interface Node { id: ID! }
+            type User implements Node { id: ID! name: String }
+            type Viewer { name: String }",
+            &options,
+        );
+        let object_entity_id = |name: &str| {
+            let name: UnvalidatedTypeName = name.intern().into();
+            *schema.server_entity_data.defined_entities[&name]
+                .as_object()
+                .expect("Expected an object")
+        };
+
+        let refetch_field = schema
+            .refetch_field(object_entity_id("User"))
+            .expect("Expected User to be refetchable");
+        assert_eq!(refetch_field.name, "refetchMe");
+
+        assert!(schema.refetch_field(object_entity_id("Viewer")).is_none());
+        assert!(schema.refetch_field(object_entity_id("Query")).is_none());
+    }
+
+    #[test]
+    fn root_objects_are_identified() {
+        let schema = create_schema_from_sdl(
+            "type Query { viewer: User }
+            type Mutation { setName(name: String): User }
+            type User { name: String }",
+            &CompilerConfigOptions::default(),
+        );
+        let object_entity_id = |name: &str| {
+            let name: UnvalidatedTypeName = name.intern().into();
+            *schema.server_entity_data.defined_entities[&name]
+                .as_object()
+                .expect("Expected an object")
+        };
+
+        assert!(schema.is_query_root(object_entity_id("Query")));
+        assert!(!schema.is_query_root(object_entity_id("Mutation")));
+        assert!(!schema.is_query_root(object_entity_id("User")));
+
+        assert!(schema.is_mutation_root(object_entity_id("Mutation")));
+        assert!(!schema.is_mutation_root(object_entity_id("Query")));
+        assert!(!schema.is_mutation_root(object_entity_id("User")));
+    }
+
+    #[test]
+    fn schemas_without_a_query_type_have_a_default_query_root() {
+        let schema = create_schema_from_sdl(
+            "type Mutation { setName(name: String): User }
+            type User { name: String }",
+            &CompilerConfigOptions::default(),
+        );
+        let query_name: UnvalidatedTypeName = "Query".intern().into();
+        let query_id = *schema.server_entity_data.defined_entities[&query_name]
+            .as_object()
+            .expect("Expected an object");
+
+        assert_eq!(schema.query_id(), query_id);
+        assert_eq!(
+            schema.server_entity_data.server_object_entity_extra_info[&query_id]
+                .selectables
+                .keys()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>(),
+            vec!["__typename".to_string()]
+        );
+    }
+
+    #[test]
+    fn subscription_root_is_not_fetchable() {
+        let schema = create_schema_from_sdl(
+            "schema { query: Query, subscription: Events }
+            type Query { viewer: User }
+            type Events { userAdded: User }
+            type User { name: String }",
+            &CompilerConfigOptions::default(),
+        );
+        let events_name: UnvalidatedTypeName = "Events".intern().into();
+        let events_id = *schema.server_entity_data.defined_entities[&events_name]
+            .as_object()
+            .expect("Expected an object");
+
+        assert_eq!(schema.subscription_type_id, Some(events_id));
+        assert!(!schema.fetchable_types.contains_key(&events_id));
+    }
+
+    #[test]
+    fn client_scalar_selectables_can_be_inserted_before_iso_literals_are_processed() {
+        let mut schema = create_schema_from_sdl(
+            "type Query { viewer: User }
+            type User { name: String }",
+            &CompilerConfigOptions::default(),
+        );
+        let user_name: UnvalidatedTypeName = "User".intern().into();
+        let user_object_entity_id = *schema.server_entity_data.defined_entities[&user_name]
+            .as_object()
+            .expect("Expected an object");
+        let client_scalar_selectable = |name: &str| ClientScalarSelectable {
+            description: None,
+            name: name.intern().into(),
+            reader_selection_set: vec![],
+            variant: ClientFieldVariant::Link,
+            variable_definitions: vec![],
+            type_and_field: ObjectTypeAndFieldName {
+                type_name: "User".intern().into(),
+                field_name: name.intern().into(),
+            },
+            parent_object_entity_id: user_object_entity_id,
+            refetch_strategy: None,
+            output_format: PhantomData,
+        };
+
+        let client_scalar_selectable_id = schema
+            .insert_client_scalar_selectable(client_scalar_selectable("greeting"))
+            .expect("Expected selectable to be inserted");
+        assert_eq!(
+            schema.server_entity_data.server_object_entity_extra_info[&user_object_entity_id]
+                .selectables[&"greeting".intern().into()],
+            DefinitionLocation::Client(SelectionType::Scalar(client_scalar_selectable_id))
+        );
+        assert_eq!(
+            schema.client_field(client_scalar_selectable_id).name,
+            "greeting"
+        );
+
+        let name_selectable_id = schema.server_entity_data.server_object_entity_extra_info
+            [&user_object_entity_id]
+            .selectables[&"name".intern().into()];
+        assert!(matches!(
+            schema.insert_client_scalar_selectable(client_scalar_selectable("name")),
+            Err(CreateAdditionalFieldsError::DuplicateField { .. })
+        ));
+        // The server field is still mapped, and no client field was added
+        assert_eq!(
+            schema.server_entity_data.server_object_entity_extra_info[&user_object_entity_id]
+                .selectables[&"name".intern().into()],
+            name_selectable_id
+        );
+        assert_eq!(schema.client_scalar_selectables.len(), 1);
+    }
+
+    #[test]
+    fn resolved_arguments_have_entity_ids() {
+        let schema = create_schema_from_sdl(
+            "type Query { user(id: ID!, \"The new name\" name: String = \"Anonymous\"): User }
+            type User { name: String }",
+            &CompilerConfigOptions::default(),
+        );
+        let query_name: UnvalidatedTypeName = "Query".intern().into();
+        let query_object_entity_id = *schema.server_entity_data.defined_entities[&query_name]
+            .as_object()
+            .expect("Expected an object");
+        let user_field_id = *schema.server_entity_data.server_object_entity_extra_info
+            [&query_object_entity_id]
+            .selectables[&"user".intern().into()]
+            .as_server()
+            .expect("Expected a server selectable");
+
+        let id_type = SelectionType::Scalar(schema.server_entity_data.id_type_id);
+        let string_type = SelectionType::Scalar(schema.server_entity_data.string_type_id);
+        assert_eq!(
+            schema.resolved_arguments(user_field_id),
+            vec![
+                FieldArgument {
+                    name: "id".intern().into(),
+                    type_annotation: TypeAnnotation::non_null_scalar(id_type),
+                    default: None,
+                    description: None,
+                },
+                FieldArgument {
+                    name: "name".intern().into(),
+                    type_annotation: TypeAnnotation::nullable_scalar(string_type),
+                    default: Some(ConstantValue::String("Anonymous".intern().into())),
+                    description: Some("The new name".intern().into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn server_selectable_parent_is_the_defining_object() {
+        let schema = create_schema_from_sdl(
+            "type Query { viewer: User }
+            type User { name: String, pet: Pet }
+            type Pet { name: String }",
+            &CompilerConfigOptions::default(),
+        );
+        let object_entity_id = |name: &str| {
+            let name: UnvalidatedTypeName = name.intern().into();
+            *schema.server_entity_data.defined_entities[&name]
+                .as_object()
+                .expect("Expected an object")
+        };
+        let server_selectable_id = |parent: &str, name: &str| {
+            *schema.server_entity_data.server_object_entity_extra_info[&object_entity_id(parent)]
+                .selectables[&name.intern().into()]
+                .as_server()
+                .expect("Expected a server selectable")
+        };
+
+        for (parent, name) in [("User", "name"), ("User", "pet"), ("Pet", "name")] {
+            assert_eq!(
+                schema
+                    .server_selectable_parent(server_selectable_id(parent, name))
+                    .name
+                    .to_string(),
+                parent
+            );
+        }
+    }
+
+    #[test]
+    fn scalars_and_objects_have_the_location_of_their_names() {
+        let sdl = "scalar Date
+            type Query { viewer: User }
+            type User { name: String, birthday: Date }";
+        let schema = create_schema_from_sdl(sdl, &CompilerConfigOptions::default());
+        let name_span = |definition: &str, name: &str| {
+            let start = (sdl
+                .find(definition)
+                .expect("Expected definition to be found")
+                + definition.len()
+                - name.len()) as u32;
+            Span::new(start, start + name.len() as u32)
+        };
+
+        let date: UnvalidatedTypeName = "Date".intern().into();
+        let date_entity_id = *schema.server_entity_data.defined_entities[&date]
+            .as_scalar()
+            .expect("Expected a scalar");
+        assert_eq!(
+            schema.scalar_location(date_entity_id).span(),
+            Some(name_span("scalar Date", "Date"))
+        );
+
+        let user: UnvalidatedTypeName = "User".intern().into();
+        let user_entity_id = *schema.server_entity_data.defined_entities[&user]
+            .as_object()
+            .expect("Expected an object");
+        assert_eq!(
+            schema.object_location(user_entity_id).span(),
+            Some(name_span("type User", "User"))
+        );
+    }
+
+    #[test]
+    fn link_field_collisions_are_reported_at_the_object_name() {
+        let sdl = "type Query { viewer: User }
+            type User { link: String }";
+        let mut schema = create_schema_from_sdl(sdl, &CompilerConfigOptions::default());
+        let user_start = (sdl.find("type User").expect("Expected User to be defined") + 5) as u32;
+
+        let user: UnvalidatedTypeName = "User".intern().into();
+        let user_entity_id = *schema.server_entity_data.defined_entities[&user]
+            .as_object()
+            .expect("Expected an object");
+        assert_eq!(
+            schema.object_location(user_entity_id).span(),
+            Some(Span::new(user_start, user_start + 4))
+        );
+
+        let error = schema
+            .add_link_fields()
+            .expect_err("Expected the link field to collide with the User.link field");
+        assert!(matches!(
+            error.item,
+            CreateAdditionalFieldsError::CompilerCreatedFieldExistsOnType { .. }
+        ));
+        assert_eq!(
+            error.location.span(),
+            Some(Span::new(user_start, user_start + 4))
+        );
+    }
+
+    #[test]
+    fn only_objects_with_an_id_field_are_refetchable() {
+        let schema = create_schema_from_sdl(
+            "type Query { viewer: User }
+            type User { id: ID!, name: String, pet: Pet }
+            type Pet { name: String }",
+            &CompilerConfigOptions::default(),
+        );
+        let user: UnvalidatedTypeName = "User".intern().into();
+        let user_entity_id = *schema.server_entity_data.defined_entities[&user]
+            .as_object()
+            .expect("Expected an object");
+
+        assert_eq!(schema.refetchable_objects(), vec![user_entity_id]);
+    }
+
+    #[test]
+    fn scalar_imports_are_read_from_the_config() {
+        let options = CompilerConfigOptions {
+            scalar_imports: ScalarImports::from([(
+                "Decimal".intern().into(),
+                ScalarImport {
+                    type_name: "Big".intern().into(),
+                    module_path: "big.js".intern().into(),
+                },
+            )]),
+            ..Default::default()
+        };
+        let schema = create_schema_from_sdl(
+            "scalar Decimal scalar Money type Query { price: Decimal, total: Money }",
+            &options,
+        );
+        let javascript_import = |scalar_name: &str| {
+            schema
+                .server_entity_data
+                .server_scalars
+                .iter()
+                .find(|scalar| scalar.name.item.lookup() == scalar_name)
+                .expect("Expected scalar to exist")
+                .javascript_import
+        };
+
+        assert_eq!(
+            javascript_import("Decimal"),
+            Some(JavascriptImport {
+                type_name: "Big".intern().into(),
+                module_path: "big.js".intern().into(),
+            })
+        );
+        assert_eq!(javascript_import("Money"), None);
+    }
+
+    #[test]
+    fn exposed_fields_can_return_lists() {
+        let schema = create_schema_from_sdl(
+            "type Query { pets: [Pet] }
+            type Mutation @exposeField(
+                field: \"set_pet_names\"
+                as: \"set_name\"
+                fieldMap: [{ from: \"id\", to: \"id\" }]
+            ) {
+                set_pet_names(id: ID!, name: String!): [Pet!]!
+            }
+            type Pet { id: ID! name: String }",
+            &CompilerConfigOptions::default(),
+        );
+        let pet: UnvalidatedTypeName = "Pet".intern().into();
+        let pet_object_entity_id = *schema.server_entity_data.defined_entities[&pet]
+            .as_object()
+            .expect("Expected an object");
+
+        // The exposed field is defined on the type of the list's items
+        assert!(
+            schema.server_entity_data.server_object_entity_extra_info[&pet_object_entity_id]
+                .selectables
+                .contains_key(&"set_name".intern().into())
+        );
+    }
+
+    #[test]
+    fn exposed_fields_on_interfaces_are_added_to_implementors() {
+        let schema = create_schema_from_sdl(
+            "type Query { node(id: ID!): Node }
+            type Mutation @exposeField(
+                field: \"rename_node.node\"
+                as: \"rename\"
+                fieldMap: [{ from: \"id\", to: \"id\" }]
+            ) {
+                rename_node(id: ID!, name: String!): RenameNodeResponse!
+            }
+            type RenameNodeResponse { node: Node }
+            interface Node { id: ID! }
+            type Pet implements Node { id: ID! name: String }
+            type User implements Node { id: ID! name: String }
+            type Viewer { id: ID! }",
+            &CompilerConfigOptions::default(),
+        );
+        let rename_selectable = |type_name: &str| {
+            let type_name: UnvalidatedTypeName = type_name.intern().into();
+            let object_entity_id = *schema.server_entity_data.defined_entities[&type_name]
+                .as_object()
+                .expect("Expected an object");
+            schema.server_entity_data.server_object_entity_extra_info[&object_entity_id]
+                .selectables
+                .get(&"rename".intern().into())
+                .copied()
+        };
+
+        let on_interface = rename_selectable("Node").expect("Expected field on interface");
+        assert_eq!(rename_selectable("Pet"), Some(on_interface));
+        assert_eq!(rename_selectable("User"), Some(on_interface));
+        assert_eq!(rename_selectable("Viewer"), None);
+    }
+
+    #[test]
+    fn exposed_list_fields_generate_a_query_selecting_the_list() {
+        let schema = create_schema_from_sdl(
+            "type Query { pets: [Pet] }
+            type Mutation @exposeField(
+                field: \"set_pet_names\"
+                as: \"set_name\"
+                fieldMap: [{ from: \"id\", to: \"id\" }]
+            ) {
+                set_pet_names(id: ID!, name: String!): [Pet!]!
+            }
+            type Pet { id: ID! name: String }",
+            &CompilerConfigOptions::default(),
+        );
+        let set_name = schema
+            .client_scalar_selectables
+            .iter()
+            .find(|client_field| client_field.name.lookup() == "set_name")
+            .expect("Expected set_name to exist");
+        assert_eq!(set_name.type_and_field.type_name.lookup(), "Pet");
+        let ClientFieldVariant::ImperativelyLoadedField(variant) = &set_name.variant else {
+            panic!("Expected set_name to be imperatively loaded");
+        };
+
+        let scalar_field = |name: &str| {
+            MergedServerSelection::ScalarField(MergedScalarFieldSelection {
+                name: name.intern().into(),
+                arguments: vec![],
+            })
+        };
+        let selection_map = selection_map_wrapped(
+            MergedSelectionMap::from([
+                (NormalizationKey::Id, scalar_field("id")),
+                (
+                    NormalizationKey::ServerField(NameAndArguments {
+                        name: "name".intern().into(),
+                        arguments: vec![],
+                    }),
+                    scalar_field("name"),
+                ),
+            ]),
+            variant.subfields_or_inline_fragments.clone(),
+        );
+        let query_text = GraphQLNetworkProtocol::generate_query_text(
+            "Pet__set_name".intern().into(),
+            &schema,
+            &selection_map,
+            variant.top_level_schema_field_arguments.iter(),
+            &RootOperationName("mutation".to_string()),
+        );
+
+        // The list's items are selected like those of any other linked field
+        assert_eq!(
+            query_text.0,
+            "mutation Pet__set_name($id: ID!, $name: String!) {\\\n  \
+            set_pet_names____id___v_id____name___v_name: set_pet_names(id: $id, name: $name) {\\\n    \
+            id,\\\n    \
+            name,\\\n  \
+            },\\\n\
+            }"
+        );
+    }
+
+    #[test]
+    fn exposed_field_paths_cannot_contain_lists() {
+        let error = try_create_schema_from_sdl(
+            "type Query { pets: [Pet] }
+            type Mutation @exposeField(field: \"set_pet_names.pets\", as: \"set_name\") {
+                set_pet_names(name: String!): SetPetNamesResponse!
+            }
+            type SetPetNamesResponse { pets: [Pet!]! }
+            type Pet { id: ID! name: String }",
+            &CompilerConfigOptions::default(),
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            CreateAdditionalFieldsError::ExposeFieldPathContainsList {
+                field: "set_pet_names.pets".to_string(),
+                parent_type: "SetPetNamesResponse".intern().into(),
+                list_field_name: "pets".intern().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn exposed_field_paths_must_name_a_mutation_field() {
+        let error = try_create_schema_from_sdl(
+            "type Query { pet: Pet }
+            type Mutation @exposeField(field: \"set_pet_nickname.pet\", as: \"set_nickname\") {
+                set_pet_name(name: String!): SetPetNameResponse!
+            }
+            type SetPetNameResponse { pet: Pet! }
+            type Pet { id: ID! name: String }",
+            &CompilerConfigOptions::default(),
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            CreateAdditionalFieldsError::ExposeFieldPathNotFound {
+                path: "set_pet_nickname.pet".intern().into(),
+                mutation_object_name: "Mutation".intern().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn exposed_fields_must_return_an_object() {
+        let error = try_create_schema_from_sdl(
+            "type Query { pet: Pet }
+            type Mutation @exposeField(field: \"pet_count\", as: \"count\") {
+                pet_count: Int!
+            }
+            type Pet { id: ID! name: String }",
+            &CompilerConfigOptions::default(),
+        )
+        .map(|_| ())
+        .expect_err("Expected error");
+
+        assert_eq!(
+            error.item,
+            CreateAdditionalFieldsError::InvalidMutationField {
+                mutation_field: "pet_count".to_string(),
+            }
+        );
+    }
+}
@@ -1,11 +1,15 @@
 use common_lang_types::{
     relative_path_from_absolute_and_working_directory, AbsolutePathAndRelativePath,
-    CurrentWorkingDirectory, GeneratedFileHeader,
+    CurrentWorkingDirectory, GeneratedFileHeader, GraphQLScalarTypeName, JavascriptName,
+    SelectableName, StringLiteralValue,
 };
 use intern::string_key::Intern;
 use schemars::JsonSchema;
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
 use tracing::warn;
 
 pub static ISOGRAPH_FOLDER: &str = "__isograph";
@@ -33,13 +37,121 @@ pub struct CompilerConfig {
     pub current_working_directory: CurrentWorkingDirectory,
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct CompilerConfigOptions {
     pub on_invalid_id_type: OptionalValidationLevel,
     pub no_babel_transform: bool,
     pub include_file_extensions_in_import_statements: GenerateFileExtensionsOption,
     pub module: JavascriptModule,
     pub generated_file_header: Option<GeneratedFileHeader>,
+    pub void_in_nullable_parameter_types: VoidInNullableParameterTypesOption,
+    /// If None, the network protocol's default refetch field name is used.
+    pub refetch_field_name: Option<SelectableName>,
+    /// When only part of a schema is validated (e.g. a single file, in an editor),
+    /// references to types that are not defined are reported as warnings, and the
+    /// referencing fields and arguments are skipped.
+    pub partial_schema_validation: bool,
+    /// What to do if a type that implements Node (and can therefore be refetched)
+    /// has no id field.
+    pub on_missing_id_field: OptionalValidationLevel,
+    /// What to do if a field has a directive that is neither built-in (e.g.
+    /// @deprecated) nor defined in the schema.
+    pub on_unknown_field_directive: OptionalValidationLevel,
+    pub scalar_literal_types: ScalarLiteralTypes,
+    pub scalar_map_types: ScalarMapTypes,
+    pub scalar_imports: ScalarImports,
+    /// If set, nullable types are formatted as e.g. `Maybe<T>` instead of `T | null`.
+    pub nullable_type_wrapper: Option<String>,
+    /// How scalars with literal types (see scalar_literal_types) are emitted.
+    pub enum_emit_style: EnumEmitStyle,
+    /// How nullable fields and parameters are emitted. If None, nullable parameters
+    /// (and fields of input objects) are emitted with OptionalFieldStyle::Both, and
+    /// nullable fields of reader outputs, which are always present, are emitted with
+    /// OptionalFieldStyle::UnionNull.
+    pub optional_field_style: Option<OptionalFieldStyle>,
+    /// The order in which the fields of input objects are emitted in parameter
+    /// types. The fields of reader outputs are always emitted in the order in which
+    /// they are selected.
+    pub input_object_property_order: PropertyOrder,
+    /// If true, the fields of objects are emitted with the readonly modifier.
+    pub readonly_properties: bool,
+    /// If true, scalars with a @specifiedBy url are preceded by a `/** @see url */`
+    /// comment.
+    pub link_specified_by_urls: bool,
+    /// If true, type_guards.ts declares a union of the shapes of the concrete types
+    /// of each interface and union, along with a type guard for each concrete type.
+    pub emit_type_guards: bool,
+    /// If true, the types of parameters that are unions of scalars (e.g.
+    /// `string | null`) and that occur more than once in a parameters type are
+    /// declared once, as a `type` alias, which is referenced by each parameter.
+    pub hoist_type_aliases: bool,
+    /// If true, the `type` aliases that are declared when hoist_type_aliases is
+    /// true are exported, so that they can be imported elsewhere.
+    pub export_declarations: bool,
+    /// If true, fields of type ID in the data that is read are emitted as a string
+    /// that is branded with the name of the object that the field belongs to, e.g.
+    /// `string & { readonly __brand: 'UserID' }`. Parameters are not branded.
+    pub brand_id_types: bool,
+    /// How the server fields in the data that client fields read are named.
+    pub field_name_transform: FieldNameTransform,
+    /// The maximum number of errors to report at once. If None, DEFAULT_MAX_ERRORS
+    /// is used.
+    pub max_errors: Option<usize>,
+}
+
+impl Default for CompilerConfigOptions {
+    fn default() -> Self {
+        Self {
+            on_invalid_id_type: OptionalValidationLevel::default(),
+            no_babel_transform: false,
+            include_file_extensions_in_import_statements: GenerateFileExtensionsOption::default(),
+            module: JavascriptModule::default(),
+            generated_file_header: None,
+            void_in_nullable_parameter_types: VoidInNullableParameterTypesOption::default(),
+            refetch_field_name: None,
+            partial_schema_validation: false,
+            on_missing_id_field: OptionalValidationLevel::default(),
+            on_unknown_field_directive: OptionalValidationLevel::default(),
+            scalar_literal_types: ScalarLiteralTypes::new(),
+            scalar_map_types: ScalarMapTypes::new(),
+            scalar_imports: ScalarImports::new(),
+            nullable_type_wrapper: None,
+            enum_emit_style: EnumEmitStyle::default(),
+            optional_field_style: None,
+            input_object_property_order: PropertyOrder::default(),
+            // Properties have always been emitted with the readonly modifier.
+            readonly_properties: true,
+            link_specified_by_urls: false,
+            emit_type_guards: false,
+            hoist_type_aliases: false,
+            export_declarations: false,
+            brand_id_types: false,
+            field_name_transform: FieldNameTransform::default(),
+            max_errors: None,
+        }
+    }
+}
+
+pub const DEFAULT_MAX_ERRORS: usize = 100;
+
+/// For scalars that are effectively enums (i.e. that are validated server-side),
+/// the set of string literals that they can take. These scalars are typed as a
+/// union of those literals (e.g. `"A" | "B"`) instead of as their javascript type.
+pub type ScalarLiteralTypes = HashMap<GraphQLScalarTypeName, Vec<String>>;
+
+/// For scalars that represent maps (e.g. JSON objects), the type of the map's values.
+/// These scalars are typed as `Record<string, ValueType>` instead of as their
+/// javascript type.
+pub type ScalarMapTypes = HashMap<GraphQLScalarTypeName, String>;
+
+/// For scalars whose TypeScript type is defined in another module, the type and the
+/// module that it is imported from.
+pub type ScalarImports = HashMap<GraphQLScalarTypeName, ScalarImport>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScalarImport {
+    pub type_name: JavascriptName,
+    pub module_path: StringLiteralValue,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -58,6 +170,83 @@ impl GenerateFileExtensionsOption {
     }
 }
 
+#[derive(Default, Debug, Clone, Copy)]
+pub enum VoidInNullableParameterTypesOption {
+    #[default]
+    IncludeVoidInNullableParameterTypes,
+    ExcludeVoidFromNullableParameterTypes,
+}
+
+impl VoidInNullableParameterTypesOption {
+    pub fn nullable_suffix(&self) -> &str {
+        match self {
+            VoidInNullableParameterTypesOption::IncludeVoidInNullableParameterTypes => {
+                " | null | void"
+            }
+            VoidInNullableParameterTypesOption::ExcludeVoidFromNullableParameterTypes => " | null",
+        }
+    }
+
+    /// The suffix for nullable parameter types that are wrapped (e.g. in `Maybe<T>`),
+    /// and which therefore do not need `| null`.
+    pub fn void_suffix(&self) -> &str {
+        match self {
+            VoidInNullableParameterTypesOption::IncludeVoidInNullableParameterTypes => " | void",
+            VoidInNullableParameterTypesOption::ExcludeVoidFromNullableParameterTypes => "",
+        }
+    }
+}
+
+/// How scalars that are effectively enums (i.e. that have scalar_literal_types)
+/// are emitted. The declarations for the ConstEnum and ConstObject styles are
+/// emitted once, in enums.ts in the artifact directory, and are imported by each
+/// file that references the enum.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumEmitStyle {
+    /// e.g. `"A" | "B"`
+    #[default]
+    StringLiteralUnion,
+    /// e.g. `const enum Status { A = "A", B = "B" }`
+    ConstEnum,
+    /// e.g. `const Status = { A: "A", B: "B" } as const`, along with a type of
+    /// the same name that is the union of its values
+    ConstObject,
+}
+
+/// How nullable fields and parameters are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionalFieldStyle {
+    /// e.g. `readonly name?: string`
+    QuestionMark,
+    /// e.g. `readonly name: string | null`
+    UnionNull,
+    /// e.g. `readonly name?: string | null`
+    Both,
+}
+
+/// The order in which the fields of input objects are emitted.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyOrder {
+    /// Fields are sorted by name, so that reordering fields in the schema does
+    /// not change generated types.
+    #[default]
+    Alphabetical,
+    /// Fields are emitted in the order in which they are defined in the schema.
+    /// Generated fields (e.g. __typename) are emitted last.
+    SchemaOrder,
+}
+
+/// How the server fields in the data that client fields read are named. Fields
+/// that are aliased, parameters and the fields of input objects are never renamed.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldNameTransform {
+    /// Fields are read under the name that the schema defines, e.g. `first_name`
+    #[default]
+    Unchanged,
+    /// snake_case fields are read under a camelCase name, e.g. `firstName`
+    CamelCase,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum OptionalValidationLevel {
     /// If this validation error is encountered, it will be ignored
@@ -216,7 +405,7 @@ pub fn create_config(
     }
 }
 
-#[derive(Deserialize, Default, JsonSchema, Debug)]
+#[derive(Deserialize, JsonSchema, Debug)]
 #[serde(default, deny_unknown_fields)]
 pub struct ConfigFileOptions {
     /// What the compiler should do if it encounters an id field whose
@@ -233,6 +422,134 @@ pub struct ConfigFileOptions {
     pub module: ConfigFileJavascriptModule,
     /// A string to generate, in a comment, at the top of every generated file.
     generated_file_header: Option<String>,
+    /// Should nullable parameters be typed as `T | null` instead of
+    /// `T | null | void`?
+    exclude_void_from_nullable_parameter_types: bool,
+    /// The name of the field that the compiler generates on types that
+    /// implement Node, and which is used to refetch them. Defaults to
+    /// __refetch.
+    refetch_field_name: Option<String>,
+    /// Should references to types that are not defined in the schema be reported
+    /// as warnings (and the referencing fields and arguments skipped), instead of
+    /// as errors? This is useful if only part of the schema is available, e.g. when
+    /// validating a single file. Defaults to false.
+    partial_schema_validation: bool,
+    /// What the compiler should do if it encounters a type that implements Node,
+    /// but which does not have an id field. Defaults to ignore.
+    on_missing_id_field: ConfigFileOptionalValidationLevel,
+    /// What the compiler should do if it encounters a field with a directive
+    /// that is neither built-in (e.g. @deprecated) nor defined in the schema
+    /// (e.g. with `directive @auth on FIELD_DEFINITION`). Defaults to ignore.
+    on_unknown_field_directive: ConfigFileOptionalValidationLevel,
+    /// A map from scalar names to the string literals that the scalar can take,
+    /// e.g. `{ "Color": ["RED", "GREEN"] }`. Fields and parameters of these scalars
+    /// are typed as unions of those literals (e.g. `"RED" | "GREEN"`).
+    scalar_literal_types: BTreeMap<String, Vec<String>>,
+    /// A map from the names of scalars that represent maps (e.g. JSON objects)
+    /// to the type of their values, e.g. `{ "JSONObject": "unknown" }`. Fields and
+    /// parameters of these scalars are typed as `Record<string, unknown>`.
+    scalar_map_types: BTreeMap<String, String>,
+    /// A map from the names of scalars whose TypeScript types are defined in
+    /// another module to that type and module, e.g.
+    /// `{ "Decimal": { "type_name": "Decimal", "module_path": "decimal.js" } }`.
+    /// Generated files that reference these scalars import the type.
+    scalar_imports: BTreeMap<String, ConfigFileScalarImport>,
+    /// The name of a generic type (e.g. `Maybe`) that nullable types should be
+    /// wrapped in, instead of being formatted as `T | null`. This type must be
+    /// globally available in your project.
+    nullable_type_wrapper: Option<String>,
+    /// How should scalars with literal types (see scalar_literal_types) be emitted?
+    /// As a union of string literals (the default), as a `const enum`, or as a
+    /// `const` object (along with a type that is the union of its values)?
+    enum_emit_style: ConfigFileEnumEmitStyle,
+    /// How should nullable fields and parameters be emitted? Marked optional
+    /// (`name?: T`), as a union with null (`name: T | null`), or both? By default,
+    /// nullable parameters are emitted as both, and nullable fields of the data
+    /// that is read (which are always present) as a union with null.
+    optional_field_style: Option<ConfigFileOptionalFieldStyle>,
+    /// In what order should the fields of input objects be emitted in parameter
+    /// types? Sorted by name (the default), or in the order in which they are
+    /// defined in the schema? The fields of the data that is read are always
+    /// emitted in the order in which they are selected.
+    input_object_property_order: ConfigFilePropertyOrder,
+    /// Should the fields of objects be emitted with the readonly modifier?
+    /// Defaults to true.
+    readonly_properties: Option<bool>,
+    /// Should scalars with a @specifiedBy url be preceded by a `/** @see url */`
+    /// comment in generated types? Defaults to false.
+    link_specified_by_urls: bool,
+    /// Should type_guards.ts be generated? It contains a union of the concrete
+    /// types of each interface and union (e.g. `NodeUnion`), discriminated by
+    /// __typename, and a type guard for each concrete type (e.g. `isUser`).
+    /// Defaults to false.
+    emit_type_guards: bool,
+    /// Should parameter types that are unions of scalars (e.g. `string | null`),
+    /// and that occur more than once in the parameters of a client field, be
+    /// declared once as a `type` alias (e.g. `type Union0 = string | null;`)
+    /// that each of those parameters references? Defaults to false.
+    hoist_type_aliases: bool,
+    /// Should the `type` aliases that are declared if hoist_type_aliases is true
+    /// be exported (`export type Union0 = ...`), so that they can be imported
+    /// elsewhere? Defaults to false, i.e. they are private to the generated file.
+    export_declarations: bool,
+    /// Should fields of type ID be emitted as a branded string, e.g.
+    /// `string & { readonly __brand: 'UserID' }` for the id field of User, so that
+    /// the ids of different types are not interchangeable? This applies to the
+    /// data that is read. Parameters (including the fields of input objects) are
+    /// not branded, so that any id can be passed to them. Defaults to false.
+    brand_id_types: bool,
+    /// How should the server fields in the data that client fields read be named?
+    /// As defined in the schema (the default), or in camelCase (e.g. `firstName`
+    /// for a `first_name` field)? The generated reader reads renamed fields under
+    /// their new name, as if they were aliased. Aliased fields, parameters and the
+    /// fields of input objects are never renamed.
+    field_name_transform: ConfigFileFieldNameTransform,
+    /// The maximum number of errors that the compiler should report at once.
+    /// Any additional errors are suppressed. Defaults to 100.
+    max_errors: Option<usize>,
+}
+
+impl Default for ConfigFileOptions {
+    fn default() -> Self {
+        Self {
+            on_invalid_id_type: ConfigFileOptionalValidationLevel::default(),
+            no_babel_transform: false,
+            include_file_extensions_in_import_statements: false,
+            module: ConfigFileJavascriptModule::default(),
+            generated_file_header: None,
+            exclude_void_from_nullable_parameter_types: false,
+            refetch_field_name: None,
+            partial_schema_validation: false,
+            // Unlike on_invalid_id_type, these validations are opt-in, so that
+            // existing schemas continue to compile.
+            on_missing_id_field: ConfigFileOptionalValidationLevel::Ignore,
+            on_unknown_field_directive: ConfigFileOptionalValidationLevel::Ignore,
+            scalar_literal_types: BTreeMap::new(),
+            scalar_map_types: BTreeMap::new(),
+            scalar_imports: BTreeMap::new(),
+            nullable_type_wrapper: None,
+            enum_emit_style: ConfigFileEnumEmitStyle::default(),
+            optional_field_style: None,
+            input_object_property_order: ConfigFilePropertyOrder::default(),
+            readonly_properties: None,
+            link_specified_by_urls: false,
+            emit_type_guards: false,
+            hoist_type_aliases: false,
+            export_declarations: false,
+            brand_id_types: false,
+            field_name_transform: ConfigFileFieldNameTransform::default(),
+            max_errors: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFileScalarImport {
+    /// The name of the exported type, e.g. Decimal
+    type_name: String,
+    /// The module that the type is imported from, e.g. decimal.js
+    module_path: String,
 }
 
 #[derive(Deserialize, Debug, Clone, Copy, JsonSchema)]
@@ -252,6 +569,49 @@ impl Default for ConfigFileOptionalValidationLevel {
     }
 }
 
+#[derive(Deserialize, Default, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFileEnumEmitStyle {
+    /// e.g. `"A" | "B"`
+    #[default]
+    StringLiteralUnion,
+    /// e.g. `const enum Status { A = "A", B = "B" }`
+    ConstEnum,
+    /// e.g. `const Status = { A: "A", B: "B" } as const`
+    ConstObject,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFileOptionalFieldStyle {
+    /// e.g. `readonly name?: string`
+    QuestionMark,
+    /// e.g. `readonly name: string | null`
+    UnionNull,
+    /// e.g. `readonly name?: string | null`
+    Both,
+}
+
+#[derive(Deserialize, Default, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFilePropertyOrder {
+    /// Fields are sorted by name
+    #[default]
+    Alphabetical,
+    /// Fields are emitted in the order in which they are defined in the schema
+    SchemaOrder,
+}
+
+#[derive(Deserialize, Default, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFileFieldNameTransform {
+    /// Fields are read under the name that the schema defines
+    #[default]
+    Unchanged,
+    /// snake_case fields are read under a camelCase name
+    CamelCase,
+}
+
 #[derive(Deserialize, Default, Debug, Clone, Copy, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ConfigFileJavascriptModule {
@@ -270,6 +630,66 @@ fn create_options(options: ConfigFileOptions) -> CompilerConfigOptions {
 
     let generated_file_header = options.generated_file_header.map(|x| x.intern().into());
 
+    if let Some(refetch_field_name) = options.refetch_field_name.as_ref() {
+        if refetch_field_name.is_empty() {
+            panic!("config.options.refetch_field_name should not be an empty string.")
+        }
+    }
+
+    let refetch_field_name = options.refetch_field_name.map(|x| x.intern().into());
+
+    if options.max_errors == Some(0) {
+        panic!("config.options.max_errors should be greater than 0.")
+    }
+
+    let scalar_literal_types = options
+        .scalar_literal_types
+        .into_iter()
+        .map(|(scalar_name, literals)| {
+            if literals.is_empty() {
+                panic!("config.options.scalar_literal_types.{scalar_name} should not be empty.")
+            }
+            (scalar_name.intern().into(), literals)
+        })
+        .collect();
+
+    if let Some(nullable_type_wrapper) = options.nullable_type_wrapper.as_ref() {
+        if nullable_type_wrapper.is_empty() {
+            panic!("config.options.nullable_type_wrapper should not be an empty string.")
+        }
+    }
+
+    let scalar_map_types = options
+        .scalar_map_types
+        .into_iter()
+        .map(|(scalar_name, value_type)| {
+            if value_type.is_empty() {
+                panic!("config.options.scalar_map_types.{scalar_name} should not be empty.")
+            }
+            (scalar_name.intern().into(), value_type)
+        })
+        .collect();
+
+    let scalar_imports = options
+        .scalar_imports
+        .into_iter()
+        .map(|(scalar_name, scalar_import)| {
+            if scalar_import.type_name.is_empty() || scalar_import.module_path.is_empty() {
+                panic!(
+                    "config.options.scalar_imports.{scalar_name} should have a non-empty \
+                    type_name and module_path."
+                )
+            }
+            (
+                scalar_name.intern().into(),
+                ScalarImport {
+                    type_name: scalar_import.type_name.intern().into(),
+                    module_path: scalar_import.module_path.intern().into(),
+                },
+            )
+        })
+        .collect();
+
     CompilerConfigOptions {
         on_invalid_id_type: create_optional_validation_level(options.on_invalid_id_type),
         no_babel_transform: options.no_babel_transform,
@@ -278,6 +698,32 @@ fn create_options(options: ConfigFileOptions) -> CompilerConfigOptions {
         ),
         module: create_module(options.module),
         generated_file_header,
+        void_in_nullable_parameter_types: create_void_in_nullable_parameter_types(
+            options.exclude_void_from_nullable_parameter_types,
+        ),
+        refetch_field_name,
+        partial_schema_validation: options.partial_schema_validation,
+        on_missing_id_field: create_optional_validation_level(options.on_missing_id_field),
+        on_unknown_field_directive: create_optional_validation_level(
+            options.on_unknown_field_directive,
+        ),
+        scalar_literal_types,
+        scalar_map_types,
+        scalar_imports,
+        nullable_type_wrapper: options.nullable_type_wrapper,
+        enum_emit_style: create_enum_emit_style(options.enum_emit_style),
+        optional_field_style: options
+            .optional_field_style
+            .map(create_optional_field_style),
+        input_object_property_order: create_property_order(options.input_object_property_order),
+        readonly_properties: options.readonly_properties.unwrap_or(true),
+        link_specified_by_urls: options.link_specified_by_urls,
+        emit_type_guards: options.emit_type_guards,
+        hoist_type_aliases: options.hoist_type_aliases,
+        export_declarations: options.export_declarations,
+        brand_id_types: options.brand_id_types,
+        field_name_transform: create_field_name_transform(options.field_name_transform),
+        max_errors: options.max_errors,
     }
 }
 
@@ -300,6 +746,49 @@ fn create_generate_file_extensions(
     }
 }
 
+fn create_void_in_nullable_parameter_types(
+    exclude_void_from_nullable_parameter_types: bool,
+) -> VoidInNullableParameterTypesOption {
+    match exclude_void_from_nullable_parameter_types {
+        true => VoidInNullableParameterTypesOption::ExcludeVoidFromNullableParameterTypes,
+        false => VoidInNullableParameterTypesOption::IncludeVoidInNullableParameterTypes,
+    }
+}
+
+fn create_enum_emit_style(enum_emit_style: ConfigFileEnumEmitStyle) -> EnumEmitStyle {
+    match enum_emit_style {
+        ConfigFileEnumEmitStyle::StringLiteralUnion => EnumEmitStyle::StringLiteralUnion,
+        ConfigFileEnumEmitStyle::ConstEnum => EnumEmitStyle::ConstEnum,
+        ConfigFileEnumEmitStyle::ConstObject => EnumEmitStyle::ConstObject,
+    }
+}
+
+fn create_optional_field_style(
+    optional_field_style: ConfigFileOptionalFieldStyle,
+) -> OptionalFieldStyle {
+    match optional_field_style {
+        ConfigFileOptionalFieldStyle::QuestionMark => OptionalFieldStyle::QuestionMark,
+        ConfigFileOptionalFieldStyle::UnionNull => OptionalFieldStyle::UnionNull,
+        ConfigFileOptionalFieldStyle::Both => OptionalFieldStyle::Both,
+    }
+}
+
+fn create_property_order(property_order: ConfigFilePropertyOrder) -> PropertyOrder {
+    match property_order {
+        ConfigFilePropertyOrder::Alphabetical => PropertyOrder::Alphabetical,
+        ConfigFilePropertyOrder::SchemaOrder => PropertyOrder::SchemaOrder,
+    }
+}
+
+fn create_field_name_transform(
+    field_name_transform: ConfigFileFieldNameTransform,
+) -> FieldNameTransform {
+    match field_name_transform {
+        ConfigFileFieldNameTransform::Unchanged => FieldNameTransform::Unchanged,
+        ConfigFileFieldNameTransform::CamelCase => FieldNameTransform::CamelCase,
+    }
+}
+
 fn create_module(module: ConfigFileJavascriptModule) -> JavascriptModule {
     match module {
         ConfigFileJavascriptModule::CommonJs => JavascriptModule::CommonJs,
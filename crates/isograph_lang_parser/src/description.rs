@@ -24,14 +24,7 @@ fn parse_multiline_description(tokens: &mut PeekableLexer) -> Option<WithSpan<De
 
 fn parse_single_line_description(tokens: &mut PeekableLexer) -> Option<WithSpan<DescriptionValue>> {
     tokens
-        .parse_source_of_kind(IsographLangTokenKind::StringLiteral)
-        .map(|parsed_str| {
-            parsed_str.map(|source_with_quotes| {
-                source_with_quotes[1..source_with_quotes.len() - 1]
-                    .intern()
-                    .into()
-            })
-        })
+        .parse_string_key_type(IsographLangTokenKind::StringLiteral)
         .ok()
 }
 // https://spec.graphql.org/June2018/#sec-String-Value
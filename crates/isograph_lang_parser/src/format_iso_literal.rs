@@ -0,0 +1,355 @@
+use std::fmt::Write;
+
+use common_lang_types::{WithLocation, WithSpan};
+use intern::string_key::Lookup;
+use isograph_lang_types::{
+    quote_string_literal, LoadableDirectiveParameters, NonConstantValue,
+    ObjectSelectionDirectiveSet, ScalarSelectionDirectiveSet, SelectionFieldArgument,
+    SelectionTypeContainingSelections, UnvalidatedSelection,
+};
+
+/// The order in which the selections of each selection set are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SelectionOrder {
+    /// The order in which the selections appear in the source
+    #[default]
+    Declaration,
+    /// Sorted by alias (or by name, if there is no alias)
+    Alphabetical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatIsoLiteralOptions {
+    /// The number of spaces by which each nested selection set is indented
+    pub indent_width: usize,
+    pub selection_order: SelectionOrder,
+}
+
+impl Default for FormatIsoLiteralOptions {
+    fn default() -> Self {
+        Self {
+            indent_width: 2,
+            selection_order: SelectionOrder::Declaration,
+        }
+    }
+}
+
+/// Print a parsed selection set as canonical iso literal source, e.g.
+/// `{\n  id\n  pet {\n    nickname\n  }\n}`. Each selection is printed on its
+/// own line, so no commas are emitted. The output can be parsed with
+/// parse_selection_set, and printing is deterministic: formatting the re-parsed
+/// selections yields the same string.
+pub fn format_iso_literal(
+    selections: &[WithSpan<UnvalidatedSelection>],
+    options: FormatIsoLiteralOptions,
+) -> String {
+    let mut s = String::new();
+    write_selection_set(&mut s, selections, options, 0);
+    s
+}
+
+fn write_selection_set(
+    s: &mut String,
+    selections: &[WithSpan<UnvalidatedSelection>],
+    options: FormatIsoLiteralOptions,
+    indentation_level: usize,
+) {
+    if selections.is_empty() {
+        s.push_str("{}");
+        return;
+    }
+
+    let mut selections = selections.iter().map(|x| &x.item).collect::<Vec<_>>();
+    if options.selection_order == SelectionOrder::Alphabetical {
+        selections.sort_by_key(|selection| selection.name_or_alias().item.to_string());
+    }
+
+    s.push_str("{\n");
+    let indent = " ".repeat(options.indent_width * (indentation_level + 1));
+    for selection in selections {
+        s.push_str(&indent);
+        match selection {
+            SelectionTypeContainingSelections::Scalar(scalar_selection) => {
+                if let Some(alias) = scalar_selection.reader_alias {
+                    write!(s, "{}: ", alias.item).expect("Expected writing to succeed");
+                }
+                s.push_str(&scalar_selection.name.item.to_string());
+                write_arguments(s, &scalar_selection.arguments);
+                write_scalar_selection_directive_set(
+                    s,
+                    &scalar_selection.scalar_selection_directive_set,
+                );
+            }
+            SelectionTypeContainingSelections::Object(object_selection) => {
+                if let Some(alias) = object_selection.reader_alias {
+                    write!(s, "{}: ", alias.item).expect("Expected writing to succeed");
+                }
+                s.push_str(&object_selection.name.item.to_string());
+                write_arguments(s, &object_selection.arguments);
+                write_object_selection_directive_set(
+                    s,
+                    &object_selection.object_selection_directive_set,
+                );
+                s.push(' ');
+                write_selection_set(
+                    s,
+                    &object_selection.selection_set,
+                    options,
+                    indentation_level + 1,
+                );
+            }
+        }
+        s.push('\n');
+    }
+    s.push_str(&" ".repeat(options.indent_width * indentation_level));
+    s.push('}');
+}
+
+fn write_arguments(s: &mut String, arguments: &[WithLocation<SelectionFieldArgument>]) {
+    if arguments.is_empty() {
+        return;
+    }
+    s.push('(');
+    for (index, argument) in arguments.iter().enumerate() {
+        if index != 0 {
+            s.push_str(", ");
+        }
+        write!(s, "{}: ", argument.item.name.item).expect("Expected writing to succeed");
+        write_value(s, &argument.item.value.item);
+    }
+    s.push(')');
+}
+
+fn write_scalar_selection_directive_set(
+    s: &mut String,
+    directive_set: &ScalarSelectionDirectiveSet,
+) {
+    match directive_set {
+        ScalarSelectionDirectiveSet::Loadable(loadable_directive_set) => {
+            s.push_str(" @loadable");
+            write_loadable_directive_parameters(s, &loadable_directive_set.loadable);
+        }
+        ScalarSelectionDirectiveSet::Updatable(_) => s.push_str(" @updatable"),
+        ScalarSelectionDirectiveSet::None(_) => {}
+    }
+}
+
+fn write_object_selection_directive_set(
+    s: &mut String,
+    directive_set: &ObjectSelectionDirectiveSet,
+) {
+    match directive_set {
+        ObjectSelectionDirectiveSet::Updatable(_) => s.push_str(" @updatable"),
+        ObjectSelectionDirectiveSet::None(_) => {}
+    }
+}
+
+/// Only the parameters that differ from their defaults (i.e. that are true) are
+/// printed.
+fn write_loadable_directive_parameters(s: &mut String, parameters: &LoadableDirectiveParameters) {
+    let arguments = [
+        ("completeSelectionSet", parameters.complete_selection_set),
+        ("lazyLoadArtifact", parameters.lazy_load_artifact),
+    ]
+    .into_iter()
+    .filter(|(_, value)| *value)
+    .map(|(name, _)| format!("{name}: true"))
+    .collect::<Vec<_>>();
+    if !arguments.is_empty() {
+        write!(s, "({})", arguments.join(", ")).expect("Expected writing to succeed");
+    }
+}
+
+fn write_value(s: &mut String, value: &NonConstantValue) {
+    match value {
+        NonConstantValue::Variable(variable_name) => write!(s, "${variable_name}"),
+        NonConstantValue::Integer(int_value) => write!(s, "{int_value}"),
+        NonConstantValue::Boolean(bool) => write!(s, "{bool}"),
+        NonConstantValue::String(string) => write!(s, "{}", quote_string_literal(string.lookup())),
+        NonConstantValue::Float(float) => write!(s, "{}", float.as_float()),
+        NonConstantValue::Null => write!(s, "null"),
+        NonConstantValue::Enum(enum_literal) => write!(s, "{enum_literal}"),
+        NonConstantValue::List(items) => {
+            s.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index != 0 {
+                    s.push_str(", ");
+                }
+                write_value(s, &item.item);
+            }
+            write!(s, "]")
+        }
+        NonConstantValue::Object(entries) => {
+            if entries.is_empty() {
+                s.push_str("{}");
+                return;
+            }
+            s.push_str("{ ");
+            for (index, entry) in entries.iter().enumerate() {
+                if index != 0 {
+                    s.push_str(", ");
+                }
+                write!(s, "{}: ", entry.name.item).expect("Expected writing to succeed");
+                write_value(s, &entry.value.item);
+            }
+            write!(s, " }}")
+        }
+    }
+    .expect("Expected writing to succeed");
+}
+
+#[cfg(test)]
+mod test {
+    use common_lang_types::{Location, Span, TextSource, WithLocation, WithSpan};
+    use graphql_lang_types::NameValuePair;
+    use intern::string_key::Intern;
+    use isograph_lang_types::{
+        NonConstantValue, SelectionFieldArgument, SelectionTypeContainingSelections,
+        UnvalidatedSelection,
+    };
+
+    use crate::parse_selection_set;
+
+    use super::{format_iso_literal, FormatIsoLiteralOptions, SelectionOrder};
+
+    fn text_source() -> TextSource {
+        TextSource {
+            relative_path_to_source_file: "dummy".intern().into(),
+            span: None,
+            current_working_directory: "cwd".intern().into(),
+        }
+    }
+
+    fn parse(source: &str) -> Vec<WithSpan<UnvalidatedSelection>> {
+        parse_selection_set(source, text_source()).expect("Expected selection set to parse")
+    }
+
+    fn generated<T>(with_location: &WithLocation<T>) -> WithLocation<T>
+    where
+        T: Clone,
+    {
+        WithLocation::new(with_location.item.clone(), Location::generated())
+    }
+
+    fn strip_value_locations(value: &NonConstantValue) -> NonConstantValue {
+        match value {
+            NonConstantValue::List(items) => NonConstantValue::List(
+                items
+                    .iter()
+                    .map(|item| {
+                        WithLocation::new(strip_value_locations(&item.item), Location::generated())
+                    })
+                    .collect(),
+            ),
+            NonConstantValue::Object(entries) => NonConstantValue::Object(
+                entries
+                    .iter()
+                    .map(|entry| NameValuePair {
+                        name: generated(&entry.name),
+                        value: WithLocation::new(
+                            strip_value_locations(&entry.value.item),
+                            Location::generated(),
+                        ),
+                    })
+                    .collect(),
+            ),
+            value => value.clone(),
+        }
+    }
+
+    fn strip_argument_locations(
+        arguments: &[WithLocation<SelectionFieldArgument>],
+    ) -> Vec<WithLocation<SelectionFieldArgument>> {
+        arguments
+            .iter()
+            .map(|argument| {
+                WithLocation::new(
+                    SelectionFieldArgument {
+                        name: WithSpan::new(argument.item.name.item, Span::todo_generated()),
+                        value: WithLocation::new(
+                            strip_value_locations(&argument.item.value.item),
+                            Location::generated(),
+                        ),
+                    },
+                    Location::generated(),
+                )
+            })
+            .collect()
+    }
+
+    /// Replace every span and location, so that selections parsed from differently
+    /// formatted sources can be compared structurally.
+    fn strip_spans(
+        selections: &[WithSpan<UnvalidatedSelection>],
+    ) -> Vec<WithSpan<UnvalidatedSelection>> {
+        selections
+            .iter()
+            .map(|selection| {
+                let selection = match &selection.item {
+                    SelectionTypeContainingSelections::Scalar(scalar_selection) => {
+                        let mut scalar_selection = scalar_selection.clone();
+                        scalar_selection.name = generated(&scalar_selection.name);
+                        scalar_selection.reader_alias =
+                            scalar_selection.reader_alias.as_ref().map(generated);
+                        scalar_selection.arguments =
+                            strip_argument_locations(&scalar_selection.arguments);
+                        SelectionTypeContainingSelections::Scalar(scalar_selection)
+                    }
+                    SelectionTypeContainingSelections::Object(object_selection) => {
+                        let mut object_selection = object_selection.clone();
+                        object_selection.name = generated(&object_selection.name);
+                        object_selection.reader_alias =
+                            object_selection.reader_alias.as_ref().map(generated);
+                        object_selection.arguments =
+                            strip_argument_locations(&object_selection.arguments);
+                        object_selection.selection_set =
+                            strip_spans(&object_selection.selection_set);
+                        SelectionTypeContainingSelections::Object(object_selection)
+                    }
+                };
+                WithSpan::new(selection, Span::todo_generated())
+            })
+            .collect()
+    }
+
+    const SOURCE: &str = "{ id, name,
+        best_friend: pet(id: $id, filter: { kind: \"dog\", min_age: 2, adopted: true }) @updatable {
+            nickname, picture @loadable(lazyLoadArtifact: true)
+            owner { __typename }
+        }
+        empty {}
+        tagline(locale: null) }";
+
+    #[test]
+    fn format_iso_literal_round_trips() {
+        let selections = parse(SOURCE);
+        let formatted = format_iso_literal(&selections, FormatIsoLiteralOptions::default());
+        assert_eq!(
+            formatted,
+            "{\n  id\n  name\n  best_friend: pet(id: $id, filter: { kind: \"dog\", min_age: 2, \
+            adopted: true }) @updatable {\n    nickname\n    picture @loadable(lazyLoadArtifact: true)\n    owner {\n      \
+            __typename\n    }\n  }\n  empty {}\n  tagline(locale: null)\n}"
+        );
+
+        let reparsed = parse(&formatted);
+        assert_eq!(strip_spans(&reparsed), strip_spans(&selections));
+        assert_eq!(
+            format_iso_literal(&reparsed, FormatIsoLiteralOptions::default()),
+            formatted
+        );
+    }
+
+    #[test]
+    fn format_iso_literal_sorts_and_indents() {
+        let options = FormatIsoLiteralOptions {
+            indent_width: 4,
+            selection_order: SelectionOrder::Alphabetical,
+        };
+        let formatted = format_iso_literal(&parse("{ name, pet { nickname, age }, id }"), options);
+        assert_eq!(
+            formatted,
+            "{\n    id\n    name\n    pet {\n        age\n        nickname\n    }\n}"
+        );
+        assert_eq!(format_iso_literal(&parse(&formatted), options), formatted);
+    }
+}
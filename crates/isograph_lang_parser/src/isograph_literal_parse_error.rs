@@ -1,4 +1,4 @@
-use common_lang_types::{SelectableName, SelectableNameOrAlias, WithLocation, WithSpan};
+use common_lang_types::{SelectableName, SelectableNameOrAlias, Span, WithLocation, WithSpan};
 use isograph_lang_types::DeserializationError;
 use thiserror::Error;
 
@@ -53,12 +53,21 @@ pub enum IsographLiteralParseError {
     #[error("Expected a comma, linebreak or closing curly brace")]
     ExpectedCommaOrLineBreak,
 
+    #[error("Unexpected `{delimiter}`. Delimiters must follow a field, argument or value")]
+    UnexpectedDelimiter { delimiter: IsographLangTokenKind },
+
     #[error(
         "Selection sets are required. If you do not want to \
         select any fields, write an empty selection set: {{}}"
     )]
     ExpectedSelectionSet,
 
+    #[error(
+        "This opening curly brace (`{{`) is never closed. Expected a closing \
+        curly brace (`}}`), but reached the end of the file"
+    )]
+    UnterminatedSelectionSet { open_brace_span: Span },
+
     #[error(
         "You must call the iso function with parentheses. \"iso`...`\" is \
         not supported"
@@ -73,6 +82,12 @@ pub enum IsographLiteralParseError {
         name_or_alias: SelectableNameOrAlias,
     },
 
+    #[error("Expected an alias before the colon, e.g. `alias: fieldName`")]
+    EmptyAlias,
+
+    #[error("Expected a field name after the alias `{alias}`, e.g. `{alias}: fieldName`")]
+    ExpectedFieldNameAfterAlias { alias: SelectableNameOrAlias },
+
     #[error("Expected a boolean value (true or false).")]
     ExpectedBoolean,
 
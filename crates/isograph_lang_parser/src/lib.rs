@@ -1,10 +1,12 @@
 mod description;
+mod format_iso_literal;
 mod isograph_literal_parse_error;
 mod parse_iso_literal;
 mod peekable_lexer;
 mod token_kind;
 
 pub(crate) use description::*;
+pub use format_iso_literal::*;
 pub use isograph_literal_parse_error::*;
 pub use parse_iso_literal::*;
 pub use peekable_lexer::*;
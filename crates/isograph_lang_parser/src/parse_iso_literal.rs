@@ -187,7 +187,7 @@ fn parse_client_field_declaration_inner(
 
         let description = parse_optional_description(tokens);
 
-        let selection_set = parse_selection_set(tokens, text_source)?;
+        let selection_set = parse_required_selection_set(tokens, text_source)?;
 
         let const_export_name = const_export_name.ok_or_else(|| {
             WithSpan::new(
@@ -285,7 +285,7 @@ fn parse_client_pointer_declaration_inner(
 
         let description = parse_optional_description(tokens);
 
-        let selection_set = parse_selection_set(tokens, text_source)?;
+        let selection_set = parse_required_selection_set(tokens, text_source)?;
 
         let const_export_name = const_export_name.ok_or_else(|| {
             WithSpan::new(
@@ -313,10 +313,29 @@ fn parse_client_pointer_declaration_inner(
     })
 }
 
+/// Parse a standalone selection set, e.g. `{ id, name }`. This is useful for
+/// testing, where wrapping the selection set in a full iso literal is noise.
+pub fn parse_selection_set(
+    selection_set_text: &str,
+    text_source: TextSource,
+) -> Result<Vec<WithSpan<UnvalidatedSelection>>, WithSpan<IsographLiteralParseError>> {
+    let mut tokens = PeekableLexer::new(selection_set_text);
+    let selection_set = parse_required_selection_set(&mut tokens, text_source)?;
+
+    if let Some(span) = tokens.remaining_token_span() {
+        return Err(WithSpan::new(
+            IsographLiteralParseError::LeftoverTokens,
+            span,
+        ));
+    }
+
+    Ok(selection_set)
+}
+
 // Note: for now, top-level selection sets are required
 //
 // TODO: perform some refactor to make type easier to read.
-fn parse_selection_set(
+fn parse_required_selection_set(
     tokens: &mut PeekableLexer<'_>,
     text_source: TextSource,
 ) -> ParseResultWithSpan<Vec<WithSpan<UnvalidatedSelection>>> {
@@ -335,11 +354,10 @@ fn parse_optional_selection_set(
     tokens: &mut PeekableLexer<'_>,
     text_source: TextSource,
 ) -> ParseResultWithSpan<Option<Vec<WithSpan<UnvalidatedSelection>>>> {
-    let open_brace: Result<WithSpan<IsographLangTokenKind>, WithSpan<crate::LowLevelParseError>> =
-        tokens.parse_token_of_kind(IsographLangTokenKind::OpenBrace);
-    if open_brace.is_err() {
-        return Ok(None);
-    }
+    let open_brace_span = match tokens.parse_token_of_kind(IsographLangTokenKind::OpenBrace) {
+        Ok(open_brace) => open_brace.span,
+        Err(_) => return Ok(None),
+    };
 
     let mut encountered_names_or_aliases = HashSet::new();
     let mut selections = vec![];
@@ -347,6 +365,15 @@ fn parse_optional_selection_set(
         .parse_token_of_kind(IsographLangTokenKind::CloseBrace)
         .is_err()
     {
+        if tokens.reached_eof() {
+            // Nested selection sets are parsed recursively, so the innermost
+            // unclosed brace is the one reported.
+            return Err(WithSpan::new(
+                IsographLiteralParseError::UnterminatedSelectionSet { open_brace_span },
+                open_brace_span,
+            ));
+        }
+        parse_unexpected_delimiter(tokens, IsographLangTokenKind::Comma)?;
         let selection = parse_selection(tokens, text_source)?;
         let selection_name_or_alias = selection.item.name_or_alias().item;
         if !encountered_names_or_aliases.insert(selection_name_or_alias) {
@@ -383,6 +410,7 @@ fn parse_delimited_list<'a, TResult>(
     }
 
     loop {
+        parse_unexpected_delimiter(tokens, delimiter)?;
         items.push(parse_item(tokens)?);
 
         if let Ok(end_span) = tokens.parse_token_of_kind(closing_token) {
@@ -406,11 +434,32 @@ fn parse_delimited_list<'a, TResult>(
     }
 }
 
+/// A delimiter must follow an item, so a delimiter where an item is expected (e.g.
+/// the second comma in `foo,, bar`) is an error.
+fn parse_unexpected_delimiter(
+    tokens: &mut PeekableLexer<'_>,
+    delimiter: IsographLangTokenKind,
+) -> ParseResultWithSpan<()> {
+    match tokens.parse_optional_token_of_kind(delimiter) {
+        Some(unexpected_delimiter) => Err(WithSpan::new(
+            IsographLiteralParseError::UnexpectedDelimiter { delimiter },
+            unexpected_delimiter.span,
+        )),
+        None => Ok(()),
+    }
+}
+
 fn parse_comma_line_break_or_curly(tokens: &mut PeekableLexer<'_>) -> ParseResultWithSpan<()> {
     let comma = tokens.parse_token_of_kind(IsographLangTokenKind::Comma);
+    let white_space = tokens
+        .source(tokens.white_space_span())
+        .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
     if comma.is_ok()
-        || tokens.source(tokens.white_space_span()).contains('\n')
-        || matches!(tokens.peek().item, IsographLangTokenKind::CloseBrace)
+        || white_space.contains('\n')
+        || matches!(
+            tokens.peek().item,
+            IsographLangTokenKind::CloseBrace | IsographLangTokenKind::EndOfFile
+        )
     {
         Ok(())
     } else {
@@ -489,6 +538,12 @@ fn parse_selection(
 fn parse_optional_alias_and_field_name(
     tokens: &mut PeekableLexer,
 ) -> ParseResultWithSpan<(WithSpan<StringKey>, Option<WithSpan<StringKey>>)> {
+    if let Ok(colon) = tokens.parse_token_of_kind(IsographLangTokenKind::Colon) {
+        return Err(WithSpan::new(
+            IsographLiteralParseError::EmptyAlias,
+            colon.span,
+        ));
+    }
     let field_name_or_alias = tokens
         .parse_string_key_type::<StringKey>(IsographLangTokenKind::Identifier)
         .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
@@ -497,7 +552,14 @@ fn parse_optional_alias_and_field_name(
         (
             tokens
                 .parse_string_key_type::<StringKey>(IsographLangTokenKind::Identifier)
-                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?,
+                .map_err(|with_span| {
+                    WithSpan::new(
+                        IsographLiteralParseError::ExpectedFieldNameAfterAlias {
+                            alias: field_name_or_alias.item.into(),
+                        },
+                        with_span.span,
+                    )
+                })?,
             Some(field_name_or_alias),
         )
     } else {
@@ -511,29 +573,41 @@ fn parse_directives(
     text_source: TextSource,
 ) -> ParseResultWithSpan<Vec<WithSpan<IsographFieldDirective>>> {
     let mut directives = vec![];
-    while let Ok(token) = tokens.parse_token_of_kind(IsographLangTokenKind::At) {
-        let name = tokens
-            .parse_string_key_type(IsographLangTokenKind::Identifier)
-            .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
-        let directive_span = Span::join(token.span, name.span);
-
-        let arguments = parse_optional_arguments(tokens, text_source)?;
-
-        directives.push(WithSpan::new(
-            IsographFieldDirective { name, arguments },
-            directive_span,
-        ));
+    while let Some(directive) = parse_optional_directive(tokens, text_source)? {
+        directives.push(directive);
     }
     Ok(directives)
 }
 
+/// Parses a single `@name(args)` directive, if the next token is an `@`.
+fn parse_optional_directive(
+    tokens: &mut PeekableLexer,
+    text_source: TextSource,
+) -> ParseResultWithSpan<Option<WithSpan<IsographFieldDirective>>> {
+    let token = match tokens.parse_token_of_kind(IsographLangTokenKind::At) {
+        Ok(token) => token,
+        Err(_) => return Ok(None),
+    };
+    let name = tokens
+        .parse_string_key_type(IsographLangTokenKind::Identifier)
+        .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+    let directive_span = Span::join(token.span, name.span);
+
+    let arguments = parse_optional_arguments(tokens, text_source)?;
+
+    Ok(Some(WithSpan::new(
+        IsographFieldDirective { name, arguments },
+        directive_span,
+    )))
+}
+
 fn parse_optional_arguments(
     tokens: &mut PeekableLexer,
     text_source: TextSource,
 ) -> ParseResultWithSpan<Vec<WithLocation<SelectionFieldArgument>>> {
     if tokens
-        .parse_token_of_kind(IsographLangTokenKind::OpenParen)
-        .is_ok()
+        .parse_optional_token_of_kind(IsographLangTokenKind::OpenParen)
+        .is_some()
     {
         let arguments = parse_delimited_list(
             tokens,
@@ -583,14 +657,7 @@ fn parse_non_constant_value(
 
         to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
             let string = tokens
-                .parse_source_of_kind(IsographLangTokenKind::StringLiteral)
-                .map(|parsed_str| {
-                    parsed_str.map(|source_with_quotes| {
-                        source_with_quotes[1..source_with_quotes.len() - 1]
-                            .intern()
-                            .into()
-                    })
-                })
+                .parse_string_key_type(IsographLangTokenKind::StringLiteral)
                 .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
 
             Ok(string.map(NonConstantValue::String))
@@ -675,8 +742,8 @@ fn parse_variable_definitions(
     text_source: TextSource,
 ) -> ParseResultWithSpan<Vec<WithSpan<VariableDefinition<UnvalidatedTypeName>>>> {
     if tokens
-        .parse_token_of_kind(IsographLangTokenKind::OpenParen)
-        .is_ok()
+        .parse_optional_token_of_kind(IsographLangTokenKind::OpenParen)
+        .is_some()
     {
         let variable_definitions = parse_delimited_list(
             tokens,
@@ -712,9 +779,11 @@ fn parse_variable_definition(
         let default_value = parse_optional_default_value(tokens, text_source)?;
 
         Ok::<_, WithSpan<IsographLiteralParseError>>(VariableDefinition {
+            description: None,
             name,
             type_,
             default_value,
+            deprecation_reason: None,
         })
     })?;
     Ok(variable_definition)
@@ -824,7 +893,24 @@ fn from_control_flow<T, E>(control_flow: impl FnOnce() -> ControlFlow<T, E>) ->
 
 #[cfg(test)]
 mod test {
-    use crate::{IsographLangTokenKind, PeekableLexer};
+    use common_lang_types::{Span, TextSource, WithSpan};
+    use intern::string_key::Intern;
+    use isograph_lang_types::{
+        ObjectSelectionDirectiveSet, ScalarSelectionDirectiveSet, SelectionTypeContainingSelections,
+    };
+
+    use crate::{
+        parse_iso_literal, parse_selection_set, IsoLiteralExtractionResult, IsographLangTokenKind,
+        IsographLiteralParseError, PeekableLexer,
+    };
+
+    fn text_source() -> TextSource {
+        TextSource {
+            relative_path_to_source_file: "dummy".intern().into(),
+            span: None,
+            current_working_directory: "cwd".intern().into(),
+        }
+    }
 
     #[test]
     fn parse_literal_tests() {
@@ -838,4 +924,202 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn parse_selection_set_tests() {
+        let selection_set = parse_selection_set("{ id, name, pet { nickname } }", text_source())
+            .expect("Expected selection set to parse");
+        let names = selection_set
+            .iter()
+            .map(|selection| selection.item.name_or_alias().item.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["id", "name", "pet"]);
+
+        let error = parse_selection_set("{ id, pet { nickname }", text_source())
+            .expect_err("Expected unbalanced braces to fail");
+        assert_eq!(
+            error.item,
+            IsographLiteralParseError::UnterminatedSelectionSet {
+                open_brace_span: Span::new(0, 1)
+            }
+        );
+    }
+
+    #[test]
+    fn unterminated_selection_set_points_at_the_unmatched_open_brace() {
+        let error = parse_selection_set("{ id, pet { nickname, owner { name } ", text_source())
+            .expect_err("Expected a missing close brace to fail");
+        assert_eq!(
+            error.item,
+            IsographLiteralParseError::UnterminatedSelectionSet {
+                open_brace_span: Span::new(10, 11)
+            }
+        );
+        assert_eq!(error.span, Span::new(10, 11));
+    }
+
+    #[test]
+    fn aliases_are_retained() {
+        let selection_set = parse_selection_set("{ id, avatar: picture }", text_source())
+            .expect("Expected aliased selections to parse");
+        let names_and_aliases = selection_set
+            .iter()
+            .map(|selection| match &selection.item {
+                SelectionTypeContainingSelections::Scalar(scalar) => (
+                    scalar.name.item.to_string(),
+                    scalar.reader_alias.map(|alias| alias.item.to_string()),
+                ),
+                SelectionTypeContainingSelections::Object(object) => (
+                    object.name.item.to_string(),
+                    object.reader_alias.map(|alias| alias.item.to_string()),
+                ),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names_and_aliases,
+            vec![
+                ("id".to_string(), None),
+                ("picture".to_string(), Some("avatar".to_string()))
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_aliases_are_errors() {
+        let error = parse_selection_set("{ id, : picture }", text_source())
+            .expect_err("Expected an empty alias to fail");
+        assert_eq!(error.item, IsographLiteralParseError::EmptyAlias);
+        assert_eq!(error.span, Span::new(6, 7));
+
+        let error = parse_selection_set("{ id, avatar: }", text_source())
+            .expect_err("Expected a missing field name to fail");
+        assert_eq!(
+            error.item,
+            IsographLiteralParseError::ExpectedFieldNameAfterAlias {
+                alias: "avatar".intern().into()
+            }
+        );
+
+        let error = parse_selection_set("{ picture, picture: avatar }", text_source())
+            .expect_err("Expected a colliding alias to fail");
+        assert_eq!(
+            error.item,
+            IsographLiteralParseError::DuplicateNameOrAlias {
+                name_or_alias: "picture".intern().into()
+            }
+        );
+    }
+
+    #[test]
+    fn iter_selections_yields_nested_selections() {
+        let result = parse_iso_literal(
+            "field Query.Foo { id, pet { name, owner { id, email } }, avatar: picture }",
+            "dummy".intern().into(),
+            Some("Foo"),
+            text_source(),
+        )
+        .expect("Expected client field to parse");
+        let IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration) = result
+        else {
+            panic!("Expected a client field declaration");
+        };
+
+        let names_or_aliases = client_field_declaration
+            .item
+            .iter_selections()
+            .map(|selection| selection.item.name_or_alias().item.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names_or_aliases,
+            vec!["id", "pet", "name", "owner", "id", "email", "avatar"]
+        );
+    }
+
+    #[test]
+    fn selection_directives_are_attached() {
+        let selection_set = parse_selection_set(
+            "{ pet @updatable { nickname }, avatar @loadable(lazyLoadArtifact: true) }",
+            text_source(),
+        )
+        .expect("Expected selection directives to parse");
+
+        let SelectionTypeContainingSelections::Object(pet) = &selection_set[0].item else {
+            panic!("Expected pet to be an object selection");
+        };
+        assert!(matches!(
+            pet.object_selection_directive_set,
+            ObjectSelectionDirectiveSet::Updatable(_)
+        ));
+        let SelectionTypeContainingSelections::Scalar(avatar) = &selection_set[1].item else {
+            panic!("Expected avatar to be a scalar selection");
+        };
+        let ScalarSelectionDirectiveSet::Loadable(loadable_directive_set) =
+            avatar.scalar_selection_directive_set
+        else {
+            panic!("Expected avatar to be loadable");
+        };
+        assert!(loadable_directive_set.loadable.lazy_load_artifact);
+    }
+
+    #[test]
+    fn malformed_selection_directives_are_rejected() {
+        let error = parse_selection_set("{ id @(if: true) }", text_source())
+            .expect_err("Expected a directive without a name to fail");
+        assert!(matches!(
+            error.item,
+            IsographLiteralParseError::ParseError { .. }
+        ));
+
+        let error = parse_selection_set("{ id @loadable(lazyLoadArtifact: ) }", text_source())
+            .expect_err("Expected a directive with a missing argument value to fail");
+        assert_eq!(
+            error.item,
+            IsographLiteralParseError::ExpectedNonConstantValue
+        );
+
+        let error = parse_selection_set("{ id @skip(if: true) }", text_source())
+            .expect_err("Expected an unknown directive to fail");
+        assert!(matches!(
+            error.item,
+            IsographLiteralParseError::UnableToDeserializeDirectives { .. }
+        ));
+    }
+
+    #[test]
+    fn trailing_commas_are_accepted() {
+        let selection_set = parse_selection_set(
+            "{ id, pets(first: 1, after: $cursor,) { nickname, }, }",
+            text_source(),
+        )
+        .expect("Expected trailing commas to be accepted");
+        assert_eq!(selection_set.len(), 2);
+    }
+
+    #[test]
+    fn doubled_commas_are_rejected() {
+        let error = parse_selection_set("{ id,, name }", text_source())
+            .expect_err("Expected doubled comma in selection set to fail");
+        assert_eq!(
+            error,
+            WithSpan::new(
+                IsographLiteralParseError::UnexpectedDelimiter {
+                    delimiter: IsographLangTokenKind::Comma
+                },
+                Span::new(5, 6)
+            )
+        );
+
+        let error =
+            parse_selection_set("{ pets(first: 1,, after: $cursor) { id } }", text_source())
+                .expect_err("Expected doubled comma in arguments to fail");
+        assert_eq!(
+            error,
+            WithSpan::new(
+                IsographLiteralParseError::UnexpectedDelimiter {
+                    delimiter: IsographLangTokenKind::Comma
+                },
+                Span::new(16, 17)
+            )
+        );
+    }
 }
@@ -1,10 +1,12 @@
+use std::str::Utf8Error;
+
 use crate::IsographLangTokenKind;
 use common_lang_types::{Span, WithSpan};
 use intern::string_key::{Intern, StringKey};
 use logos::Logos;
 use thiserror::Error;
 
-pub(crate) struct PeekableLexer<'source> {
+pub struct PeekableLexer<'source> {
     current: WithSpan<IsographLangTokenKind>,
     lexer: logos::Lexer<'source, IsographLangTokenKind>,
     source: &'source str,
@@ -37,6 +39,12 @@ impl<'source> PeekableLexer<'source> {
         parser
     }
 
+    /// Construct a lexer from bytes that are expected to be valid UTF-8, e.g. when
+    /// the source was read as a byte slice. Invalid UTF-8 is reported, not lexed.
+    pub fn try_from_bytes(source: &'source [u8]) -> Result<Self, Utf8Error> {
+        std::str::from_utf8(source).map(Self::new)
+    }
+
     /// Get the next token (and advance)
     pub fn parse_token(&mut self) -> WithSpan<IsographLangTokenKind> {
         let kind = self
@@ -45,18 +53,39 @@ impl<'source> PeekableLexer<'source> {
             .unwrap_or(IsographLangTokenKind::EndOfFile);
 
         self.end_index_of_last_parsed_token = self.current.span.end;
-        let span = self.lexer_span();
+        let mut span = self.lexer_span();
+        if kind == IsographLangTokenKind::Error {
+            span = self.coalesce_error_tokens(span);
+        }
         // TODO why does self.current = ... not work here?
         std::mem::replace(&mut self.current, WithSpan::new(kind, span))
     }
 
+    /// Consume every Error token that immediately follows the Error token with the
+    /// given span, so that a run of invalid characters is reported once. The run ends
+    /// at whitespace, at a comment or at the next valid token.
+    fn coalesce_error_tokens(&mut self, mut span: Span) -> Span {
+        loop {
+            let mut lookahead = self.lexer.clone();
+            if lookahead.next() != Some(IsographLangTokenKind::Error) {
+                return span;
+            }
+            let next_span = Span::from(lookahead.span()).with_offset(self.current_offset());
+            if next_span.start != span.end {
+                return span;
+            }
+            self.lexer = lookahead;
+            span.end = next_span.end;
+        }
+    }
+
     pub fn peek(&self) -> WithSpan<IsographLangTokenKind> {
         self.current
     }
 
     pub fn lexer_span(&self) -> Span {
         let span: Span = self.lexer.span().into();
-        span.with_offset(self.offset)
+        span.with_offset(self.current_offset())
     }
 
     pub fn remaining_token_span(&mut self) -> Option<Span> {
@@ -72,13 +101,38 @@ impl<'source> PeekableLexer<'source> {
         self.current.item == IsographLangTokenKind::EndOfFile
     }
 
-    /// A &str for the source of the given span
-    pub fn source(&self, span: Span) -> &'source str {
+    /// The byte offset of the end of the most recently parsed token. Comparing
+    /// this with the start of the next token reveals skipped whitespace and comments.
+    pub fn previous_token_end(&self) -> u32 {
+        self.end_index_of_last_parsed_token
+    }
+
+    /// The offset that is added to the spans of all tokens
+    pub fn current_offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// A &str for the source of the given span. If the span is not within the
+    /// source (e.g. because the offset was miscomputed), this returns an error
+    /// instead of panicking.
+    pub fn source(&self, span: Span) -> LowLevelParseResult<&'source str> {
         let (raw_start, raw_end) = span.as_usize();
-        let start = raw_start - self.offset as usize;
-        let end = raw_end - self.offset as usize;
+        raw_start
+            .checked_sub(self.offset as usize)
+            .zip(raw_end.checked_sub(self.offset as usize))
+            .and_then(|(start, end)| self.source.get(start..end))
+            .ok_or(WithSpan::new(
+                LowLevelParseError::SpanOutOfRange {
+                    start: span.start,
+                    end: span.end,
+                },
+                span,
+            ))
+    }
 
-        &self.source[start..end]
+    /// A &str for the source of the token returned by peek()
+    pub fn peek_source(&self) -> LowLevelParseResult<&'source str> {
+        self.source(self.current.span)
     }
 
     /// If the next token doesn't match expected_kind, we don't advance
@@ -101,6 +155,19 @@ impl<'source> PeekableLexer<'source> {
         }
     }
 
+    /// Advances the parser and returns the token if the next token matches
+    /// expected_kind. Otherwise, returns None and does not advance the parser.
+    pub fn parse_optional_token_of_kind(
+        &mut self,
+        expected_kind: IsographLangTokenKind,
+    ) -> Option<WithSpan<IsographLangTokenKind>> {
+        if self.peek().item == expected_kind {
+            Some(self.parse_token())
+        } else {
+            None
+        }
+    }
+
     /// Advances the parser iff the IsographLangTokenKind, so this is safe
     /// to call to see if the next token matches.
     pub fn parse_source_of_kind(
@@ -109,16 +176,23 @@ impl<'source> PeekableLexer<'source> {
     ) -> LowLevelParseResult<WithSpan<&'source str>> {
         let kind = self.parse_token_of_kind(expected_kind)?;
 
-        Ok(WithSpan::new(self.source(kind.span), kind.span))
+        Ok(WithSpan::new(self.source(kind.span)?, kind.span))
     }
 
+    /// Parse a token of the expected kind and intern its source. String literals
+    /// are interned without their quotes, and with their escape sequences unescaped.
     pub fn parse_string_key_type<T: From<StringKey>>(
         &mut self,
         expected_kind: IsographLangTokenKind,
     ) -> LowLevelParseResult<WithSpan<T>> {
         let kind = self.parse_token_of_kind(expected_kind)?;
-        let source = self.source(kind.span).intern();
-        Ok(WithSpan::new(source.into(), kind.span))
+        let source = self.source(kind.span)?;
+        let value = if expected_kind == IsographLangTokenKind::StringLiteral {
+            unescape_string_literal(source, kind.span)?.intern()
+        } else {
+            source.intern()
+        };
+        Ok(WithSpan::new(value.into(), kind.span))
     }
 
     #[allow(dead_code)]
@@ -128,7 +202,7 @@ impl<'source> PeekableLexer<'source> {
     ) -> LowLevelParseResult<WithSpan<IsographLangTokenKind>> {
         let peeked = self.peek();
         if peeked.item == IsographLangTokenKind::Identifier {
-            let source = self.source(peeked.span);
+            let source = self.peek_source()?;
             if source == identifier {
                 Ok(self.parse_token())
             } else {
@@ -157,15 +231,156 @@ impl<'source> PeekableLexer<'source> {
     ) -> Result<WithSpan<T>, E> {
         let start = self.current.span.start;
         let result = do_stuff(self)?;
-        let end = self.end_index_of_last_parsed_token;
-        Ok(WithSpan::new(result, Span::new(start, end)))
+        let end = self.previous_token_end();
+        let span = if end < start {
+            // do_stuff consumed no tokens, so end still points at the end of the token
+            // before start. Report an empty span at the position we would have parsed.
+            Span::new(start, start)
+        } else {
+            merge_spans(Span::new(start, start), Span::new(end, end))
+        };
+        Ok(WithSpan::new(result, span))
+    }
+
+    /// Consume tokens until the next token of the given kind, which is left
+    /// unconsumed, or until EOF. Returns the span of the skipped tokens, which is
+    /// empty if the next token is already of the given kind.
+    pub fn skip_until(&mut self, kind: IsographLangTokenKind) -> Span {
+        let start = self.current.span.start;
+        let mut skipped_any = false;
+        while self.current.item != kind && !self.reached_eof() {
+            self.parse_token();
+            skipped_any = true;
+        }
+        if skipped_any {
+            Span::new(start, self.previous_token_end())
+        } else {
+            Span::new(start, start)
+        }
     }
 
     pub fn white_space_span(&self) -> Span {
-        Span::new(self.end_index_of_last_parsed_token, self.peek().span.start)
+        Span::new(self.previous_token_end(), self.peek().span.start)
     }
 }
 
+/// Yields the remaining tokens, stopping before EndOfFile. Invalid input is
+/// yielded as one of the Error* token kinds, rather than causing a panic.
+impl Iterator for PeekableLexer<'_> {
+    type Item = WithSpan<IsographLangTokenKind>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reached_eof() {
+            None
+        } else {
+            Some(self.parse_token())
+        }
+    }
+}
+
+/// All of the tokens in the source (e.g. an iso literal), not including EndOfFile.
+pub fn iso_literal_tokens(
+    source: &str,
+) -> impl Iterator<Item = WithSpan<IsographLangTokenKind>> + '_ {
+    PeekableLexer::new(source)
+}
+
+/// Lexes the entire source, separating valid tokens from invalid input. This never
+/// panics, no matter the input, and is therefore suitable for fuzzing.
+pub fn try_lex_all(
+    source: &str,
+) -> (
+    Vec<WithSpan<IsographLangTokenKind>>,
+    Vec<WithSpan<LowLevelParseError>>,
+) {
+    let mut tokens = vec![];
+    let mut errors = vec![];
+    for token in iso_literal_tokens(source) {
+        if token.item == IsographLangTokenKind::Error {
+            errors.push(token.map(|_| LowLevelParseError::UnexpectedCharacters));
+        } else if token.item.is_error() {
+            errors.push(token.map(|found_kind| LowLevelParseError::InvalidToken { found_kind }));
+        } else {
+            tokens.push(token);
+        }
+    }
+    (tokens, errors)
+}
+
+/// Strips the quotes from a string literal (including the quotes, as lexed) and
+/// replaces its escape sequences (`\"`, `\\`, `\/`, `\b`, `\f`, `\n`, `\r`,
+/// `\t` and `\uXXXX`) with the characters they represent. Surrogate pairs, e.g.
+/// `\uD83D\uDE00`, are combined.
+fn unescape_string_literal(source_with_quotes: &str, span: Span) -> LowLevelParseResult<String> {
+    let inner = &source_with_quotes[1..source_with_quotes.len() - 1];
+    let mut unescaped = String::with_capacity(inner.len());
+    let mut chars = inner.char_indices().peekable();
+    while let Some((escape_start, c)) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        let invalid_escape = |escape_end: usize| {
+            // Account for the opening quote
+            let start = span.start + 1 + escape_start as u32;
+            WithSpan::new(
+                LowLevelParseError::InvalidEscape {
+                    escape_sequence: inner[escape_start..escape_end].to_string(),
+                },
+                Span::new(start, span.start + 1 + escape_end as u32),
+            )
+        };
+        let escaped = match chars.next() {
+            Some((_, '"')) => '"',
+            Some((_, '\\')) => '\\',
+            Some((_, '/')) => '/',
+            Some((_, 'b')) => '\u{8}',
+            Some((_, 'f')) => '\u{c}',
+            Some((_, 'n')) => '\n',
+            Some((_, 'r')) => '\r',
+            Some((_, 't')) => '\t',
+            Some((index, 'u')) => {
+                let code_unit = |start: usize| {
+                    inner
+                        .get(start..start + 4)
+                        .filter(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()))
+                        .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                };
+                let high = code_unit(index + 1).ok_or_else(|| invalid_escape(index + 1))?;
+                let mut escape_end = index + 5;
+                let code_point = if (0xD800..0xDC00).contains(&high) {
+                    // A high surrogate must be followed by an escaped low surrogate
+                    let low = inner
+                        .get(escape_end..escape_end + 2)
+                        .filter(|prefix| *prefix == "\\u")
+                        .and_then(|_| code_unit(escape_end + 2))
+                        .filter(|low| (0xDC00..0xE000).contains(low))
+                        .ok_or_else(|| invalid_escape(escape_end))?;
+                    escape_end += 6;
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+                let c = char::from_u32(code_point).ok_or_else(|| invalid_escape(escape_end))?;
+                while chars.peek().is_some_and(|(index, _)| *index < escape_end) {
+                    chars.next();
+                }
+                c
+            }
+            Some((index, other)) => return Err(invalid_escape(index + other.len_utf8())),
+            None => return Err(invalid_escape(inner.len())),
+        };
+        unescaped.push(escaped);
+    }
+    Ok(unescaped)
+}
+
+/// Returns the smallest span covering both a and b.
+pub fn merge_spans(a: Span, b: Span) -> Span {
+    Span::new(a.start.min(b.start), a.end.max(b.end))
+}
+
 type LowLevelParseResult<T> = Result<T, WithSpan<LowLevelParseError>>;
 
 /// Low-level errors. If peekable_lexer could be made generic (it can't because it needs to know
@@ -183,4 +398,391 @@ pub enum LowLevelParseError {
         expected_identifier: &'static str,
         found_text: String,
     },
+
+    #[error("Invalid input: {found_kind}.")]
+    InvalidToken { found_kind: IsographLangTokenKind },
+
+    #[error("Unexpected characters.")]
+    UnexpectedCharacters,
+
+    #[error("Invalid escape sequence `{escape_sequence}` in string literal.")]
+    InvalidEscape { escape_sequence: String },
+
+    #[error("The span {start}..{end} is not within the source being parsed.")]
+    SpanOutOfRange { start: u32, end: u32 },
+}
+
+#[cfg(test)]
+mod test {
+    use common_lang_types::{Span, WithSpan};
+    use intern::{string_key::StringKey, Lookup};
+
+    use crate::{
+        iso_literal_tokens, merge_spans, try_lex_all, IsographLangTokenKind, LowLevelParseError,
+        PeekableLexer,
+    };
+
+    #[test]
+    fn iso_literal_tokens_yields_tokens_until_eof() {
+        let tokens = iso_literal_tokens("field Query.foo { id, ^ }")
+            .map(|token| (token.item, token.span))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            vec![
+                (IsographLangTokenKind::Identifier, Span::new(0, 5)),
+                (IsographLangTokenKind::Identifier, Span::new(6, 11)),
+                (IsographLangTokenKind::Period, Span::new(11, 12)),
+                (IsographLangTokenKind::Identifier, Span::new(12, 15)),
+                (IsographLangTokenKind::OpenBrace, Span::new(16, 17)),
+                (IsographLangTokenKind::Identifier, Span::new(18, 20)),
+                (IsographLangTokenKind::Comma, Span::new(20, 21)),
+                (IsographLangTokenKind::Error, Span::new(22, 23)),
+                (IsographLangTokenKind::CloseBrace, Span::new(24, 25)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_optional_token_of_kind_when_present() {
+        let mut lexer = PeekableLexer::new("foo, bar");
+        lexer.parse_token();
+
+        assert_eq!(
+            lexer.parse_optional_token_of_kind(IsographLangTokenKind::Comma),
+            Some(WithSpan::new(IsographLangTokenKind::Comma, Span::new(3, 4)))
+        );
+        assert_eq!(lexer.peek().item, IsographLangTokenKind::Identifier);
+    }
+
+    #[test]
+    fn parse_optional_token_of_kind_when_absent() {
+        let mut lexer = PeekableLexer::new("foo bar");
+        lexer.parse_token();
+
+        assert_eq!(
+            lexer.parse_optional_token_of_kind(IsographLangTokenKind::Comma),
+            None
+        );
+        assert_eq!(
+            lexer.peek(),
+            WithSpan::new(IsographLangTokenKind::Identifier, Span::new(4, 7))
+        );
+    }
+
+    #[test]
+    fn parse_optional_token_of_kind_at_eof() {
+        let mut lexer = PeekableLexer::new("foo");
+        lexer.parse_token();
+
+        assert_eq!(
+            lexer.parse_optional_token_of_kind(IsographLangTokenKind::Comma),
+            None
+        );
+        assert!(lexer.reached_eof());
+    }
+
+    #[test]
+    fn iso_literal_tokens_of_empty_source_is_empty() {
+        assert_eq!(iso_literal_tokens("  ").count(), 0);
+    }
+
+    #[test]
+    fn merge_spans_covers_both_spans() {
+        assert_eq!(
+            merge_spans(Span::new(4, 8), Span::new(1, 5)),
+            Span::new(1, 8)
+        );
+        assert_eq!(
+            merge_spans(Span::new(0, 2), Span::new(6, 9)),
+            Span::new(0, 9)
+        );
+    }
+
+    #[test]
+    fn with_span_covers_consumed_tokens() {
+        let mut lexer = PeekableLexer::new("foo  bar  baz");
+        lexer.parse_token();
+
+        let parsed = lexer
+            .with_span(|lexer| {
+                lexer.parse_token_of_kind(IsographLangTokenKind::Identifier)?;
+                lexer.parse_token_of_kind(IsographLangTokenKind::Identifier)
+            })
+            .expect("Expected parse to succeed");
+        assert_eq!(parsed.span, Span::new(5, 13));
+    }
+
+    #[test]
+    fn with_span_consuming_no_tokens_is_empty() {
+        let mut lexer = PeekableLexer::new("foo  bar");
+        lexer.parse_token();
+
+        let parsed = lexer
+            .with_span(|_| Ok::<_, LowLevelParseError>(()))
+            .expect("Expected parse to succeed");
+        assert_eq!(parsed.span, Span::new(5, 5));
+    }
+
+    #[test]
+    fn skip_until_leaves_the_token_of_the_given_kind() {
+        let mut lexer = PeekableLexer::new("{ foo bar(baz: 1) } qux");
+        lexer.parse_token();
+
+        assert_eq!(
+            lexer.skip_until(IsographLangTokenKind::CloseBrace),
+            Span::new(2, 17)
+        );
+        assert_eq!(
+            lexer.parse_token_of_kind(IsographLangTokenKind::CloseBrace),
+            Ok(WithSpan::new(
+                IsographLangTokenKind::CloseBrace,
+                Span::new(18, 19)
+            ))
+        );
+
+        assert_eq!(
+            lexer.skip_until(IsographLangTokenKind::Identifier),
+            Span::new(20, 20)
+        );
+        assert_eq!(
+            lexer.skip_until(IsographLangTokenKind::CloseBrace),
+            Span::new(20, 23)
+        );
+        assert!(lexer.reached_eof());
+    }
+
+    #[test]
+    fn previous_token_end_skips_whitespace() {
+        let mut lexer = PeekableLexer::new("foo   bar\n  baz");
+        assert_eq!(lexer.current_offset(), 0);
+        assert_eq!(lexer.previous_token_end(), 0);
+
+        lexer.parse_token();
+        assert_eq!(lexer.previous_token_end(), 3);
+        assert_eq!(lexer.peek().span.start, 6);
+
+        lexer.parse_token();
+        assert_eq!(lexer.previous_token_end(), 9);
+        assert_eq!(lexer.peek().span.start, 12);
+
+        lexer.parse_token();
+        assert_eq!(lexer.previous_token_end(), 15);
+        assert!(lexer.reached_eof());
+    }
+
+    #[test]
+    fn try_from_bytes_lexes_valid_utf8() {
+        let mut lexer = PeekableLexer::try_from_bytes("field Query.foo".as_bytes())
+            .expect("Expected valid UTF-8 to be accepted");
+        assert_eq!(
+            lexer.parse_token(),
+            WithSpan::new(IsographLangTokenKind::Identifier, Span::new(0, 5))
+        );
+        assert_eq!(
+            lexer.peek(),
+            WithSpan::new(IsographLangTokenKind::Identifier, Span::new(6, 11))
+        );
+    }
+
+    #[test]
+    fn try_from_bytes_rejects_invalid_utf8() {
+        let error = PeekableLexer::try_from_bytes(b"field \xff")
+            .err()
+            .expect("Expected invalid UTF-8 to be rejected");
+        assert_eq!(error.valid_up_to(), 6);
+    }
+
+    #[test]
+    fn peek_source_is_the_source_of_the_peeked_token() {
+        let mut lexer = PeekableLexer::new("field Query.foo @loadable");
+        let mut sources = vec![];
+        while !lexer.reached_eof() {
+            assert_eq!(lexer.peek_source(), lexer.source(lexer.peek().span));
+            sources.push(lexer.peek_source().expect("Expected span to be in range"));
+            lexer.parse_token();
+        }
+        assert_eq!(sources, vec!["field", "Query", ".", "foo", "@", "loadable"]);
+    }
+
+    #[test]
+    fn out_of_range_spans_are_errors() {
+        let mut lexer = PeekableLexer::new("field Query.foo");
+        assert_eq!(lexer.source(Span::new(6, 11)), Ok("Query"));
+        assert_eq!(
+            lexer.source(Span::new(12, 40)),
+            Err(WithSpan::new(
+                LowLevelParseError::SpanOutOfRange { start: 12, end: 40 },
+                Span::new(12, 40)
+            ))
+        );
+
+        // e.g. a sub-lexer whose offset was miscomputed
+        lexer.offset = 10;
+        assert_eq!(lexer.source(Span::new(16, 21)), Ok("Query"));
+        assert_eq!(
+            lexer.source(Span::new(2, 4)),
+            Err(WithSpan::new(
+                LowLevelParseError::SpanOutOfRange { start: 2, end: 4 },
+                Span::new(2, 4)
+            ))
+        );
+    }
+
+    #[test]
+    fn try_lex_all_separates_tokens_from_errors() {
+        let (tokens, errors) = try_lex_all("foo ^ bar \"\"\"\u{1F600}\"\"\"");
+        assert_eq!(
+            tokens,
+            vec![
+                WithSpan::new(IsographLangTokenKind::Identifier, Span::new(0, 3)),
+                WithSpan::new(IsographLangTokenKind::Identifier, Span::new(6, 9)),
+            ]
+        );
+        // The block string cannot contain the emoji, so the opening triple quote, the
+        // emoji, and the unterminated closing triple quote are reported as one error.
+        assert_eq!(
+            errors,
+            vec![
+                WithSpan::new(LowLevelParseError::UnexpectedCharacters, Span::new(4, 5)),
+                WithSpan::new(LowLevelParseError::UnexpectedCharacters, Span::new(10, 20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn consecutive_invalid_characters_are_coalesced() {
+        let (tokens, errors) = try_lex_all("foo ^^^% bar ^ ^");
+        assert_eq!(
+            tokens,
+            vec![
+                WithSpan::new(IsographLangTokenKind::Identifier, Span::new(0, 3)),
+                WithSpan::new(IsographLangTokenKind::Identifier, Span::new(9, 12)),
+            ]
+        );
+        // Whitespace ends a run of invalid characters
+        assert_eq!(
+            errors,
+            vec![
+                WithSpan::new(LowLevelParseError::UnexpectedCharacters, Span::new(4, 8)),
+                WithSpan::new(LowLevelParseError::UnexpectedCharacters, Span::new(13, 14)),
+                WithSpan::new(LowLevelParseError::UnexpectedCharacters, Span::new(15, 16)),
+            ]
+        );
+
+        let mut lexer = PeekableLexer::new("^^{");
+        assert_eq!(
+            lexer.parse_token(),
+            WithSpan::new(IsographLangTokenKind::Error, Span::new(0, 2))
+        );
+        assert_eq!(
+            lexer.parse_token(),
+            WithSpan::new(IsographLangTokenKind::OpenBrace, Span::new(2, 3))
+        );
+    }
+
+    fn parse_string_literal(source: &str) -> Result<String, WithSpan<LowLevelParseError>> {
+        PeekableLexer::new(source)
+            .parse_string_key_type::<StringKey>(IsographLangTokenKind::StringLiteral)
+            .map(|string| string.item.lookup().to_string())
+    }
+
+    #[test]
+    fn string_literals_are_unescaped() {
+        assert_eq!(
+            parse_string_literal(r#""a \"b\" \\ \/ \n\t\r\b\f""#),
+            Ok("a \"b\" \\ / \n\t\r\u{8}\u{c}".to_string())
+        );
+        assert_eq!(
+            parse_string_literal(r#""\u00e9 \uD83D\uDE00""#),
+            Ok("\u{e9} \u{1F600}".to_string())
+        );
+        assert_eq!(parse_string_literal(r#""plain""#), Ok("plain".to_string()));
+    }
+
+    #[test]
+    fn invalid_escapes_are_errors() {
+        let invalid_escape = |escape_sequence: &str, start: u32, end: u32| {
+            Err(WithSpan::new(
+                LowLevelParseError::InvalidEscape {
+                    escape_sequence: escape_sequence.to_string(),
+                },
+                Span::new(start, end),
+            ))
+        };
+
+        assert_eq!(
+            parse_string_literal(r#""ab\q""#),
+            invalid_escape("\\q", 3, 5)
+        );
+        assert_eq!(
+            parse_string_literal(r#""\u12G4""#),
+            invalid_escape("\\u", 1, 3)
+        );
+        // A high surrogate must be followed by a low surrogate
+        assert_eq!(
+            parse_string_literal(r#""\uD83Dx""#),
+            invalid_escape("\\uD83D", 1, 7)
+        );
+        assert_eq!(
+            parse_string_literal(r#""\uDE00""#),
+            invalid_escape("\\uDE00", 1, 7)
+        );
+    }
+
+    /// A small xorshift generator, so that the test is deterministic and does not
+    /// require additional dependencies.
+    fn random_bytes(seed: &mut u64, len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                *seed ^= *seed << 13;
+                *seed ^= *seed >> 7;
+                *seed ^= *seed << 17;
+                *seed as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn try_lex_all_does_not_panic_on_random_input() {
+        // Bytes that are likely to exercise strings, numbers, comments and
+        // multi-byte characters.
+        let interesting = [
+            "\"",
+            "\"\"\"",
+            "\\",
+            "#",
+            "\n",
+            "0",
+            "-",
+            ".",
+            "e",
+            "{",
+            "@",
+            "é",
+            "\u{1F600}",
+            "\u{0}",
+        ];
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..2_000 {
+            let bytes = random_bytes(&mut seed, 32);
+            let mut source = String::from_utf8_lossy(&bytes).into_owned();
+            for byte in bytes.iter().take(8) {
+                source.push_str(interesting[*byte as usize % interesting.len()]);
+            }
+
+            let (tokens, errors) = try_lex_all(&source);
+            let spans = tokens
+                .iter()
+                .map(|token| token.span)
+                .chain(errors.iter().map(|error| error.span));
+            for span in spans {
+                let (start, end) = span.as_usize();
+                assert!(
+                    source.get(start..end).is_some(),
+                    "Span {start}..{end} is not valid for {source:?}"
+                );
+            }
+        }
+    }
 }
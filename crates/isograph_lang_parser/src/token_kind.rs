@@ -91,7 +91,9 @@ pub enum StringToken {
     #[error]
     Error,
 
-    #[regex(r#"\\["\\/bfnrt]"#)]
+    // Unsupported escapes (e.g. \q) are lexed as well, so that they can be reported
+    // as invalid escapes when the string is unescaped.
+    #[regex(r#"\\[^\n\r]"#)]
     EscapedCharacter,
 
     #[regex(r#"\\u[0-9A-Fa-f][0-9A-Fa-f][0-9A-Fa-f][0-9A-Fa-f]"#)]
@@ -149,6 +151,22 @@ fn lex_string(lexer: &mut Lexer<'_, IsographLangTokenKind>) -> bool {
     false
 }
 
+impl IsographLangTokenKind {
+    /// Whether this token represents invalid input, rather than a valid token.
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            IsographLangTokenKind::Error
+                | IsographLangTokenKind::ErrorUnterminatedString
+                | IsographLangTokenKind::ErrorUnsupportedStringCharacter
+                | IsographLangTokenKind::ErrorUnterminatedBlockString
+                | IsographLangTokenKind::ErrorNumberLiteralLeadingZero
+                | IsographLangTokenKind::ErrorNumberLiteralTrailingInvalid
+                | IsographLangTokenKind::ErrorFloatLiteralMissingZero
+        )
+    }
+}
+
 impl fmt::Display for IsographLangTokenKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let message = match self {
@@ -207,7 +225,9 @@ fn lex_block_string(lexer: &mut Lexer<'_, IsographLangTokenKind>) -> bool {
                 return true;
             }
             BlockStringToken::EscapedTripleQuote | BlockStringToken::Other => {}
-            BlockStringToken::Error => unreachable!(),
+            // e.g. a control character, or a character outside of the basic
+            // multilingual plane
+            BlockStringToken::Error => return false,
         }
     }
     false
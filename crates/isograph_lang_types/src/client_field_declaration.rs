@@ -36,6 +36,13 @@ pub struct ClientFieldDeclaration {
     pub dot: WithSpan<()>,
 }
 
+impl ClientFieldDeclaration {
+    /// Every selection in the selection set, including nested selections.
+    pub fn iter_selections(&self) -> impl Iterator<Item = &WithSpan<UnvalidatedSelection>> {
+        iter_selections(&self.selection_set)
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub struct ClientPointerDeclaration {
     pub directives: Vec<WithSpan<IsographFieldDirective>>,
@@ -54,11 +61,38 @@ pub struct ClientPointerDeclaration {
     pub dot: WithSpan<()>,
 }
 
+impl ClientPointerDeclaration {
+    /// Every selection in the selection set, including nested selections.
+    pub fn iter_selections(&self) -> impl Iterator<Item = &WithSpan<UnvalidatedSelection>> {
+        iter_selections(&self.selection_set)
+    }
+}
+
+/// Iterate over every selection in a selection set, including nested selections.
+/// Selections are yielded in the order in which they appear, i.e. an object
+/// selection is followed by its own selections.
+pub fn iter_selections<TScalarField, TLinkedField>(
+    selection_set: &[WithSpan<SelectionTypeContainingSelections<TScalarField, TLinkedField>>],
+) -> impl Iterator<Item = &WithSpan<SelectionTypeContainingSelections<TScalarField, TLinkedField>>>
+{
+    let mut stack = vec![selection_set.iter()];
+    std::iter::from_fn(move || loop {
+        let Some(selection) = stack.last_mut()?.next() else {
+            stack.pop();
+            continue;
+        };
+        if let SelectionType::Object(object) = &selection.item {
+            stack.push(object.selection_set.iter());
+        }
+        return Some(selection);
+    })
+}
+
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Copy, Default, Hash)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct LoadableDirectiveParameters {
     #[serde(default)]
-    complete_selection_set: bool,
+    pub complete_selection_set: bool,
     #[serde(default)]
     pub lazy_load_artifact: bool,
 }
@@ -325,12 +359,34 @@ impl TryFrom<NonConstantValue> for ConstantValue {
     }
 }
 
+/// Formats a string as a quoted string literal, escaping quotes, backslashes and
+/// control characters. The result is valid in GraphQL, JavaScript and iso literals.
+pub fn quote_string_literal(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\u{8}' => quoted.push_str("\\b"),
+            '\u{c}' => quoted.push_str("\\f"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 impl ConstantValue {
     pub fn print_to_string(&self) -> String {
         match self {
             ConstantValue::Integer(i) => i.to_string(),
             ConstantValue::Boolean(b) => b.to_string(),
-            ConstantValue::String(s) => format!("\"{s}\""),
+            ConstantValue::String(s) => quote_string_literal(s.lookup()),
             ConstantValue::Float(f) => f.as_float().to_string(),
             ConstantValue::Null => "null".to_string(),
             ConstantValue::Enum(e) => e.to_string(),
@@ -362,9 +418,12 @@ impl ConstantValue {
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub struct VariableDefinition<TValue: Ord + Debug> {
+    pub description: Option<WithSpan<DescriptionValue>>,
     pub name: WithLocation<VariableName>,
     pub type_: GraphQLTypeAnnotation<TValue>,
     pub default_value: Option<WithLocation<ConstantValue>>,
+    /// Only server field arguments can be deprecated, via @deprecated
+    pub deprecation_reason: Option<DescriptionValue>,
 }
 
 impl<TValue: Ord + Debug> VariableDefinition<TValue> {
@@ -373,9 +432,11 @@ impl<TValue: Ord + Debug> VariableDefinition<TValue> {
         map: &mut impl FnMut(TValue) -> TNewValue,
     ) -> VariableDefinition<TNewValue> {
         VariableDefinition {
+            description: self.description,
             name: self.name,
             type_: self.type_.map(map),
             default_value: self.default_value,
+            deprecation_reason: self.deprecation_reason,
         }
     }
 
@@ -384,9 +445,11 @@ impl<TValue: Ord + Debug> VariableDefinition<TValue> {
         map: &mut impl FnMut(TValue) -> Result<TNewValue, E>,
     ) -> Result<VariableDefinition<TNewValue>, E> {
         Ok(VariableDefinition {
+            description: self.description,
             name: self.name,
             type_: self.type_.and_then(map)?,
             default_value: self.default_value,
+            deprecation_reason: self.deprecation_reason,
         })
     }
 }
@@ -65,6 +65,25 @@ impl<TInner: Ord> TypeAnnotation<TInner> {
     }
 }
 
+impl<TInner: Ord> TypeAnnotation<TInner> {
+    /// e.g. String
+    pub fn nullable_scalar(inner: TInner) -> Self {
+        TypeAnnotation::Union(UnionTypeAnnotation::new_nullable(UnionVariant::Scalar(
+            inner,
+        )))
+    }
+
+    /// e.g. String!
+    pub fn non_null_scalar(inner: TInner) -> Self {
+        TypeAnnotation::Scalar(inner)
+    }
+
+    /// A non-null list, e.g. [String]! if inner is String
+    pub fn list_of(inner: TypeAnnotation<TInner>) -> Self {
+        TypeAnnotation::Plural(Box::new(inner))
+    }
+}
+
 impl<TInner: Ord> TypeAnnotation<TInner> {
     pub fn inner(&self) -> &TInner {
         match self {
@@ -82,6 +101,18 @@ impl<TInner: Ord> TypeAnnotation<TInner> {
         }
     }
 
+    /// e.g. [String] or [String]!, but not String
+    pub fn is_plural(&self) -> bool {
+        match self {
+            TypeAnnotation::Scalar(_) => false,
+            TypeAnnotation::Union(union_type_annotation) => union_type_annotation
+                .variants
+                .iter()
+                .any(|variant| matches!(variant, UnionVariant::Plural(_))),
+            TypeAnnotation::Plural(_) => true,
+        }
+    }
+
     // TODO this function should not exist, as we should not be treating "null" as special,
     // ideally
     pub fn inner_non_null(&self) -> &TInner {
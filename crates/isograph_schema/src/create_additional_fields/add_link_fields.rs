@@ -1,5 +1,5 @@
 use crate::{ClientFieldVariant, ClientScalarSelectable, NetworkProtocol, Schema, LINK_FIELD_NAME};
-use common_lang_types::{Location, ObjectTypeAndFieldName, WithLocation};
+use common_lang_types::{ObjectTypeAndFieldName, WithLocation};
 use intern::string_key::Intern;
 use isograph_lang_types::{DefinitionLocation, SelectionType, WithId};
 
@@ -40,6 +40,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                 object_entity_id,
                 field_name,
                 object.name,
+                object.name_location,
                 next_client_field_id,
             ));
         }
@@ -48,8 +49,13 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         // loop, we can't also update self.server_entity_data.server_object_entity_available_selectables!
         //
         // This is temporary: when everything moves to pico, this will be easier!
-        for (object_entity_id, field_name, object_name, next_client_field_id) in
-            selectables_to_process
+        for (
+            object_entity_id,
+            field_name,
+            object_name,
+            object_name_location,
+            next_client_field_id,
+        ) in selectables_to_process
         {
             if self
                 .server_entity_data
@@ -68,7 +74,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                         field_name: field_name.into(),
                         parent_type: object_name,
                     },
-                    Location::generated(),
+                    object_name_location,
                 ));
             }
         }
@@ -2,8 +2,8 @@ use std::collections::HashMap;
 
 use crate::{NetworkProtocol, Schema};
 use common_lang_types::{
-    IsographObjectTypeName, SelectableName, StringLiteralValue, UnvalidatedTypeName, VariableName,
-    WithLocation,
+    IsographObjectTypeName, Location, SelectableName, ServerObjectSelectableName,
+    StringLiteralValue, UnvalidatedTypeName, VariableName, WithLocation,
 };
 use intern::{string_key::Intern, Lookup};
 use isograph_lang_types::ServerObjectEntityId;
@@ -62,18 +62,47 @@ pub enum CreateAdditionalFieldsError {
         parent_type: IsographObjectTypeName,
     },
 
-    // TODO include info about where the field was previously defined
     #[error("Duplicate field named \"{field_name}\" on type \"{parent_type}\"")]
     DuplicateField {
         field_name: SelectableName,
         parent_type: IsographObjectTypeName,
+        /// Where the field was previously defined
+        previous_location: Location,
     },
 
     #[error("Invalid field `{field_arg}` in @exposeField directive")]
     InvalidField { field_arg: String },
 
-    #[error("Invalid mutation field")]
-    InvalidMutationField,
+    #[error(
+        "Invalid mutation field `{mutation_field}` in @exposeField directive. \
+        Only server fields that return an object can be exposed"
+    )]
+    InvalidMutationField { mutation_field: String },
+
+    #[error(
+        "Invalid fieldMap `{value}` in @exposeField directive. The fieldMap must be \
+        a list of objects with `from` and `to` strings, e.g. [{{ from: \"id\", to: \"input.id\" }}]"
+    )]
+    InvalidFieldMap { value: String },
+
+    #[error(
+        "Multiple items in the fieldMap of this @exposeField directive map to `{path}`. \
+        Each target may only appear once."
+    )]
+    DuplicateFieldMapTarget {
+        path: StringLiteralValue,
+        /// Where the field map item that previously targeted path was defined
+        previous_location: Location,
+    },
+
+    #[error(
+        "The @exposeField directive is missing the required argument(s) {missing_arguments}. \
+        Found argument(s): {found_arguments}."
+    )]
+    MissingExposeFieldDirectiveArguments {
+        found_arguments: String,
+        missing_arguments: String,
+    },
 
     #[error(
         "Error when processing @exposeField directive on type `{primary_type_name}`. \
@@ -105,6 +134,26 @@ pub enum CreateAdditionalFieldsError {
         field_name: StringLiteralValue,
     },
 
+    #[error(
+        "Error when processing @exposeField directive with field `{field}`. \
+        The field `{parent_type}.{list_field_name}` returns a list. Only the first field \
+        of the path can return a list."
+    )]
+    ExposeFieldPathContainsList {
+        field: String,
+        parent_type: IsographObjectTypeName,
+        list_field_name: ServerObjectSelectableName,
+    },
+
+    #[error(
+        "Error when processing @exposeField directive with field `{path}`. \
+        `{mutation_object_name}` has no object field named by the first segment of the path."
+    )]
+    ExposeFieldPathNotFound {
+        path: StringLiteralValue,
+        mutation_object_name: IsographObjectTypeName,
+    },
+
     #[error("Failed to deserialize {0}")]
     FailedToDeserialize(String),
 
@@ -132,6 +181,12 @@ pub enum CreateAdditionalFieldsError {
         target_entity_type_name: UnvalidatedTypeName,
     },
 
+    #[error(
+        "The type \"{name}\" cannot be defined, because it is a built-in scalar. \
+        Remove this definition, or rename the type."
+    )]
+    RedefinedBuiltinScalar { name: UnvalidatedTypeName },
+
     #[error("Duplicate type definition ({type_definition_type}) named \"{type_name}\"")]
     DuplicateTypeDefinition {
         type_definition_type: &'static str,
@@ -2,6 +2,7 @@ use common_lang_types::{
     IsographObjectTypeName, Location, ObjectTypeAndFieldName, SelectableName, Span,
     StringLiteralValue, WithLocation, WithSpan,
 };
+use graphql_lang_types::GraphQLDirective;
 use intern::{string_key::Intern, Lookup};
 use isograph_lang_types::{
     ClientScalarSelectableId, DefinitionLocation, EmptyDirectiveSet, ScalarSelection,
@@ -51,14 +52,64 @@ impl ExposeFieldDirective {
             field,
         }
     }
+
+    /// Check that the directive has all of the required arguments before attempting
+    /// to deserialize it, so that we can report every missing argument along with
+    /// the arguments that were found.
+    pub fn validate_arguments<TValue>(
+        directive: &GraphQLDirective<TValue>,
+    ) -> Result<(), CreateAdditionalFieldsError> {
+        let found_arguments = directive
+            .arguments
+            .iter()
+            .map(|argument| argument.name.item.lookup())
+            .collect::<Vec<_>>();
+        let missing_arguments = EXPOSE_FIELD_REQUIRED_ARGUMENTS
+            .iter()
+            .filter(|required_argument| !found_arguments.contains(required_argument))
+            .copied()
+            .collect::<Vec<_>>();
+
+        if missing_arguments.is_empty() {
+            return Ok(());
+        }
+
+        Err(
+            CreateAdditionalFieldsError::MissingExposeFieldDirectiveArguments {
+                found_arguments: format_argument_names(&found_arguments),
+                missing_arguments: format_argument_names(&missing_arguments),
+            },
+        )
+    }
+}
+
+/// `as` and `fieldMap` are optional.
+const EXPOSE_FIELD_REQUIRED_ARGUMENTS: [&str; 1] = ["field"];
+
+fn format_argument_names(argument_names: &[&str]) -> String {
+    if argument_names.is_empty() {
+        return "none".to_string();
+    }
+    argument_names
+        .iter()
+        .map(|argument_name| format!("`{argument_name}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
+    /// Creates the client field for an @exposeField directive, and returns it along
+    /// with the implementors of the primary type (if it is abstract) onto which the
+    /// field was also exposed.
+    #[allow(clippy::type_complexity)]
     pub fn create_new_exposed_field(
         &mut self,
         expose_field_to_insert: ExposeAsFieldToInsert,
         parent_object_entity_id: ServerObjectEntityId,
-    ) -> Result<UnprocessedClientFieldItem, WithLocation<CreateAdditionalFieldsError>> {
+    ) -> Result<
+        (UnprocessedClientFieldItem, Vec<ServerObjectEntityId>),
+        WithLocation<CreateAdditionalFieldsError>,
+    > {
         let ExposeFieldDirective {
             expose_as,
             field_map,
@@ -70,6 +121,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         // between field and path, and we should clean this up.
         //
         // But, this is an expedient way to combine field and path.
+        let field_path = field;
         let mut path = field.lookup().split('.');
         let field = path.next().expect(
             "Expected iter to have at least one element. \
@@ -79,13 +131,38 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             path.map(|x| x.intern().into()).collect::<Vec<_>>();
 
         let mutation_subfield_id =
-            self.parse_mutation_subfield_id(field, parent_object_entity_id)?;
+            self.parse_mutation_subfield_id(field, field_path, parent_object_entity_id)?;
 
         // TODO do not use mutation naming here
         let mutation_field = self.server_object_selectable(mutation_subfield_id);
         let payload_object_type_annotation = &mutation_field.target_object_entity;
         let payload_object_entity_id = *payload_object_type_annotation.inner();
 
+        // If the field returns a list (e.g. a mutation that updates many objects), the
+        // path and the field map are resolved against the type of the list's items.
+        // Lists within the path itself are not yet supported.
+        if let Some(list_selectable) = self
+            .get_object_selections_path(
+                payload_object_entity_id,
+                primary_field_name_selection_parts.iter().copied(),
+            )
+            .map_err(|e| WithLocation::new(e, Location::generated()))?
+            .into_iter()
+            .find(|selectable| selectable.target_object_entity.is_plural())
+        {
+            return Err(WithLocation::new(
+                CreateAdditionalFieldsError::ExposeFieldPathContainsList {
+                    field: field_path.to_string(),
+                    parent_type: self
+                        .server_entity_data
+                        .server_object_entity(list_selectable.parent_object_entity_id)
+                        .name,
+                    list_field_name: list_selectable.name.item,
+                },
+                Location::generated(),
+            ));
+        }
+
         // TODO it's a bit annoying that we call .object twice!
         let mutation_field_payload_type_name = self
             .server_entity_data
@@ -234,19 +311,86 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             mutation_field_client_field_id,
             mutation_field_payload_type_name,
         )?;
-        Ok(UnprocessedClientFieldItem {
-            client_field_id: mutation_field_client_field_id,
-            reader_selection_set: vec![],
-            refetch_strategy: Some(RefetchStrategy::UseRefetchField(
-                generate_refetch_field_strategy(
-                    fields.to_vec(),
-                    // NOTE: this will probably panic if we're not exposing fields which are
-                    // originally on Mutation
-                    parent_object_entity_id,
-                    subfields_or_inline_fragments,
-                ),
-            )),
-        })
+        let implementor_object_entity_ids = if primary_field_concrete_type.is_none() {
+            self.insert_client_field_on_implementors(
+                client_field_scalar_selection_name,
+                maybe_abstract_parent_object_entity_id,
+                mutation_field_client_field_id,
+                mutation_field_payload_type_name,
+            )?
+        } else {
+            vec![]
+        };
+
+        Ok((
+            UnprocessedClientFieldItem {
+                client_field_id: mutation_field_client_field_id,
+                reader_selection_set: vec![],
+                refetch_strategy: Some(RefetchStrategy::UseRefetchField(
+                    generate_refetch_field_strategy(
+                        fields.to_vec(),
+                        // NOTE: this will probably panic if we're not exposing fields which are
+                        // originally on Mutation
+                        parent_object_entity_id,
+                        subfields_or_inline_fragments,
+                    ),
+                )),
+            },
+            implementor_object_entity_ids,
+        ))
+    }
+
+    /// When the primary type of an exposed field is abstract (e.g. an interface),
+    /// the field is also made available on each of its implementors, i.e. the targets
+    /// of its asConcreteType fields. Returns the implementors, sorted by id.
+    fn insert_client_field_on_implementors(
+        &mut self,
+        client_field_name: SelectableName,
+        abstract_object_entity_id: ServerObjectEntityId,
+        client_field_id: ClientScalarSelectableId,
+        payload_object_name: IsographObjectTypeName,
+    ) -> Result<Vec<ServerObjectEntityId>, WithLocation<CreateAdditionalFieldsError>> {
+        let mut implementor_object_entity_ids = self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&abstract_object_entity_id)
+            .map(|extra_info| {
+                extra_info
+                    .selectables
+                    .values()
+                    .filter_map(|selectable_id| match selectable_id {
+                        DefinitionLocation::Server(SelectionType::Object(
+                            server_object_selectable_id,
+                        )) => Some(*server_object_selectable_id),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(|server_object_selectable_id| {
+                self.server_object_selectable(server_object_selectable_id)
+            })
+            .filter(|server_object_selectable| {
+                matches!(
+                    server_object_selectable.object_selectable_variant,
+                    SchemaServerObjectSelectableVariant::InlineFragment
+                )
+            })
+            .map(|server_object_selectable| *server_object_selectable.target_object_entity.inner())
+            .collect::<Vec<_>>();
+        implementor_object_entity_ids.sort();
+
+        for implementor_object_entity_id in implementor_object_entity_ids.iter() {
+            self.insert_client_field_on_object(
+                client_field_name,
+                *implementor_object_entity_id,
+                client_field_id,
+                payload_object_name,
+            )?;
+        }
+
+        Ok(implementor_object_entity_ids)
     }
 
     // TODO this should be defined elsewhere, probably
@@ -283,14 +427,15 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         Ok(())
     }
 
-    /// Here, we are turning "pet" (the field_arg) to the ServerFieldId
-    /// of that specific field
+    /// Here, we are turning "pet" (the field_arg, i.e. the first segment of the
+    /// path) to the ServerFieldId of that specific field
     fn parse_mutation_subfield_id(
         &self,
         field_arg: &str,
+        path: StringLiteralValue,
         mutation_object_entity_id: ServerObjectEntityId,
     ) -> ProcessTypeDefinitionResult<ServerObjectSelectableId> {
-        let field_id = self
+        let selectable_id = self
             .server_entity_data
             .server_object_entity_extra_info
             .get(&mutation_object_entity_id)
@@ -300,26 +445,34 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             )
             .selectables
             .iter()
-            .find_map(|(name, field_id)| {
-                if let DefinitionLocation::Server(SelectionType::Object(server_field_id)) = field_id
-                {
-                    if name.lookup() == field_arg {
-                        return Some(server_field_id);
-                    }
-                }
-                None
-            })
+            .find_map(|(name, selectable_id)| (name.lookup() == field_arg).then_some(selectable_id))
             .ok_or_else(|| {
                 WithLocation::new(
-                    CreateAdditionalFieldsError::InvalidField {
-                        field_arg: field_arg.to_string(),
+                    CreateAdditionalFieldsError::ExposeFieldPathNotFound {
+                        path,
+                        mutation_object_name: self
+                            .server_entity_data
+                            .server_object_entity(mutation_object_entity_id)
+                            .name,
                     },
                     // TODO
                     Location::generated(),
                 )
             })?;
 
-        Ok(*field_id)
+        match selectable_id {
+            DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) => {
+                Ok(*server_object_selectable_id)
+            }
+            // Only server fields that return an object can be exposed
+            _ => Err(WithLocation::new(
+                CreateAdditionalFieldsError::InvalidMutationField {
+                    mutation_field: field_arg.to_string(),
+                },
+                // TODO
+                Location::generated(),
+            )),
+        }
     }
 }
 
@@ -101,15 +101,7 @@ pub struct MergedScalarFieldSelection {
 
 impl MergedScalarFieldSelection {
     pub fn normalization_alias(&self) -> Option<String> {
-        // None if the alias is the same as the name (i.e. there are no args)
-        if self.arguments.is_empty() {
-            None
-        } else {
-            Some(get_aliased_mutation_field_name(
-                self.name.into(),
-                &self.arguments,
-            ))
-        }
+        normalization_alias(self.name.into(), &self.arguments)
     }
 }
 
@@ -125,15 +117,7 @@ pub struct MergedLinkedFieldSelection {
 
 impl MergedLinkedFieldSelection {
     pub fn normalization_alias(&self) -> Option<String> {
-        // None if the alias is the same as the name (i.e. there are no args)
-        if self.arguments.is_empty() {
-            None
-        } else {
-            Some(get_aliased_mutation_field_name(
-                self.name.into(),
-                &self.arguments,
-            ))
-        }
+        normalization_alias(self.name.into(), &self.arguments)
     }
 }
 
@@ -533,9 +517,11 @@ fn process_imperatively_loaded_field<TNetworkProtocol: NetworkProtocol>(
 
     for variable_definition in top_level_schema_field_arguments.iter() {
         definitions_of_used_variables.push(VariableDefinition {
+            description: variable_definition.description,
             name: variable_definition.name,
             type_: variable_definition.type_.clone(),
             default_value: variable_definition.default_value.clone(),
+            deprecation_reason: variable_definition.deprecation_reason,
         });
     }
 
@@ -1249,23 +1235,31 @@ fn maybe_add_typename_selection(selections: &mut MergedSelectionMap) {
     );
 }
 
-fn get_aliased_mutation_field_name(
+/// The alias under which a server field is selected in a query (and is therefore
+/// normalized), e.g. `node____id___v_id` for `node(id: $id)`. Fields that are
+/// selected with different arguments need distinct aliases. The alias only depends
+/// on the name and the arguments, in the order in which they are passed, and must
+/// be kept in sync with @isograph/react. None if the field has no arguments, in
+/// which case the alias would be the same as the name.
+pub fn normalization_alias(
     name: SelectableName,
-    parameters: &[ArgumentKeyAndValue],
-) -> String {
-    let mut s = name.to_string();
+    arguments: &[ArgumentKeyAndValue],
+) -> Option<String> {
+    if arguments.is_empty() {
+        return None;
+    }
 
-    for param in parameters.iter() {
-        // TODO NonConstantValue will format to a string like "$name", but we want just "name".
-        // There is probably a better way to do this.
+    let mut s = name.to_string();
+    for argument in arguments.iter() {
         s.push_str("____");
-        s.push_str(&param.to_alias_str_chunk());
+        s.push_str(&argument.to_alias_str_chunk());
     }
-    s
+    Some(s)
 }
 
 pub fn id_arguments(id_type_id: ServerScalarEntityId) -> Vec<VariableDefinition<ServerEntityId>> {
     vec![VariableDefinition {
+        description: None,
         name: WithLocation::new("id".intern().into(), Location::generated()),
         type_: GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::Named(
             GraphQLNamedTypeAnnotation(WithSpan::new(
@@ -1274,6 +1268,7 @@ pub fn id_arguments(id_type_id: ServerScalarEntityId) -> Vec<VariableDefinition<
             )),
         ))),
         default_value: None,
+        deprecation_reason: None,
     }]
 }
 
@@ -1,8 +1,8 @@
 use std::{collections::BTreeMap, marker::PhantomData};
 
 use common_lang_types::{
-    DescriptionValue, GraphQLScalarTypeName, IsographObjectTypeName, JavascriptName,
-    SelectableName, WithLocation, WithSpan,
+    DescriptionValue, GraphQLScalarTypeName, IsographObjectTypeName, JavascriptName, Location,
+    SelectableName, StringLiteralValue, WithLocation, WithSpan,
 };
 use isograph_lang_types::{
     impl_with_id, DefinitionLocation, SelectionType, ServerObjectEntityId, ServerScalarEntityId,
@@ -15,9 +15,21 @@ pub struct ServerScalarEntity<TNetworkProtocol: NetworkProtocol> {
     pub description: Option<WithSpan<DescriptionValue>>,
     pub name: WithLocation<GraphQLScalarTypeName>,
     pub javascript_name: JavascriptName,
+    /// The url passed to @specifiedBy, which describes how the scalar is serialized
+    pub specified_by_url: Option<StringLiteralValue>,
+    /// If the scalar's TypeScript type is defined in another module, where to import it from
+    pub javascript_import: Option<JavascriptImport>,
     pub output_format: PhantomData<TNetworkProtocol>,
 }
 
+/// A TypeScript type that must be imported from another module, e.g. `Decimal`
+/// from `decimal.js`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JavascriptImport {
+    pub type_name: JavascriptName,
+    pub module_path: StringLiteralValue,
+}
+
 impl_with_id!(ServerScalarEntity<TNetworkProtocol: NetworkProtocol>, ServerScalarEntityId);
 
 type SelectableId = DefinitionLocation<ServerSelectableId, ClientSelectableId>;
@@ -28,6 +40,7 @@ pub type ServerObjectEntityAvailableSelectables = BTreeMap<SelectableName, Selec
 pub struct ServerObjectEntity<TNetworkProtocol: NetworkProtocol> {
     pub description: Option<DescriptionValue>,
     pub name: IsographObjectTypeName,
+    pub name_location: Location,
     /// Some if the object is concrete; None otherwise.
     pub concrete_type: Option<IsographObjectTypeName>,
 
@@ -1,15 +1,16 @@
 use std::{fmt::Debug, marker::PhantomData};
 
+use common_lang_types::VariableName;
 use common_lang_types::{
     DescriptionValue, ServerObjectSelectableName, ServerScalarSelectableName, WithLocation,
 };
 use isograph_lang_types::{
-    impl_with_id, impl_with_target_id, SelectionType, ServerEntityId, ServerObjectEntityId,
-    ServerObjectSelectableId, ServerScalarEntityId, ServerScalarSelectableId, TypeAnnotation,
-    VariableDefinition,
+    impl_with_id, impl_with_target_id, ConstantValue, SelectionType, ServerEntityId,
+    ServerObjectEntityId, ServerObjectSelectableId, ServerScalarEntityId, ServerScalarSelectableId,
+    TypeAnnotation, VariableDefinition,
 };
 
-use crate::{NetworkProtocol, SchemaServerObjectSelectableVariant};
+use crate::{NetworkProtocol, SchemaServerObjectSelectableVariant, ServerFieldKind};
 
 #[derive(Debug, Clone)]
 pub struct ServerScalarSelectable<TNetworkProtocol: NetworkProtocol> {
@@ -17,6 +18,7 @@ pub struct ServerScalarSelectable<TNetworkProtocol: NetworkProtocol> {
     pub name: WithLocation<ServerScalarSelectableName>,
 
     pub target_scalar_entity: TypeAnnotation<ServerScalarEntityId>,
+    pub kind: ServerFieldKind,
 
     pub parent_object_entity_id: ServerObjectEntityId,
     pub arguments: Vec<WithLocation<VariableDefinition<ServerEntityId>>>,
@@ -49,3 +51,28 @@ pub type ServerSelectable<'a, TNetworkProtocol> = SelectionType<
     &'a ServerScalarSelectable<TNetworkProtocol>,
     &'a ServerObjectSelectable<TNetworkProtocol>,
 >;
+
+/// An argument of a server selectable, whose type has been resolved against the
+/// schema. Unlike VariableDefinition, this does not contain GraphQL type annotations
+/// or locations, so consumers (e.g. custom generators) need not depend on the AST.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldArgument {
+    pub name: VariableName,
+    pub type_annotation: TypeAnnotation<ServerEntityId>,
+    pub default: Option<ConstantValue>,
+    pub description: Option<DescriptionValue>,
+}
+
+impl From<&VariableDefinition<ServerEntityId>> for FieldArgument {
+    fn from(argument: &VariableDefinition<ServerEntityId>) -> Self {
+        FieldArgument {
+            name: argument.name.item,
+            type_annotation: TypeAnnotation::from_graphql_type_annotation(argument.type_.clone()),
+            default: argument
+                .default_value
+                .as_ref()
+                .map(|default_value| default_value.item.clone()),
+            description: argument.description.map(|description| description.item),
+        }
+    }
+}
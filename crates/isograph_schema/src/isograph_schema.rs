@@ -1,12 +1,14 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     fmt::Debug,
+    hash::Hasher,
 };
 
 use common_lang_types::{
     ClientScalarSelectableName, GraphQLScalarTypeName, IsographObjectTypeName, JavascriptName,
     Location, ObjectSelectableName, SelectableName, UnvalidatedTypeName, WithLocation,
 };
+use fnv::FnvHasher;
 use graphql_lang_types::GraphQLNamedTypeAnnotation;
 use intern::string_key::Intern;
 use intern::Lookup;
@@ -16,17 +18,18 @@ use isograph_lang_types::{
     ClientScalarSelectableId, DefinitionLocation, EmptyDirectiveSet, ObjectSelection,
     ScalarSelection, SelectionType, SelectionTypeContainingSelections, ServerEntityId,
     ServerObjectEntityId, ServerObjectSelectableId, ServerScalarEntityId, ServerScalarSelectableId,
-    ServerStrongIdFieldId, VariableDefinition, WithId,
+    ServerStrongIdFieldId, TypeAnnotation, UnionVariant, VariableDefinition, WithId,
 };
 use lazy_static::lazy_static;
 
 use crate::{
     create_additional_fields::{CreateAdditionalFieldsError, CreateAdditionalFieldsResult},
-    ClientFieldVariant, ClientObjectSelectable, ClientScalarSelectable, ClientSelectableId,
-    EntrypointDeclarationInfo, NetworkProtocol, NormalizationKey, ObjectSelectable,
-    ObjectSelectableId, ServerEntity, ServerObjectEntity, ServerObjectEntityAvailableSelectables,
+    ClientFieldVariant, ClientObjectSelectable, ClientScalarSelectable, ClientSelectable,
+    ClientSelectableId, EntrypointDeclarationInfo, FieldArgument, NetworkProtocol,
+    NormalizationKey, ObjectSelectable, ObjectSelectableId, SdlKeyword, ServerEntity,
+    ServerFieldKind, ServerObjectEntity, ServerObjectEntityAvailableSelectables,
     ServerObjectSelectable, ServerScalarEntity, ServerScalarSelectable, ServerSelectable,
-    ServerSelectableId, UseRefetchFieldRefetchStrategy,
+    ServerSelectableId, UseRefetchFieldRefetchStrategy, REFETCH_FIELD_NAME,
 };
 
 lazy_static! {
@@ -49,6 +52,12 @@ pub struct Schema<TNetworkProtocol: NetworkProtocol> {
 
     /// These are root types like Query, Mutation, Subscription
     pub fetchable_types: BTreeMap<ServerObjectEntityId, RootOperationName>,
+    /// The root type of subscriptions. Subscriptions are not yet fetchable, so
+    /// this type is not one of the fetchable_types.
+    pub subscription_type_id: Option<ServerObjectEntityId>,
+
+    /// The name of the refetch field that is generated on objects that implement Node.
+    pub refetch_field_name: SelectableName,
 }
 
 impl<TNetworkProtocol: NetworkProtocol> Default for Schema<TNetworkProtocol> {
@@ -57,6 +66,17 @@ impl<TNetworkProtocol: NetworkProtocol> Default for Schema<TNetworkProtocol> {
     }
 }
 
+/// The number of entities and selectables that are about to be inserted into a
+/// schema. Used to reserve capacity up front, instead of repeatedly reallocating
+/// while processing large schemas.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SchemaCapacityHint {
+    pub server_scalars: usize,
+    pub server_objects: usize,
+    pub server_scalar_selectables: usize,
+    pub server_object_selectables: usize,
+}
+
 impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
     pub fn new() -> Self {
         // TODO add __typename
@@ -124,7 +144,508 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                 null_type_id,
             },
             fetchable_types: BTreeMap::new(),
+            subscription_type_id: None,
+            refetch_field_name: (*REFETCH_FIELD_NAME).into(),
+        }
+    }
+
+    pub fn with_capacity_hint(capacity_hint: SchemaCapacityHint) -> Self {
+        let mut schema = Self::new();
+        schema.reserve(capacity_hint);
+        schema
+    }
+
+    /// Reserve capacity for at least the hinted number of additional entities
+    /// and selectables.
+    pub fn reserve(&mut self, capacity_hint: SchemaCapacityHint) {
+        self.server_scalar_selectables
+            .reserve(capacity_hint.server_scalar_selectables);
+        self.server_object_selectables
+            .reserve(capacity_hint.server_object_selectables);
+
+        let server_entity_data = &mut self.server_entity_data;
+        server_entity_data
+            .server_scalars
+            .reserve(capacity_hint.server_scalars);
+        server_entity_data
+            .server_objects
+            .reserve(capacity_hint.server_objects);
+        server_entity_data
+            .defined_entities
+            .reserve(capacity_hint.server_scalars + capacity_hint.server_objects);
+        server_entity_data
+            .server_object_entity_extra_info
+            .reserve(capacity_hint.server_objects);
+    }
+
+    /// Remove everything that was added to the schema after it was created, so that
+    /// the schema can be reused (e.g. across rebuilds in watch mode). Schema-defined
+    /// scalars (ID, String, etc.) are preserved, and keep their ids.
+    pub fn clear(&mut self) {
+        self.server_scalar_selectables.clear();
+        self.server_object_selectables.clear();
+        self.client_scalar_selectables.clear();
+        self.client_object_selectables.clear();
+        self.entrypoints.clear();
+        self.fetchable_types.clear();
+        self.subscription_type_id = None;
+        self.server_entity_data.clear();
+    }
+
+    /// All types (including schema-defined scalars) that have been defined,
+    /// and whether they are scalars or objects.
+    pub fn defined_type_names(
+        &self,
+    ) -> impl Iterator<Item = (UnvalidatedTypeName, ServerEntityId)> + '_ {
+        self.server_entity_data
+            .defined_entities
+            .iter()
+            .map(|(name, entity_id)| (*name, *entity_id))
+    }
+
+    /// Objects that cannot be reached by traversing server object selectables
+    /// (including refinements, e.g. asPet) from the query root, and optionally
+    /// from the mutation root.
+    pub fn unreachable_objects(&self, include_mutation_root: bool) -> Vec<ServerObjectEntityId> {
+        let mut queue = self
+            .find_query()
+            .into_iter()
+            .chain(self.find_mutation().filter(|_| include_mutation_root))
+            .map(|(object_entity_id, _)| *object_entity_id)
+            .collect::<VecDeque<_>>();
+        let mut visited = queue.iter().copied().collect::<HashSet<_>>();
+
+        while let Some(object_entity_id) = queue.pop_front() {
+            let Some(extra_info) = self
+                .server_entity_data
+                .server_object_entity_extra_info
+                .get(&object_entity_id)
+            else {
+                continue;
+            };
+
+            for selectable_id in extra_info.selectables.values() {
+                if let DefinitionLocation::Server(SelectionType::Object(
+                    server_object_selectable_id,
+                )) = selectable_id
+                {
+                    let target_object_entity_id = *self
+                        .server_object_selectable(*server_object_selectable_id)
+                        .target_object_entity
+                        .inner();
+                    if visited.insert(target_object_entity_id) {
+                        queue.push_back(target_object_entity_id);
+                    }
+                }
+            }
+        }
+
+        (0..self.server_entity_data.server_objects.len())
+            .map(ServerObjectEntityId::from)
+            .filter(|object_entity_id| !visited.contains(object_entity_id))
+            .collect()
+    }
+
+    /// The linked fields (i.e. not inline fragments) whose innermost type is the
+    /// given object, along with the objects on which they are defined.
+    pub fn fields_returning(
+        &self,
+        target_object_entity_id: ServerObjectEntityId,
+    ) -> Vec<(ServerObjectEntityId, ServerObjectSelectableId)> {
+        self.server_object_selectables_and_ids()
+            .filter(|server_object_selectable| {
+                *server_object_selectable.item.target_object_entity.inner()
+                    == target_object_entity_id
+                    && matches!(
+                        server_object_selectable.item.object_selectable_variant,
+                        SchemaServerObjectSelectableVariant::LinkedField
+                    )
+            })
+            .map(|server_object_selectable| {
+                (
+                    server_object_selectable.item.parent_object_entity_id,
+                    server_object_selectable.id,
+                )
+            })
+            .collect()
+    }
+
+    /// The objects that refine (i.e. implement, or are members of) the given
+    /// object, as determined by its inline fragment (e.g. asPet) selectables.
+    pub fn implementors_of(
+        &self,
+        supertype_object_entity_id: ServerObjectEntityId,
+    ) -> Vec<ServerObjectEntityId> {
+        let Some(extra_info) = self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&supertype_object_entity_id)
+        else {
+            return vec![];
+        };
+
+        extra_info
+            .selectables
+            .values()
+            .filter_map(|selectable_id| match selectable_id {
+                DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) => {
+                    let server_object_selectable =
+                        self.server_object_selectable(*server_object_selectable_id);
+                    match server_object_selectable.object_selectable_variant {
+                        SchemaServerObjectSelectableVariant::InlineFragment => {
+                            Some(*server_object_selectable.target_object_entity.inner())
+                        }
+                        SchemaServerObjectSelectableVariant::LinkedField => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The maximum number of nested objects that can be selected starting from
+    /// (and including) the given object, by following linked server fields.
+    /// Refinements (e.g. asPet) do not add to the depth. Fields that point back
+    /// to an object that is already being traversed are ignored, so cyclic
+    /// schemas have a finite depth.
+    pub fn max_object_depth(&self, object_entity_id: ServerObjectEntityId) -> usize {
+        self.max_object_depth_impl(object_entity_id, &mut HashSet::new(), &mut HashMap::new())
+            .0
+    }
+
+    /// Returns the depth and whether a field pointing back to an object that is
+    /// being traversed was ignored. In that case, the depth depends on which objects
+    /// are being traversed, i.e. on the path to this object, so it is not memoized.
+    fn max_object_depth_impl(
+        &self,
+        object_entity_id: ServerObjectEntityId,
+        in_progress: &mut HashSet<ServerObjectEntityId>,
+        depths: &mut HashMap<ServerObjectEntityId, usize>,
+    ) -> (usize, bool) {
+        if let Some(depth) = depths.get(&object_entity_id) {
+            return (*depth, false);
+        }
+        in_progress.insert(object_entity_id);
+
+        let mut max_child_depth = 0;
+        let mut encountered_cycle = false;
+        if let Some(extra_info) = self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&object_entity_id)
+        {
+            for selectable_id in extra_info.selectables.values() {
+                if let DefinitionLocation::Server(SelectionType::Object(
+                    server_object_selectable_id,
+                )) = selectable_id
+                {
+                    let server_object_selectable =
+                        self.server_object_selectable(*server_object_selectable_id);
+                    if let SchemaServerObjectSelectableVariant::InlineFragment =
+                        server_object_selectable.object_selectable_variant
+                    {
+                        continue;
+                    }
+
+                    let target_object_entity_id =
+                        *server_object_selectable.target_object_entity.inner();
+                    if in_progress.contains(&target_object_entity_id) {
+                        encountered_cycle = true;
+                        continue;
+                    }
+                    let (child_depth, child_encountered_cycle) =
+                        self.max_object_depth_impl(target_object_entity_id, in_progress, depths);
+                    max_child_depth = max_child_depth.max(child_depth);
+                    encountered_cycle |= child_encountered_cycle;
+                }
+            }
+        }
+
+        in_progress.remove(&object_entity_id);
+        let depth = max_child_depth + 1;
+        if !encountered_cycle {
+            depths.insert(object_entity_id, depth);
+        }
+        (depth, encountered_cycle)
+    }
+
+    /// A hash of the stable parts of the schema (objects, scalars, server fields and
+    /// their types and arguments, refinements and root types, including descriptions,
+    /// deprecation reasons and default values), which can be used to determine
+    /// whether the schema has changed. Ids and locations are not hashed, and
+    /// everything is sorted before hashing, so the hash does not depend on the order
+    /// in which the schema was processed. FNV is used (rather than DefaultHasher,
+    /// whose algorithm may change between Rust versions), so the hash can be
+    /// persisted and compared across builds.
+    pub fn content_hash(&self) -> u64 {
+        let entity_name = |server_entity_id: ServerEntityId| match server_entity_id {
+            SelectionType::Scalar(scalar_entity_id) => self
+                .server_entity_data
+                .server_scalar_entity(scalar_entity_id)
+                .name
+                .item
+                .to_string(),
+            SelectionType::Object(object_entity_id) => self
+                .server_entity_data
+                .server_object_entity(object_entity_id)
+                .name
+                .to_string(),
+        };
+        let object_name = |object_entity_id: ServerObjectEntityId| {
+            entity_name(SelectionType::Object(object_entity_id))
+        };
+        let arguments_description =
+            |arguments: &[WithLocation<VariableDefinition<ServerEntityId>>]| {
+                arguments
+                    .iter()
+                    .map(|argument| {
+                        format!(
+                            "{}: {} = {:?} (description {:?}, deprecated {:?})",
+                            argument.item.name.item,
+                            argument.item.type_.clone().map(entity_name),
+                            argument
+                                .item
+                                .default_value
+                                .as_ref()
+                                .map(|default_value| default_value.item.print_to_string()),
+                            argument
+                                .item
+                                .description
+                                .map(|description| description.item),
+                            argument.item.deprecation_reason,
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+        let mut entries = vec![];
+        for scalar in self.server_entity_data.server_scalars.iter() {
+            entries.push(format!(
+                "scalar {} = {} (specified by {:?}, import {:?}, description {:?})",
+                scalar.name.item,
+                scalar.javascript_name,
+                scalar.specified_by_url,
+                scalar.javascript_import,
+                scalar.description.map(|description| description.item),
+            ));
+        }
+        for object in self.server_entity_data.server_objects.iter() {
+            entries.push(format!(
+                "object {} (concrete type {:?}, description {:?})",
+                object.name, object.concrete_type, object.description
+            ));
+        }
+        for scalar_selectable in self.server_scalar_selectables.iter() {
+            entries.push(format!(
+                "field {}.{}({}): {:?} {:?} (description {:?})",
+                object_name(scalar_selectable.parent_object_entity_id),
+                scalar_selectable.name.item,
+                arguments_description(&scalar_selectable.arguments),
+                scalar_selectable
+                    .target_scalar_entity
+                    .clone()
+                    .map(&mut |id| entity_name(SelectionType::Scalar(id))),
+                scalar_selectable.kind,
+                scalar_selectable.description,
+            ));
+        }
+        for object_selectable in self.server_object_selectables.iter() {
+            let kind = match object_selectable.object_selectable_variant {
+                SchemaServerObjectSelectableVariant::LinkedField => "field",
+                SchemaServerObjectSelectableVariant::InlineFragment => "refinement",
+            };
+            entries.push(format!(
+                "{kind} {}.{}({}): {:?} (description {:?})",
+                object_name(object_selectable.parent_object_entity_id),
+                object_selectable.name.item,
+                arguments_description(&object_selectable.arguments),
+                object_selectable
+                    .target_object_entity
+                    .clone()
+                    .map(&mut |id| object_name(id)),
+                object_selectable.description,
+            ));
+        }
+        for (object_entity_id, root_operation_name) in self.fetchable_types.iter() {
+            entries.push(format!(
+                "root {} {}",
+                root_operation_name.0,
+                object_name(*object_entity_id)
+            ));
+        }
+        entries.sort();
+
+        let mut hasher = FnvHasher::default();
+        for entry in entries {
+            hasher.write(entry.as_bytes());
+            hasher.write_u8(b'\n');
+        }
+        hasher.finish()
+    }
+
+    /// Prints the server types of the schema as SDL, e.g. for golden-file tests.
+    /// Scalars and then objects are printed, sorted by name, and fields are sorted
+    /// by name. Synthesized fields (__typename and refinements such as asPet)
+    /// are omitted; instead, refinements are printed as implements clauses and
+    /// union members.
+    pub fn to_sdl(&self) -> String {
+        let entity_name = |server_entity_id: ServerEntityId| match server_entity_id {
+            SelectionType::Scalar(scalar_entity_id) => self
+                .server_entity_data
+                .server_scalar_entity(scalar_entity_id)
+                .name
+                .item
+                .to_string(),
+            SelectionType::Object(object_entity_id) => self
+                .server_entity_data
+                .server_object_entity(object_entity_id)
+                .name
+                .to_string(),
+        };
+        let object_name = |object_entity_id: ServerObjectEntityId| {
+            entity_name(SelectionType::Object(object_entity_id))
+        };
+
+        let mut subtypes = HashMap::<_, Vec<_>>::new();
+        let mut supertypes = HashMap::<_, Vec<_>>::new();
+        for object_selectable in self.server_object_selectables.iter() {
+            if let SchemaServerObjectSelectableVariant::InlineFragment =
+                object_selectable.object_selectable_variant
+            {
+                let supertype_id = object_selectable.parent_object_entity_id;
+                let subtype_id = *object_selectable.target_object_entity.inner();
+                subtypes
+                    .entry(supertype_id)
+                    .or_default()
+                    .push(object_name(subtype_id));
+                if TNetworkProtocol::sdl_keyword(
+                    self.server_entity_data.server_object_entity(supertype_id),
+                ) == SdlKeyword::Interface
+                {
+                    supertypes
+                        .entry(subtype_id)
+                        .or_default()
+                        .push(object_name(supertype_id));
+                }
+            }
+        }
+
+        let mut scalar_definitions = self
+            .server_entity_data
+            .server_scalars
+            .iter()
+            .enumerate()
+            // The null type is an implementation detail, and is not a GraphQL scalar
+            .filter(|(index, _)| {
+                ServerScalarEntityId::from(*index) != self.server_entity_data.null_type_id
+            })
+            .map(|(_, scalar)| format!("scalar {}\n", scalar.name.item))
+            .collect::<Vec<_>>();
+        scalar_definitions.sort();
+
+        let mut object_definitions = vec![];
+        for (index, object) in self.server_entity_data.server_objects.iter().enumerate() {
+            let object_entity_id: ServerObjectEntityId = index.into();
+            let keyword = TNetworkProtocol::sdl_keyword(object);
+            if keyword == SdlKeyword::Union {
+                let mut members = subtypes.remove(&object_entity_id).unwrap_or_default();
+                members.sort();
+                object_definitions.push(format!(
+                    "union {} = {}\n",
+                    object.name,
+                    members.join(" | ")
+                ));
+                continue;
+            }
+
+            let mut definition = format!("{keyword} {}", object.name);
+            if let Some(mut interfaces) = supertypes.remove(&object_entity_id) {
+                interfaces.sort();
+                definition.push_str(&format!(" implements {}", interfaces.join(" & ")));
+            }
+            definition.push_str(" {\n");
+            let selectables = self
+                .server_entity_data
+                .server_object_entity_extra_info
+                .get(&object_entity_id)
+                .map(|extra_info| &extra_info.selectables);
+            for (name, selectable_id) in selectables.into_iter().flatten() {
+                let (arguments, type_) = match selectable_id {
+                    DefinitionLocation::Server(SelectionType::Scalar(
+                        server_scalar_selectable_id,
+                    )) => {
+                        let server_scalar_selectable =
+                            self.server_scalar_selectable(*server_scalar_selectable_id);
+                        if server_scalar_selectable.kind == ServerFieldKind::TypenameField {
+                            continue;
+                        }
+                        (
+                            &server_scalar_selectable.arguments,
+                            type_annotation_sdl(
+                                &server_scalar_selectable
+                                    .target_scalar_entity
+                                    .clone()
+                                    .map(&mut |id| entity_name(SelectionType::Scalar(id))),
+                            ),
+                        )
+                    }
+                    DefinitionLocation::Server(SelectionType::Object(
+                        server_object_selectable_id,
+                    )) => {
+                        let server_object_selectable =
+                            self.server_object_selectable(*server_object_selectable_id);
+                        if let SchemaServerObjectSelectableVariant::InlineFragment =
+                            server_object_selectable.object_selectable_variant
+                        {
+                            continue;
+                        }
+                        (
+                            &server_object_selectable.arguments,
+                            type_annotation_sdl(
+                                &server_object_selectable
+                                    .target_object_entity
+                                    .clone()
+                                    .map(&mut |id| object_name(id)),
+                            ),
+                        )
+                    }
+                    DefinitionLocation::Client(_) => continue,
+                };
+                let arguments = if arguments.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "({})",
+                        arguments
+                            .iter()
+                            .map(|argument| format!(
+                                "{}: {}",
+                                argument.item.name.item,
+                                argument.item.type_.clone().map(entity_name)
+                            ))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                };
+                definition.push_str(&format!("  {name}{arguments}: {type_}\n"));
+            }
+            definition.push_str("}\n");
+            object_definitions.push(definition);
         }
+        object_definitions.sort_by_key(|definition| {
+            // Sort by name, not by keyword
+            definition
+                .split_whitespace()
+                .nth(1)
+                .map(|name| name.to_string())
+        });
+
+        scalar_definitions
+            .into_iter()
+            .chain(object_definitions)
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
     /// This is a smell, and we should refactor away from it, or all schema's
@@ -150,6 +671,18 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             .find(|(_, root_operation_name)| root_operation_name.0 == "query")
     }
 
+    /// Whether this object is the query root. Root types are identified by the
+    /// operation they are the root of, not by their name.
+    pub fn is_query_root(&self, object_entity_id: ServerObjectEntityId) -> bool {
+        self.find_query()
+            .is_some_and(|(query_id, _)| *query_id == object_entity_id)
+    }
+
+    pub fn is_mutation_root(&self, object_entity_id: ServerObjectEntityId) -> bool {
+        self.find_mutation()
+            .is_some_and(|(mutation_id, _)| *mutation_id == object_entity_id)
+    }
+
     pub fn traverse_object_selections(
         &self,
         root_object_entity_id: ServerObjectEntityId,
@@ -365,6 +898,80 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         }
     }
 
+    /// The arguments of a server selectable, with their types resolved
+    pub fn resolved_arguments(
+        &self,
+        server_selectable_id: ServerSelectableId,
+    ) -> Vec<FieldArgument> {
+        let arguments = match self.server_selectable(server_selectable_id) {
+            SelectionType::Scalar(scalar_selectable) => &scalar_selectable.arguments,
+            SelectionType::Object(object_selectable) => &object_selectable.arguments,
+        };
+        arguments
+            .iter()
+            .map(|argument| FieldArgument::from(&argument.item))
+            .collect()
+    }
+
+    /// Look up a selectable on an object by name. Returns None if there is no such
+    /// selectable, or if it is a client selectable.
+    pub fn server_selectable_by_name(
+        &self,
+        parent_object_entity_id: ServerObjectEntityId,
+        name: SelectableName,
+    ) -> Option<ServerSelectable<'_, TNetworkProtocol>> {
+        let server_selectable_id = *self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&parent_object_entity_id)?
+            .selectables
+            .get(&name)?
+            .as_server()?;
+        Some(self.server_selectable(server_selectable_id))
+    }
+
+    /// The object on which the given server selectable is defined
+    pub fn server_selectable_parent(
+        &self,
+        server_selectable_id: ServerSelectableId,
+    ) -> &ServerObjectEntity<TNetworkProtocol> {
+        let parent_object_entity_id = match self.server_selectable(server_selectable_id) {
+            SelectionType::Scalar(scalar_selectable) => scalar_selectable.parent_object_entity_id,
+            SelectionType::Object(object_selectable) => object_selectable.parent_object_entity_id,
+        };
+        self.server_entity_data
+            .server_object_entity(parent_object_entity_id)
+    }
+
+    /// The objects that have an id field, and thus a __refetch field, in order.
+    pub fn refetchable_objects(&self) -> Vec<ServerObjectEntityId> {
+        let mut refetchable_objects = self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .iter()
+            .filter_map(|(object_entity_id, extra_info)| {
+                extra_info.id_field.map(|_| *object_entity_id)
+            })
+            .collect::<Vec<_>>();
+        refetchable_objects.sort();
+        refetchable_objects
+    }
+
+    /// The location of the name of the scalar in its definition
+    pub fn scalar_location(&self, scalar_entity_id: ServerScalarEntityId) -> Location {
+        self.server_entity_data
+            .server_scalar_entity(scalar_entity_id)
+            .name
+            .location
+    }
+
+    /// The location of the name of the object in its definition
+    pub fn object_location(&self, object_entity_id: ServerObjectEntityId) -> Location {
+        self.server_entity_data
+            .server_object_entity(object_entity_id)
+            .name_location
+    }
+
     pub fn insert_server_scalar_selectable(
         &mut self,
         server_scalar_selectable: ServerScalarSelectable<TNetworkProtocol>,
@@ -391,24 +998,21 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             .entry(parent_object_entity_id)
             .or_default();
 
-        if selectables
-            .insert(
-                next_scalar_name.item.into(),
-                DefinitionLocation::Server(SelectionType::Scalar(next_server_scalar_selectable_id)),
-            )
-            .is_some()
-        {
+        if let Some(previous_selectable_id) = selectables.insert(
+            next_scalar_name.item.into(),
+            DefinitionLocation::Server(SelectionType::Scalar(next_server_scalar_selectable_id)),
+        ) {
             let parent_object = self
                 .server_entity_data
                 .server_object_entity(parent_object_entity_id);
             return Err(CreateAdditionalFieldsError::DuplicateField {
                 field_name: server_scalar_selectable.name.item.into(),
                 parent_type: parent_object.name,
+                previous_location: self.selectable_name_location(previous_selectable_id),
             });
         }
 
-        // TODO do not do this here, this is a GraphQL-ism
-        if server_scalar_selectable.name.item == "id" {
+        if server_scalar_selectable.kind == ServerFieldKind::IdField {
             set_and_validate_id_field(
                 id_field,
                 next_server_scalar_selectable_id,
@@ -432,7 +1036,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         let parent_object_entity_id = server_object_selectable.parent_object_entity_id;
         let next_object_name = server_object_selectable.name;
 
-        if self
+        if let Some(previous_selectable_id) = self
             .server_entity_data
             .server_object_entity_extra_info
             .entry(parent_object_entity_id)
@@ -442,7 +1046,6 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                 next_object_name.item.into(),
                 DefinitionLocation::Server(SelectionType::Object(next_server_object_selectable_id)),
             )
-            .is_some()
         {
             let parent_object = self
                 .server_entity_data
@@ -450,6 +1053,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             return Err(CreateAdditionalFieldsError::DuplicateField {
                 field_name: next_object_name.item.into(),
                 parent_type: parent_object.name,
+                previous_location: self.selectable_name_location(previous_selectable_id),
             });
         }
 
@@ -459,6 +1063,70 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         Ok(())
     }
 
+    /// Add a client scalar selectable (e.g. one created by a framework integration,
+    /// rather than by an iso literal) to its parent object, before iso literals are
+    /// processed. Returns the id of the new selectable.
+    pub fn insert_client_scalar_selectable(
+        &mut self,
+        client_scalar_selectable: ClientScalarSelectable<TNetworkProtocol>,
+    ) -> CreateAdditionalFieldsResult<ClientScalarSelectableId> {
+        let next_client_scalar_selectable_id = self.client_scalar_selectables.len().into();
+        let parent_object_entity_id = client_scalar_selectable.parent_object_entity_id;
+        let next_client_scalar_name = client_scalar_selectable.name;
+
+        // Check for a duplicate before inserting, so that the previous selectable
+        // remains in place if the caller recovers from the error.
+        let selectables = &mut self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .entry(parent_object_entity_id)
+            .or_default()
+            .selectables;
+        if let Some(previous_selectable_id) =
+            selectables.get(&next_client_scalar_name.into()).copied()
+        {
+            let parent_object = self
+                .server_entity_data
+                .server_object_entity(parent_object_entity_id);
+            return Err(CreateAdditionalFieldsError::DuplicateField {
+                field_name: next_client_scalar_name.into(),
+                parent_type: parent_object.name,
+                previous_location: self.selectable_name_location(previous_selectable_id),
+            });
+        }
+        selectables.insert(
+            next_client_scalar_name.into(),
+            DefinitionLocation::Client(SelectionType::Scalar(next_client_scalar_selectable_id)),
+        );
+
+        self.client_scalar_selectables
+            .push(client_scalar_selectable);
+
+        Ok(next_client_scalar_selectable_id)
+    }
+
+    /// The location of the name of a selectable, e.g. for reporting where a
+    /// duplicate field was previously defined.
+    fn selectable_name_location(
+        &self,
+        selectable_id: DefinitionLocation<ServerSelectableId, ClientSelectableId>,
+    ) -> Location {
+        match selectable_id {
+            DefinitionLocation::Server(SelectionType::Scalar(server_scalar_selectable_id)) => {
+                self.server_scalar_selectable(server_scalar_selectable_id)
+                    .name
+                    .location
+            }
+            DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) => {
+                self.server_object_selectable(server_object_selectable_id)
+                    .name
+                    .location
+            }
+            // Client selectables do not keep track of where they were defined
+            DefinitionLocation::Client(_) => Location::generated(),
+        }
+    }
+
     /// Get a reference to a given client field by its id.
     pub fn client_field(
         &self,
@@ -537,6 +1205,38 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         }
     }
 
+    /// Look up a selectable on an object by name. Returns None if there is no such
+    /// selectable, or if it is a server selectable.
+    pub fn client_selectable_by_name(
+        &self,
+        parent_object_entity_id: ServerObjectEntityId,
+        name: SelectableName,
+    ) -> Option<ClientSelectable<'_, TNetworkProtocol>> {
+        let client_selectable_id = *self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&parent_object_entity_id)?
+            .selectables
+            .get(&name)?
+            .as_client()?;
+        Some(self.client_type(client_selectable_id))
+    }
+
+    /// The refetch field (e.g. __refetch) that is generated on an object. Returns None
+    /// if the object is not refetchable.
+    pub fn refetch_field(
+        &self,
+        parent_object_entity_id: ServerObjectEntityId,
+    ) -> Option<&ClientScalarSelectable<TNetworkProtocol>> {
+        let refetch_field = *self
+            .client_selectable_by_name(parent_object_entity_id, self.refetch_field_name)?
+            .as_scalar()?;
+        match refetch_field.variant {
+            ClientFieldVariant::ImperativelyLoadedField(_) => Some(refetch_field),
+            ClientFieldVariant::UserWritten(_) | ClientFieldVariant::Link => None,
+        }
+    }
+
     #[allow(clippy::type_complexity)]
     pub fn user_written_client_types(
         &self,
@@ -578,6 +1278,51 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
 }
 
 impl<TNetworkProtocol: NetworkProtocol> ServerEntityData<TNetworkProtocol> {
+    /// The ids of the scalars (e.g. String or ID) that every schema defines
+    fn schema_defined_scalar_ids(&self) -> [ServerScalarEntityId; 6] {
+        [
+            self.id_type_id,
+            self.string_type_id,
+            self.float_type_id,
+            self.boolean_type_id,
+            self.int_type_id,
+            self.null_type_id,
+        ]
+    }
+
+    /// Whether the entity is one of the scalars (e.g. String or ID) that every
+    /// schema defines
+    fn is_schema_defined_scalar(&self, entity_id: ServerEntityId) -> bool {
+        match entity_id {
+            SelectionType::Scalar(scalar_entity_id) => {
+                self.schema_defined_scalar_ids().contains(&scalar_entity_id)
+            }
+            SelectionType::Object(_) => false,
+        }
+    }
+
+    /// Remove all server objects and all scalars except for the schema-defined
+    /// scalars, which are added first (in Schema::new) and thus retain their ids.
+    fn clear(&mut self) {
+        let schema_defined_scalar_count = self
+            .schema_defined_scalar_ids()
+            .into_iter()
+            .map(|scalar_entity_id| scalar_entity_id.as_usize() + 1)
+            .max()
+            .expect("Expected well known types to be non-empty");
+
+        self.server_objects.clear();
+        self.server_scalars.truncate(schema_defined_scalar_count);
+        self.defined_entities
+            .retain(|_, entity_id| match entity_id {
+                SelectionType::Scalar(scalar_entity_id) => {
+                    scalar_entity_id.as_usize() < schema_defined_scalar_count
+                }
+                SelectionType::Object(_) => false,
+            });
+        self.server_object_entity_extra_info.clear();
+    }
+
     pub fn server_scalar_entity(
         &self,
         scalar_entity_id: ServerScalarEntityId,
@@ -636,14 +1381,18 @@ impl<TNetworkProtocol: NetworkProtocol> ServerEntityData<TNetworkProtocol> {
         name_location: Location,
     ) -> Result<(), WithLocation<CreateAdditionalFieldsError>> {
         let next_scalar_entity_id = self.server_scalars.len().into();
-        if self
-            .defined_entities
-            .insert(
-                server_scalar_entity.name.item.into(),
-                SelectionType::Scalar(next_scalar_entity_id),
-            )
-            .is_some()
-        {
+        if let Some(previous_entity_id) = self.defined_entities.insert(
+            server_scalar_entity.name.item.into(),
+            SelectionType::Scalar(next_scalar_entity_id),
+        ) {
+            if self.is_schema_defined_scalar(previous_entity_id) {
+                return Err(WithLocation::new(
+                    CreateAdditionalFieldsError::RedefinedBuiltinScalar {
+                        name: server_scalar_entity.name.item.into(),
+                    },
+                    name_location,
+                ));
+            }
             return Err(WithLocation::new(
                 CreateAdditionalFieldsError::DuplicateTypeDefinition {
                     type_definition_type: "scalar",
@@ -662,14 +1411,18 @@ impl<TNetworkProtocol: NetworkProtocol> ServerEntityData<TNetworkProtocol> {
         name_location: Location,
     ) -> Result<ServerObjectEntityId, WithLocation<CreateAdditionalFieldsError>> {
         let next_object_entity_id = self.server_objects.len().into();
-        if self
-            .defined_entities
-            .insert(
-                server_object_entity.name.into(),
-                SelectionType::Object(next_object_entity_id),
-            )
-            .is_some()
-        {
+        if let Some(previous_entity_id) = self.defined_entities.insert(
+            server_object_entity.name.into(),
+            SelectionType::Object(next_object_entity_id),
+        ) {
+            if self.is_schema_defined_scalar(previous_entity_id) {
+                return Err(WithLocation::new(
+                    CreateAdditionalFieldsError::RedefinedBuiltinScalar {
+                        name: server_object_entity.name.into(),
+                    },
+                    name_location,
+                ));
+            }
             return Err(WithLocation::new(
                 CreateAdditionalFieldsError::DuplicateTypeDefinition {
                     type_definition_type: "object",
@@ -722,6 +1475,8 @@ fn add_schema_defined_scalar_type<TNetworkProtocol: NetworkProtocol>(
         description: None,
         name: typename,
         javascript_name,
+        specified_by_url: None,
+        javascript_import: None,
         output_format: std::marker::PhantomData,
     });
     defined_types.insert(
@@ -753,6 +1508,30 @@ pub type ValidatedUseRefetchFieldStrategy =
 pub type ScalarSelectableId =
     DefinitionLocation<ServerScalarSelectableId, ClientScalarSelectableId>;
 
+/// e.g. [String]! for a non-null list of nullable strings
+pub(crate) fn type_annotation_sdl(type_annotation: &TypeAnnotation<String>) -> String {
+    match type_annotation {
+        TypeAnnotation::Scalar(name) => format!("{name}!"),
+        TypeAnnotation::Plural(inner) => format!("[{}]!", type_annotation_sdl(inner)),
+        TypeAnnotation::Union(union_type_annotation) => {
+            let variants = union_type_annotation
+                .variants
+                .iter()
+                .map(|variant| match variant {
+                    UnionVariant::Scalar(name) => name.clone(),
+                    UnionVariant::Plural(inner) => format!("[{}]", type_annotation_sdl(inner)),
+                })
+                .collect::<Vec<_>>()
+                .join(" | ");
+            if union_type_annotation.nullable {
+                variants
+            } else {
+                format!("{variants}!")
+            }
+        }
+    }
+}
+
 /// If we have encountered an id field, we can:
 /// - validate that the id field is properly defined, i.e. has type ID!
 /// - set the id field
@@ -794,3 +1573,73 @@ fn set_and_validate_id_field(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use common_lang_types::{Location, Span, WithLocation, WithSpan};
+    use intern::string_key::Intern;
+    use isograph_lang_types::ConstantValue;
+
+    use crate::test_schema::{argument, insert_field, insert_object, TestSchema};
+
+    type ChangeSchema = fn(&mut TestSchema);
+
+    /// type Query { pets(first: Int = 10): [Pet] } type Pet { name: String },
+    /// modified by change
+    fn pets_schema_content_hash(change: impl FnOnce(&mut TestSchema)) -> u64 {
+        let mut schema = TestSchema::new();
+        let query = insert_object(&mut schema, "Query");
+        let pet = insert_object(&mut schema, "Pet");
+        let mut first = argument(&schema, "first", "Int");
+        first.item.default_value = Some(WithLocation::new(
+            ConstantValue::Integer(10),
+            Location::generated(),
+        ));
+        insert_field(&mut schema, query, "pets", "[Pet]", vec![first]);
+        insert_field(&mut schema, pet, "name", "String", vec![]);
+        change(&mut schema);
+        schema.content_hash()
+    }
+
+    #[test]
+    fn content_hash_includes_descriptions_deprecations_and_default_values() {
+        let content_hash = pets_schema_content_hash(|_| {});
+
+        let changes: [(&str, ChangeSchema); 5] = [
+            ("default value", |schema| {
+                schema.server_object_selectables[0].arguments[0]
+                    .item
+                    .default_value = Some(WithLocation::new(
+                    ConstantValue::Integer(20),
+                    Location::generated(),
+                ));
+            }),
+            ("argument description", |schema| {
+                schema.server_object_selectables[0].arguments[0]
+                    .item
+                    .description = Some(WithSpan::new(
+                    "How many".intern().into(),
+                    Span::todo_generated(),
+                ));
+            }),
+            ("argument deprecation", |schema| {
+                schema.server_object_selectables[0].arguments[0]
+                    .item
+                    .deprecation_reason = Some("Use last".intern().into());
+            }),
+            ("field description", |schema| {
+                schema.server_scalar_selectables[0].description = Some("The name".intern().into());
+            }),
+            ("object description", |schema| {
+                schema.server_entity_data.server_objects[1].description =
+                    Some("A pet".intern().into());
+            }),
+        ];
+        for (changed, change) in changes {
+            assert_ne!(content_hash, pets_schema_content_hash(change), "{changed}");
+        }
+        // The hash does not depend on the Rust version, so it can be persisted and
+        // compared across builds.
+        assert_eq!(content_hash, 533019622939397869);
+    }
+}
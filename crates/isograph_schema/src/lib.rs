@@ -10,6 +10,11 @@ mod object_type_definition;
 mod process_client_field_declaration;
 mod refetch_strategy;
 mod root_types;
+mod scalar_type_definition;
+mod schema_consistency;
+mod schema_diff;
+#[cfg(test)]
+mod test_schema;
 mod validate_argument_types;
 mod validate_entrypoint;
 mod validate_use_of_arguments;
@@ -28,6 +33,9 @@ pub use object_type_definition::*;
 pub use process_client_field_declaration::*;
 pub use refetch_strategy::*;
 pub use root_types::*;
+pub use scalar_type_definition::*;
+pub use schema_consistency::*;
+pub use schema_diff::*;
 pub use validate_entrypoint::*;
 pub use validate_use_of_arguments::*;
 pub use variable_context::*;
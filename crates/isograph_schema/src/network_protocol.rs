@@ -1,10 +1,18 @@
-use std::{error::Error, fmt::Debug, hash::Hash};
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
 
 use common_lang_types::{
     DescriptionValue, IsographObjectTypeName, Location, QueryOperationName, QueryText,
     ServerSelectableName, UnvalidatedTypeName, WithLocation, WithSpan,
 };
-use graphql_lang_types::{GraphQLInputValueDefinition, GraphQLTypeAnnotation, RootOperationKind};
+use graphql_lang_types::{
+    GraphQLConstantValue, GraphQLDirective, GraphQLInputValueDefinition, GraphQLTypeAnnotation,
+    RootOperationKind,
+};
+use isograph_config::CompilerConfigOptions;
 use pico::Database;
 
 use crate::{
@@ -25,6 +33,7 @@ where
     fn parse_and_process_type_system_documents(
         db: &Database,
         sources: &Self::Sources,
+        options: &CompilerConfigOptions,
     ) -> Result<ProcessTypeSystemDocumentOutcome<Self>, Box<dyn Error>>;
 
     fn generate_query_text<'a>(
@@ -34,6 +43,38 @@ where
         query_variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
         root_operation_name: &RootOperationName,
     ) -> QueryText;
+
+    /// The TypeScript type that is emitted for a given scalar. By default, this
+    /// is the name of the imported type, if the scalar has an import, and otherwise
+    /// the javascript_name of the scalar.
+    fn format_scalar_type(scalar: &ServerScalarEntity<Self>) -> String {
+        match scalar.javascript_import {
+            Some(javascript_import) => javascript_import.type_name.to_string(),
+            None => scalar.javascript_name.to_string(),
+        }
+    }
+
+    /// Whether __typename fields are emitted in generated types. Protocols whose
+    /// responses do not contain a __typename should return false.
+    fn emit_typename_field() -> bool {
+        true
+    }
+
+    /// The keyword with which an object is printed in SDL. By default, abstract
+    /// objects are printed as interfaces.
+    fn sdl_keyword(object: &ServerObjectEntity<Self>) -> SdlKeyword {
+        if object.concrete_type.is_some() {
+            SdlKeyword::Type
+        } else {
+            SdlKeyword::Interface
+        }
+    }
+
+    /// Whether exactly one of the fields of an input object must be provided
+    /// (e.g. a GraphQL input object with the @oneOf directive.)
+    fn is_one_of(_object: &ServerObjectEntity<Self>) -> bool {
+        false
+    }
 }
 
 pub struct ProcessTypeSystemDocumentOutcome<TNetworkProtocol: NetworkProtocol> {
@@ -59,6 +100,7 @@ pub struct FieldToInsert {
     pub name: WithLocation<ServerSelectableName>,
     pub type_: GraphQLTypeAnnotation<UnvalidatedTypeName>,
     pub arguments: Vec<WithLocation<GraphQLInputValueDefinition>>,
+    pub directives: Vec<GraphQLDirective<GraphQLConstantValue>>,
 
     // TODO we can probably restructure things to make this less awkward.
     // As in, we should not return GraphQLFieldDefinitions to the isograph side,
@@ -71,6 +113,37 @@ pub struct FieldToInsert {
     // that the network protocol should care about?? I don't think so, but how else
     // do we add the __typename and link selections?)
     pub is_inline_fragment: bool,
+    pub kind: ServerFieldKind,
+}
+
+/// Whether a server field is one of the fields that Isograph treats specially.
+/// __typename fields are synthesized by Isograph, and are not present in the
+/// schema itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerFieldKind {
+    IdField,
+    TypenameField,
+    ActualField,
+}
+
+/// The keyword with which an object is printed in SDL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdlKeyword {
+    Type,
+    Interface,
+    Union,
+    Input,
+}
+
+impl Display for SdlKeyword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SdlKeyword::Type => write!(f, "type"),
+            SdlKeyword::Interface => write!(f, "interface"),
+            SdlKeyword::Union => write!(f, "union"),
+            SdlKeyword::Input => write!(f, "input"),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use common_lang_types::{
     ClientScalarSelectableName, ConstExportName, IsographDirectiveName, IsographObjectTypeName,
-    Location, ObjectTypeAndFieldName, RelativePathToSourceFile, SelectableName, TextSource,
+    Location, ObjectTypeAndFieldName, RelativePathToSourceFile, SelectableName, Span, TextSource,
     UnvalidatedTypeName, VariableName, WithLocation, WithSpan,
 };
 use intern::string_key::Intern;
@@ -159,20 +159,56 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         Ok(unprocessed_client_pointer_items)
     }
 
+    /// Client fields and pointers cannot share a name with a server field on
+    /// the same object, since selections would be ambiguous.
+    fn validate_does_not_shadow_server_field(
+        &self,
+        parent_object_entity_id: ServerObjectEntityId,
+        client_selectable_name: SelectableName,
+        client_selectable_name_span: Span,
+    ) -> ProcessClientFieldDeclarationResult<()> {
+        let existing_selectable = self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&parent_object_entity_id)
+            .and_then(|extra_info| extra_info.selectables.get(&client_selectable_name));
+
+        if let Some(DefinitionLocation::Server(_)) = existing_selectable {
+            return Err(WithSpan::new(
+                ProcessClientFieldDeclarationError::ClientFieldShadowsServerField {
+                    parent_type_name: self
+                        .server_entity_data
+                        .server_object_entity(parent_object_entity_id)
+                        .name,
+                    client_field_name: client_selectable_name,
+                },
+                client_selectable_name_span,
+            ));
+        }
+
+        Ok(())
+    }
+
     fn add_client_field_to_object(
         &mut self,
         parent_object_entity_id: ServerObjectEntityId,
         client_field_declaration: WithSpan<ClientFieldDeclaration>,
     ) -> ProcessClientFieldDeclarationResult<UnprocessedClientFieldItem> {
         let query_id = self.query_id();
-        let object =
-            &mut self.server_entity_data.server_objects[parent_object_entity_id.as_usize()];
         let client_field_field_name_ws = client_field_declaration.item.client_field_name;
         let client_field_name = client_field_field_name_ws.item;
         let client_field_name_span = client_field_field_name_ws.span;
 
         let next_client_field_id = self.client_scalar_selectables.len().into();
 
+        self.validate_does_not_shadow_server_field(
+            parent_object_entity_id,
+            client_field_name.into(),
+            client_field_name_span,
+        )?;
+
+        let object =
+            &mut self.server_entity_data.server_objects[parent_object_entity_id.as_usize()];
         if self
             .server_entity_data
             .server_object_entity_extra_info
@@ -365,6 +401,12 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             },
         });
 
+        self.validate_does_not_shadow_server_field(
+            parent_object_entity_id,
+            client_pointer_name.into(),
+            client_pointer_name_span,
+        )?;
+
         if self
             .server_entity_data
             .server_object_entity_extra_info
@@ -440,6 +482,15 @@ pub enum ProcessClientFieldDeclarationError {
         client_field_name: SelectableName,
     },
 
+    #[error(
+        "The Isograph object type \"{parent_type_name}\" has a server field named \"{client_field_name}\". \
+        Client fields and pointers cannot have the same name as a server field."
+    )]
+    ClientFieldShadowsServerField {
+        parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableName,
+    },
+
     #[error("Error when deserializing directives. Message: {message}")]
     UnableToDeserializeDirectives { message: DeserializationError },
 
@@ -535,9 +586,11 @@ pub fn validate_variable_definition(
 
     Ok(WithSpan::new(
         VariableDefinition {
+            description: variable_definition.item.description,
             name: variable_definition.item.name.map(VariableName::from),
             type_,
             default_value: variable_definition.item.default_value,
+            deprecation_reason: variable_definition.item.deprecation_reason,
         },
         variable_definition.span,
     ))
@@ -0,0 +1,34 @@
+use common_lang_types::{DescriptionValue, GraphQLScalarTypeName, WithLocation, WithSpan};
+use graphql_lang_types::{
+    GraphQLConstantValue, GraphQLDirective, GraphQLEnumDefinition, GraphQLScalarTypeDefinition,
+};
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct IsographScalarTypeDefinition {
+    pub description: Option<WithSpan<DescriptionValue>>,
+    pub name: WithLocation<GraphQLScalarTypeName>,
+    /// Directives that we don't know about. Maybe this should be validated to be
+    /// empty, or not exist.
+    pub directives: Vec<GraphQLDirective<GraphQLConstantValue>>,
+}
+
+impl From<GraphQLScalarTypeDefinition> for IsographScalarTypeDefinition {
+    fn from(scalar_type_definition: GraphQLScalarTypeDefinition) -> Self {
+        Self {
+            description: scalar_type_definition.description,
+            name: scalar_type_definition.name,
+            directives: scalar_type_definition.directives,
+        }
+    }
+}
+
+// TODO this is bad. Enums are not scalars, and we should model them as such.
+impl From<GraphQLEnumDefinition> for IsographScalarTypeDefinition {
+    fn from(enum_definition: GraphQLEnumDefinition) -> Self {
+        Self {
+            description: enum_definition.description,
+            name: enum_definition.name.map(|x| x.unchecked_conversion()),
+            directives: enum_definition.directives,
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use common_lang_types::SelectableName;
+use isograph_lang_types::{
+    DefinitionLocation, SelectionType, ServerObjectEntityId, ServerStrongIdFieldId,
+};
+use thiserror::Error;
+
+use crate::{ClientSelectableId, NetworkProtocol, Schema, ServerSelectableId};
+
+impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
+    /// Checks that every id stored in the schema (the available selectables and
+    /// id field of each object, and the parent of each selectable) refers to an
+    /// entity or selectable that exists. A failure indicates a bug in Isograph,
+    /// e.g. when processing extensions, not an invalid schema.
+    pub fn assert_consistent(&self) -> Result<(), ConsistencyError> {
+        let object_exists = |object_entity_id: ServerObjectEntityId| {
+            object_entity_id.as_usize() < self.server_entity_data.server_objects.len()
+        };
+
+        // Sort by id, so that the same inconsistency is always reported first
+        let mut extra_infos = self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .iter()
+            .collect::<Vec<_>>();
+        extra_infos.sort_by_key(|(object_entity_id, _)| **object_entity_id);
+
+        for (object_entity_id, extra_info) in extra_infos {
+            if !object_exists(*object_entity_id) {
+                return Err(ConsistencyError::DanglingObjectEntityId {
+                    object_entity_id: *object_entity_id,
+                });
+            }
+
+            for (selectable_name, selectable_id) in extra_info.selectables.iter() {
+                let exists = match selectable_id {
+                    DefinitionLocation::Server(SelectionType::Scalar(id)) => {
+                        id.as_usize() < self.server_scalar_selectables.len()
+                    }
+                    DefinitionLocation::Server(SelectionType::Object(id)) => {
+                        id.as_usize() < self.server_object_selectables.len()
+                    }
+                    DefinitionLocation::Client(SelectionType::Scalar(id)) => {
+                        id.as_usize() < self.client_scalar_selectables.len()
+                    }
+                    DefinitionLocation::Client(SelectionType::Object(id)) => {
+                        id.as_usize() < self.client_object_selectables.len()
+                    }
+                };
+                if !exists {
+                    return Err(ConsistencyError::DanglingSelectableId {
+                        parent_object_entity_id: *object_entity_id,
+                        selectable_name: *selectable_name,
+                        selectable_id: *selectable_id,
+                    });
+                }
+            }
+
+            if let Some(id_field) = extra_info.id_field {
+                if id_field.as_usize() >= self.server_scalar_selectables.len() {
+                    return Err(ConsistencyError::DanglingIdField {
+                        parent_object_entity_id: *object_entity_id,
+                        id_field,
+                    });
+                }
+            }
+        }
+
+        let parent_object_entity_ids =
+            self.server_scalar_selectables
+                .iter()
+                .map(|selectable| {
+                    (
+                        selectable.name.item.into(),
+                        selectable.parent_object_entity_id,
+                    )
+                })
+                .chain(self.server_object_selectables.iter().map(|selectable| {
+                    (
+                        selectable.name.item.into(),
+                        selectable.parent_object_entity_id,
+                    )
+                }))
+                .chain(
+                    self.client_scalar_selectables.iter().map(|selectable| {
+                        (selectable.name.into(), selectable.parent_object_entity_id)
+                    }),
+                )
+                .chain(self.client_object_selectables.iter().map(|selectable| {
+                    (selectable.name.into(), selectable.parent_object_entity_id)
+                }));
+        for (selectable_name, parent_object_entity_id) in parent_object_entity_ids {
+            if !object_exists(parent_object_entity_id) {
+                return Err(ConsistencyError::DanglingParentObjectEntityId {
+                    selectable_name,
+                    parent_object_entity_id,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum ConsistencyError {
+    #[error(
+        "Object {object_entity_id:?} has available selectables, but does not exist. \
+        This is indicative of a bug in Isograph."
+    )]
+    DanglingObjectEntityId {
+        object_entity_id: ServerObjectEntityId,
+    },
+
+    #[error(
+        "The selectable `{selectable_name}` on object {parent_object_entity_id:?} refers to \
+        {selectable_id:?}, which does not exist. This is indicative of a bug in Isograph."
+    )]
+    DanglingSelectableId {
+        parent_object_entity_id: ServerObjectEntityId,
+        selectable_name: SelectableName,
+        selectable_id: DefinitionLocation<ServerSelectableId, ClientSelectableId>,
+    },
+
+    #[error(
+        "The id field of object {parent_object_entity_id:?} is {id_field:?}, which does \
+        not exist. This is indicative of a bug in Isograph."
+    )]
+    DanglingIdField {
+        parent_object_entity_id: ServerObjectEntityId,
+        id_field: ServerStrongIdFieldId,
+    },
+
+    #[error(
+        "The parent of the selectable `{selectable_name}` is {parent_object_entity_id:?}, \
+        which does not exist. This is indicative of a bug in Isograph."
+    )]
+    DanglingParentObjectEntityId {
+        selectable_name: SelectableName,
+        parent_object_entity_id: ServerObjectEntityId,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use intern::string_key::Intern;
+    use isograph_lang_types::{DefinitionLocation, SelectionType};
+
+    use crate::test_schema::{insert_field, insert_object, TestSchema};
+
+    use super::ConsistencyError;
+
+    #[test]
+    fn corrupted_selectable_ids_are_inconsistent() {
+        let mut schema = TestSchema::new();
+        let query = insert_object(&mut schema, "Query");
+        let user = insert_object(&mut schema, "User");
+        insert_field(&mut schema, query, "viewer", "User", vec![]);
+        insert_field(&mut schema, user, "id", "ID!", vec![]);
+        insert_field(&mut schema, user, "name", "String", vec![]);
+        assert_eq!(schema.assert_consistent(), Ok(()));
+
+        let dangling_id = DefinitionLocation::Server(SelectionType::Scalar(
+            schema.server_scalar_selectables.len().into(),
+        ));
+        *schema
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get_mut(&user)
+            .expect("Expected User to have selectables")
+            .selectables
+            .get_mut(&"name".intern().into())
+            .expect("Expected User to have a name field") = dangling_id;
+
+        assert_eq!(
+            schema.assert_consistent(),
+            Err(ConsistencyError::DanglingSelectableId {
+                parent_object_entity_id: user,
+                selectable_name: "name".intern().into(),
+                selectable_id: dangling_id,
+            })
+        );
+    }
+}
@@ -0,0 +1,549 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use common_lang_types::{
+    JavascriptName, SelectableName, UnvalidatedTypeName, VariableName, WithLocation,
+};
+use intern::string_key::Intern;
+use isograph_lang_types::{SelectionType, ServerEntityId, VariableDefinition};
+
+use crate::{isograph_schema::type_annotation_sdl, NetworkProtocol, Schema};
+
+/// A single difference between two schemas. Removals can break existing clients;
+/// whether additions and changes can depends on what was added or changed (see
+/// is_breaking).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    TypeAdded {
+        type_name: UnvalidatedTypeName,
+    },
+    TypeRemoved {
+        type_name: UnvalidatedTypeName,
+    },
+    FieldAdded {
+        parent_type: UnvalidatedTypeName,
+        field_name: SelectableName,
+    },
+    FieldRemoved {
+        parent_type: UnvalidatedTypeName,
+        field_name: SelectableName,
+    },
+    /// The types are formatted as SDL, e.g. `[User!]`.
+    FieldTypeChanged {
+        parent_type: UnvalidatedTypeName,
+        field_name: SelectableName,
+        old_type: String,
+        new_type: String,
+    },
+    ArgumentAdded {
+        parent_type: UnvalidatedTypeName,
+        field_name: SelectableName,
+        argument_name: VariableName,
+        argument_type: String,
+        has_default_value: bool,
+    },
+    ArgumentRemoved {
+        parent_type: UnvalidatedTypeName,
+        field_name: SelectableName,
+        argument_name: VariableName,
+    },
+    ArgumentTypeChanged {
+        parent_type: UnvalidatedTypeName,
+        field_name: SelectableName,
+        argument_name: VariableName,
+        old_type: String,
+        new_type: String,
+    },
+    ScalarMappingChanged {
+        scalar_name: UnvalidatedTypeName,
+        old_javascript_name: JavascriptName,
+        new_javascript_name: JavascriptName,
+    },
+}
+
+impl SchemaChange {
+    /// Making a field non-null, or an argument nullable, is not breaking, as every
+    /// existing selection or argument remains valid. Adding an argument is only
+    /// breaking if the argument is required, i.e. non-null without a default value.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            SchemaChange::TypeAdded { .. } | SchemaChange::FieldAdded { .. } => false,
+            SchemaChange::TypeRemoved { .. }
+            | SchemaChange::FieldRemoved { .. }
+            | SchemaChange::ArgumentRemoved { .. }
+            | SchemaChange::ScalarMappingChanged { .. } => true,
+            SchemaChange::FieldTypeChanged {
+                old_type, new_type, ..
+            } => !only_adds_non_null(old_type, new_type),
+            SchemaChange::ArgumentAdded {
+                argument_type,
+                has_default_value,
+                ..
+            } => argument_type.ends_with('!') && !has_default_value,
+            SchemaChange::ArgumentTypeChanged {
+                old_type, new_type, ..
+            } => !only_adds_non_null(new_type, old_type),
+        }
+    }
+}
+
+/// Whether to_type is from_type with zero or more additional `!`s, e.g. `[User]` to
+/// `[User!]!`. The types are formatted as SDL.
+fn only_adds_non_null(from_type: &str, to_type: &str) -> bool {
+    // The type without `!`s, and the positions (in it) that are followed by a `!`
+    let non_null_positions = |type_: &str| {
+        let mut base = String::new();
+        let mut positions = BTreeSet::new();
+        for c in type_.chars() {
+            if c == '!' {
+                positions.insert(base.len());
+            } else {
+                base.push(c);
+            }
+        }
+        (base, positions)
+    };
+    let (from_base, from_positions) = non_null_positions(from_type);
+    let (to_base, to_positions) = non_null_positions(to_type);
+    from_base == to_base && from_positions.is_subset(&to_positions)
+}
+
+/// The changes between two schemas, sorted by type name and then by field name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &SchemaChange> + '_ {
+        self.changes.iter().filter(|change| change.is_breaking())
+    }
+}
+
+#[derive(Debug, Default)]
+struct TypeSummary {
+    javascript_name: Option<JavascriptName>,
+    fields: BTreeMap<String, FieldSummary>,
+}
+
+#[derive(Debug)]
+struct FieldSummary {
+    arguments: BTreeMap<VariableName, ArgumentSummary>,
+    /// Formatted as SDL
+    type_: String,
+}
+
+#[derive(Debug)]
+struct ArgumentSummary {
+    /// Formatted as SDL
+    type_: String,
+    has_default_value: bool,
+}
+
+impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
+    /// Compares the server types and fields of this (old) schema with other (the
+    /// new schema). Like content_hash, this ignores ids and locations, so the diff
+    /// does not depend on the order in which either schema was processed.
+    pub fn diff(&self, other: &Schema<TNetworkProtocol>) -> SchemaDiff {
+        let old_types = self.type_summaries();
+        let mut new_types = other.type_summaries();
+
+        let mut changes = vec![];
+        for (type_name, old_type) in old_types {
+            let Some(new_type) = new_types.remove(&type_name) else {
+                changes.push((
+                    type_name.clone(),
+                    SchemaChange::TypeRemoved {
+                        type_name: type_name.as_str().intern().into(),
+                    },
+                ));
+                continue;
+            };
+
+            if let (Some(old_javascript_name), Some(new_javascript_name)) =
+                (old_type.javascript_name, new_type.javascript_name)
+            {
+                if old_javascript_name != new_javascript_name {
+                    changes.push((
+                        type_name.clone(),
+                        SchemaChange::ScalarMappingChanged {
+                            scalar_name: type_name.as_str().intern().into(),
+                            old_javascript_name,
+                            new_javascript_name,
+                        },
+                    ));
+                }
+            }
+
+            let parent_type: UnvalidatedTypeName = type_name.as_str().intern().into();
+            let mut new_fields = new_type.fields;
+            let mut field_changes = vec![];
+            for (field_name, old_field) in old_type.fields {
+                let Some(new_field) = new_fields.remove(&field_name) else {
+                    field_changes.push((
+                        field_name.clone(),
+                        SchemaChange::FieldRemoved {
+                            parent_type,
+                            field_name: field_name.as_str().intern().into(),
+                        },
+                    ));
+                    continue;
+                };
+                let selectable_name: SelectableName = field_name.as_str().intern().into();
+                if old_field.type_ != new_field.type_ {
+                    field_changes.push((
+                        field_name.clone(),
+                        SchemaChange::FieldTypeChanged {
+                            parent_type,
+                            field_name: selectable_name,
+                            old_type: old_field.type_,
+                            new_type: new_field.type_,
+                        },
+                    ));
+                }
+                let mut new_arguments = new_field.arguments;
+                for (argument_name, old_argument) in old_field.arguments {
+                    match new_arguments.remove(&argument_name) {
+                        None => field_changes.push((
+                            field_name.clone(),
+                            SchemaChange::ArgumentRemoved {
+                                parent_type,
+                                field_name: selectable_name,
+                                argument_name,
+                            },
+                        )),
+                        Some(new_argument) if new_argument.type_ != old_argument.type_ => {
+                            field_changes.push((
+                                field_name.clone(),
+                                SchemaChange::ArgumentTypeChanged {
+                                    parent_type,
+                                    field_name: selectable_name,
+                                    argument_name,
+                                    old_type: old_argument.type_,
+                                    new_type: new_argument.type_,
+                                },
+                            ))
+                        }
+                        Some(_) => {}
+                    }
+                }
+                for (argument_name, new_argument) in new_arguments {
+                    field_changes.push((
+                        field_name.clone(),
+                        SchemaChange::ArgumentAdded {
+                            parent_type,
+                            field_name: selectable_name,
+                            argument_name,
+                            argument_type: new_argument.type_,
+                            has_default_value: new_argument.has_default_value,
+                        },
+                    ));
+                }
+            }
+            for field_name in new_fields.into_keys() {
+                field_changes.push((
+                    field_name.clone(),
+                    SchemaChange::FieldAdded {
+                        parent_type,
+                        field_name: field_name.as_str().intern().into(),
+                    },
+                ));
+            }
+            field_changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+            changes.extend(
+                field_changes
+                    .into_iter()
+                    .map(|(_, change)| (type_name.clone(), change)),
+            );
+        }
+        for type_name in new_types.into_keys() {
+            changes.push((
+                type_name.clone(),
+                SchemaChange::TypeAdded {
+                    type_name: type_name.as_str().intern().into(),
+                },
+            ));
+        }
+        // The sort is stable, so the changes within a type stay sorted by field name.
+        changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        SchemaDiff {
+            changes: changes.into_iter().map(|(_, change)| change).collect(),
+        }
+    }
+
+    fn type_summaries(&self) -> BTreeMap<String, TypeSummary> {
+        let entity_name = |server_entity_id: ServerEntityId| match server_entity_id {
+            SelectionType::Scalar(scalar_entity_id) => self
+                .server_entity_data
+                .server_scalar_entity(scalar_entity_id)
+                .name
+                .item
+                .to_string(),
+            SelectionType::Object(object_entity_id) => self
+                .server_entity_data
+                .server_object_entity(object_entity_id)
+                .name
+                .to_string(),
+        };
+        let argument_summaries =
+            |arguments: &[WithLocation<VariableDefinition<ServerEntityId>>]| {
+                arguments
+                    .iter()
+                    .map(|argument| {
+                        (
+                            argument.item.name.item,
+                            ArgumentSummary {
+                                type_: argument.item.type_.clone().map(entity_name).to_string(),
+                                has_default_value: argument.item.default_value.is_some(),
+                            },
+                        )
+                    })
+                    .collect::<BTreeMap<_, _>>()
+            };
+
+        let mut type_summaries = BTreeMap::<_, TypeSummary>::new();
+        for scalar in self.server_entity_data.server_scalars.iter() {
+            type_summaries
+                .entry(scalar.name.item.to_string())
+                .or_default()
+                .javascript_name = Some(scalar.javascript_name);
+        }
+        for object in self.server_entity_data.server_objects.iter() {
+            type_summaries.entry(object.name.to_string()).or_default();
+        }
+
+        let mut insert_field = |parent_type: String,
+                                field_name: String,
+                                arguments: BTreeMap<VariableName, ArgumentSummary>,
+                                type_: String| {
+            type_summaries
+                .entry(parent_type)
+                .or_default()
+                .fields
+                .insert(field_name, FieldSummary { arguments, type_ });
+        };
+        for scalar_selectable in self.server_scalar_selectables.iter() {
+            insert_field(
+                entity_name(SelectionType::Object(
+                    scalar_selectable.parent_object_entity_id,
+                )),
+                scalar_selectable.name.item.to_string(),
+                argument_summaries(&scalar_selectable.arguments),
+                type_annotation_sdl(
+                    &scalar_selectable
+                        .target_scalar_entity
+                        .clone()
+                        .map(&mut |id| entity_name(SelectionType::Scalar(id))),
+                ),
+            );
+        }
+        for object_selectable in self.server_object_selectables.iter() {
+            insert_field(
+                entity_name(SelectionType::Object(
+                    object_selectable.parent_object_entity_id,
+                )),
+                object_selectable.name.item.to_string(),
+                argument_summaries(&object_selectable.arguments),
+                type_annotation_sdl(
+                    &object_selectable
+                        .target_object_entity
+                        .clone()
+                        .map(&mut |id| entity_name(SelectionType::Object(id))),
+                ),
+            );
+        }
+
+        type_summaries
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common_lang_types::{Location, WithLocation};
+    use intern::string_key::Intern;
+    use isograph_lang_types::ConstantValue;
+
+    use crate::test_schema::{argument, insert_field, insert_object, TestSchema};
+
+    use super::SchemaChange;
+
+    fn user_schema(user_fields: &[&str]) -> TestSchema {
+        let mut schema = TestSchema::new();
+        let query = insert_object(&mut schema, "Query");
+        let user = insert_object(&mut schema, "User");
+        insert_field(&mut schema, query, "viewer", "User", vec![]);
+        for field_name in user_fields {
+            insert_field(&mut schema, user, field_name, "String", vec![]);
+        }
+        schema
+    }
+
+    #[test]
+    fn diff_reports_removed_fields() {
+        let before = user_schema(&["name", "email"]);
+        let after = user_schema(&["name"]);
+
+        assert!(before.diff(&before).is_empty());
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.changes,
+            [SchemaChange::FieldRemoved {
+                parent_type: "User".intern().into(),
+                field_name: "email".intern().into(),
+            }]
+        );
+        assert_eq!(diff.breaking_changes().count(), 1);
+
+        let diff = after.diff(&before);
+        assert_eq!(
+            diff.changes,
+            [SchemaChange::FieldAdded {
+                parent_type: "User".intern().into(),
+                field_name: "email".intern().into(),
+            }]
+        );
+        assert_eq!(diff.breaking_changes().count(), 0);
+    }
+
+    #[test]
+    fn diff_classifies_field_and_argument_changes() {
+        // type Query { pets(first: Int!, after: String, filter: String): [Pet] }
+        // type Pet { name: String, age: Int! }
+        let mut before = TestSchema::new();
+        let query = insert_object(&mut before, "Query");
+        let pet = insert_object(&mut before, "Pet");
+        let pets_arguments = vec![
+            argument(&before, "first", "Int!"),
+            argument(&before, "after", "String"),
+            argument(&before, "filter", "String"),
+        ];
+        insert_field(&mut before, query, "pets", "[Pet]", pets_arguments);
+        insert_field(&mut before, pet, "name", "String", vec![]);
+        insert_field(&mut before, pet, "age", "Int!", vec![]);
+
+        // type Query {
+        //     pets(first: Int, after: String!, limit: Int, offset: Int! = 0, sort: String!): [Pet!]
+        // }
+        // type Pet { name: String!, age: Int }
+        let mut after = TestSchema::new();
+        let query = insert_object(&mut after, "Query");
+        let pet = insert_object(&mut after, "Pet");
+        let mut offset = argument(&after, "offset", "Int!");
+        offset.item.default_value = Some(WithLocation::new(
+            ConstantValue::Integer(0),
+            Location::generated(),
+        ));
+        let pets_arguments = vec![
+            argument(&after, "first", "Int"),
+            argument(&after, "after", "String!"),
+            argument(&after, "limit", "Int"),
+            offset,
+            argument(&after, "sort", "String!"),
+        ];
+        insert_field(&mut after, query, "pets", "[Pet!]", pets_arguments);
+        insert_field(&mut after, pet, "name", "String!", vec![]);
+        insert_field(&mut after, pet, "age", "Int", vec![]);
+
+        let diff = before.diff(&after);
+        let changes = diff
+            .changes
+            .iter()
+            .map(|change| (change.clone(), change.is_breaking()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                (
+                    SchemaChange::FieldTypeChanged {
+                        parent_type: "Pet".intern().into(),
+                        field_name: "age".intern().into(),
+                        old_type: "Int!".to_string(),
+                        new_type: "Int".to_string(),
+                    },
+                    true
+                ),
+                (
+                    SchemaChange::FieldTypeChanged {
+                        parent_type: "Pet".intern().into(),
+                        field_name: "name".intern().into(),
+                        old_type: "String".to_string(),
+                        new_type: "String!".to_string(),
+                    },
+                    false
+                ),
+                (
+                    SchemaChange::FieldTypeChanged {
+                        parent_type: "Query".intern().into(),
+                        field_name: "pets".intern().into(),
+                        old_type: "[Pet]".to_string(),
+                        new_type: "[Pet!]".to_string(),
+                    },
+                    false
+                ),
+                (
+                    SchemaChange::ArgumentTypeChanged {
+                        parent_type: "Query".intern().into(),
+                        field_name: "pets".intern().into(),
+                        argument_name: "after".intern().into(),
+                        old_type: "String".to_string(),
+                        new_type: "String!".to_string(),
+                    },
+                    true
+                ),
+                (
+                    SchemaChange::ArgumentRemoved {
+                        parent_type: "Query".intern().into(),
+                        field_name: "pets".intern().into(),
+                        argument_name: "filter".intern().into(),
+                    },
+                    true
+                ),
+                (
+                    SchemaChange::ArgumentTypeChanged {
+                        parent_type: "Query".intern().into(),
+                        field_name: "pets".intern().into(),
+                        argument_name: "first".intern().into(),
+                        old_type: "Int!".to_string(),
+                        new_type: "Int".to_string(),
+                    },
+                    false
+                ),
+                (
+                    SchemaChange::ArgumentAdded {
+                        parent_type: "Query".intern().into(),
+                        field_name: "pets".intern().into(),
+                        argument_name: "limit".intern().into(),
+                        argument_type: "Int".to_string(),
+                        has_default_value: false,
+                    },
+                    false
+                ),
+                (
+                    SchemaChange::ArgumentAdded {
+                        parent_type: "Query".intern().into(),
+                        field_name: "pets".intern().into(),
+                        argument_name: "offset".intern().into(),
+                        argument_type: "Int!".to_string(),
+                        has_default_value: true,
+                    },
+                    false
+                ),
+                (
+                    SchemaChange::ArgumentAdded {
+                        parent_type: "Query".intern().into(),
+                        field_name: "pets".intern().into(),
+                        argument_name: "sort".intern().into(),
+                        argument_type: "String!".to_string(),
+                        has_default_value: false,
+                    },
+                    true
+                ),
+            ]
+        );
+    }
+}
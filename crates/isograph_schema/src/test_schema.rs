@@ -0,0 +1,162 @@
+//! Helpers for building schemas in unit tests. Schemas are usually created from
+//! SDL by a network protocol crate, which depends on this crate, so here the
+//! types and fields are inserted directly.
+
+use std::{error::Error, marker::PhantomData};
+
+use common_lang_types::{
+    Location, QueryOperationName, QueryText, Span, UnvalidatedTypeName, WithLocation, WithSpan,
+};
+use graphql_lang_types::{
+    GraphQLListTypeAnnotation, GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation,
+    GraphQLTypeAnnotation,
+};
+use intern::string_key::Intern;
+use isograph_config::CompilerConfigOptions;
+use isograph_lang_types::{
+    SelectionType, ServerEntityId, ServerObjectEntityId, TypeAnnotation, VariableDefinition,
+};
+use pico::Database;
+
+use crate::{
+    MergedSelectionMap, NetworkProtocol, ProcessTypeSystemDocumentOutcome, RootOperationName,
+    Schema, SchemaServerObjectSelectableVariant, ServerFieldKind, ServerObjectEntity,
+    ServerObjectSelectable, ServerScalarSelectable, ValidatedVariableDefinition,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash, Default)]
+pub(crate) struct TestNetworkProtocol {}
+
+impl NetworkProtocol for TestNetworkProtocol {
+    type Sources = ();
+
+    type SchemaObjectAssociatedData = ();
+
+    fn parse_and_process_type_system_documents(
+        _db: &Database,
+        _sources: &Self::Sources,
+        _options: &CompilerConfigOptions,
+    ) -> Result<ProcessTypeSystemDocumentOutcome<Self>, Box<dyn Error>> {
+        Ok(ProcessTypeSystemDocumentOutcome {
+            scalars: vec![],
+            objects: vec![],
+        })
+    }
+
+    fn generate_query_text<'a>(
+        query_name: QueryOperationName,
+        _schema: &Schema<Self>,
+        _selection_map: &MergedSelectionMap,
+        _query_variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
+        _root_operation_name: &RootOperationName,
+    ) -> QueryText {
+        QueryText(format!("query {query_name}"))
+    }
+}
+
+pub(crate) type TestSchema = Schema<TestNetworkProtocol>;
+
+pub(crate) fn insert_object(schema: &mut TestSchema, name: &str) -> ServerObjectEntityId {
+    schema
+        .server_entity_data
+        .insert_server_object_entity(
+            ServerObjectEntity {
+                description: None,
+                name: name.intern().into(),
+                name_location: Location::generated(),
+                concrete_type: Some(name.intern().into()),
+                output_associated_data: (),
+            },
+            Location::generated(),
+        )
+        .expect("Expected object to be inserted")
+}
+
+/// Inserts a server field whose type is written as SDL, e.g. `[Pet!]`. The named
+/// type must already be in the schema.
+pub(crate) fn insert_field(
+    schema: &mut TestSchema,
+    parent_object_entity_id: ServerObjectEntityId,
+    name: &str,
+    type_: &str,
+    arguments: Vec<WithLocation<VariableDefinition<ServerEntityId>>>,
+) {
+    let type_ = type_annotation(schema, type_);
+    match *type_.inner() {
+        SelectionType::Scalar(_) => schema
+            .insert_server_scalar_selectable(
+                ServerScalarSelectable {
+                    description: None,
+                    name: WithLocation::new(name.intern().into(), Location::generated()),
+                    target_scalar_entity: TypeAnnotation::from_graphql_type_annotation(
+                        type_.map(|id| *id.as_scalar().expect("Expected a scalar")),
+                    ),
+                    kind: ServerFieldKind::ActualField,
+                    parent_object_entity_id,
+                    arguments,
+                    phantom_data: PhantomData,
+                },
+                &CompilerConfigOptions::default(),
+                None,
+            )
+            .expect("Expected scalar selectable to be inserted"),
+        SelectionType::Object(_) => schema
+            .insert_server_object_selectable(ServerObjectSelectable {
+                description: None,
+                name: WithLocation::new(name.intern().into(), Location::generated()),
+                target_object_entity: TypeAnnotation::from_graphql_type_annotation(
+                    type_.map(|id| *id.as_object().expect("Expected an object")),
+                ),
+                object_selectable_variant: SchemaServerObjectSelectableVariant::LinkedField,
+                parent_object_entity_id,
+                arguments,
+                phantom_data: PhantomData,
+            })
+            .expect("Expected object selectable to be inserted"),
+    }
+}
+
+/// An argument without a description, default value or deprecation reason.
+pub(crate) fn argument(
+    schema: &TestSchema,
+    name: &str,
+    type_: &str,
+) -> WithLocation<VariableDefinition<ServerEntityId>> {
+    WithLocation::new(
+        VariableDefinition {
+            description: None,
+            name: WithLocation::new(name.intern().into(), Location::generated()),
+            type_: type_annotation(schema, type_),
+            default_value: None,
+            deprecation_reason: None,
+        },
+        Location::generated(),
+    )
+}
+
+fn type_annotation(schema: &TestSchema, type_: &str) -> GraphQLTypeAnnotation<ServerEntityId> {
+    if let Some(nullable_type) = type_.strip_suffix('!') {
+        GraphQLTypeAnnotation::NonNull(Box::new(match type_annotation(schema, nullable_type) {
+            GraphQLTypeAnnotation::Named(named) => GraphQLNonNullTypeAnnotation::Named(named),
+            GraphQLTypeAnnotation::List(list) => GraphQLNonNullTypeAnnotation::List(*list),
+            GraphQLTypeAnnotation::NonNull(_) => panic!("Unexpected `!!` in {type_}"),
+        }))
+    } else if let Some(item_type) = type_
+        .strip_prefix('[')
+        .and_then(|type_| type_.strip_suffix(']'))
+    {
+        GraphQLTypeAnnotation::List(Box::new(GraphQLListTypeAnnotation(type_annotation(
+            schema, item_type,
+        ))))
+    } else {
+        let type_name: UnvalidatedTypeName = type_.intern().into();
+        GraphQLTypeAnnotation::Named(GraphQLNamedTypeAnnotation(WithSpan::new(
+            *schema
+                .server_entity_data
+                .defined_entities
+                .get(&type_name)
+                .unwrap_or_else(|| panic!("Expected {type_} to be defined")),
+            Span::todo_generated(),
+        )))
+    }
+}
@@ -11,14 +11,18 @@ use graphql_lang_types::{GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionO
 fn unwrap_directive(
     extension_or_definition: GraphQLTypeSystemExtensionOrDefinition,
 ) -> Result<Vec<GraphQLDirective<GraphQLConstantValue>>, Box<dyn Error>> {
-    if let GraphQLTypeSystemExtensionOrDefinition::Extension(extension) = extension_or_definition {
-        let GraphQLTypeSystemExtension::ObjectTypeExtension(object_type_extension) = extension;
+    if let GraphQLTypeSystemExtensionOrDefinition::Extension(
+        GraphQLTypeSystemExtension::ObjectTypeExtension(object_type_extension),
+    ) = extension_or_definition
+    {
         return Ok(object_type_extension.directives.clone());
     }
     Err("unexpected structure of directive".into())
 }
 
-fn parse_mutation(source: &str) -> Result<Vec<ExposeFieldDirective>, Box<dyn Error>> {
+fn parse_directives(
+    source: &str,
+) -> Result<Vec<GraphQLDirective<GraphQLConstantValue>>, Box<dyn Error>> {
     let text_source = TextSource {
         relative_path_to_source_file: "dummy".intern().into(),
         span: None,
@@ -31,8 +35,11 @@ fn parse_mutation(source: &str) -> Result<Vec<ExposeFieldDirective>, Box<dyn Err
         .into_iter()
         .map(|dir| unwrap_directive(dir.item))
         .collect::<Result<Vec<_>, _>>()?;
-    let directives: Vec<GraphQLDirective<GraphQLConstantValue>> =
-        directives.into_iter().flatten().collect();
+    Ok(directives.into_iter().flatten().collect())
+}
+
+fn parse_mutation(source: &str) -> Result<Vec<ExposeFieldDirective>, Box<dyn Error>> {
+    let directives = parse_directives(source)?;
     let expose_field_directives: Result<Vec<ExposeFieldDirective>, _> = directives
         .into_iter()
         .map(|directive| from_graphql_directive::<ExposeFieldDirective>(&directive))
@@ -153,3 +160,29 @@ fn test_mutation_extension_missing_nestedfield_parsing_failure() -> Result<(), B
     match_failure_message(expose_field_directives, "missing field `from`");
     Ok(())
 }
+
+#[test]
+fn test_mutation_extension_missing_field_argument_lists_arguments() -> Result<(), Box<dyn Error>> {
+    let directives = parse_directives(include_str!(
+        "fixtures/directives/mutation_extension_missing_field_argument.graphql"
+    ))?;
+    let error = ExposeFieldDirective::validate_arguments(&directives[0])
+        .expect_err("Expected validation to fail");
+    assert_eq!(
+        error.to_string(),
+        "The @exposeField directive is missing the required argument(s) `field`. \
+        Found argument(s): `as`, `fieldMap`."
+    );
+    Ok(())
+}
+
+#[test]
+fn test_mutation_extension_valid_arguments() -> Result<(), Box<dyn Error>> {
+    let directives = parse_directives(include_str!(
+        "fixtures/directives/mutation_extension_valid_as.graphql"
+    ))?;
+    for directive in directives.iter() {
+        ExposeFieldDirective::validate_arguments(directive)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use intern::string_key::Intern;
+use isograph_lang_types::{ArgumentKeyAndValue, NonConstantValue};
+use isograph_schema::normalization_alias;
+
+fn argument(key: &str, value: NonConstantValue) -> ArgumentKeyAndValue {
+    ArgumentKeyAndValue {
+        key: key.intern().into(),
+        value,
+    }
+}
+
+#[test]
+fn fields_without_arguments_have_no_alias() {
+    assert_eq!(normalization_alias("id".intern().into(), &[]), None);
+}
+
+#[test]
+fn fields_with_arguments_have_stable_aliases() {
+    let arguments = [
+        argument("id", NonConstantValue::Variable("id".intern().into())),
+        argument("first", NonConstantValue::Integer(10)),
+    ];
+
+    let alias = normalization_alias("node".intern().into(), &arguments);
+    assert_eq!(alias.as_deref(), Some("node____id___v_id____first___l_10"));
+    assert_eq!(
+        normalization_alias("node".intern().into(), &arguments),
+        alias
+    );
+}
+
+#[test]
+fn fields_with_different_arguments_have_different_aliases() {
+    let first = |count| [argument("first", NonConstantValue::Integer(count))];
+
+    assert_ne!(
+        normalization_alias("friends".intern().into(), &first(10)),
+        normalization_alias("friends".intern().into(), &first(20)),
+    );
+}
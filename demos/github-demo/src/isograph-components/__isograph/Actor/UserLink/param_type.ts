@@ -8,7 +8,7 @@ The username of the actor.
     /**
 A client pointer for the User type.
     */
-    readonly asUser: ({
+    readonly asUser: {
       /**
 The Node ID of the User object
       */
@@ -16,8 +16,8 @@ The Node ID of the User object
       /**
 The user's Twitter username.
       */
-      readonly twitterUsername: (string | null),
-    } | null),
+      readonly twitterUsername: string | null,
+    } | null,
   },
   readonly parameters: Record<PropertyKey, never>,
 };
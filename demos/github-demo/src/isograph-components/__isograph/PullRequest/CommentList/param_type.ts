@@ -10,11 +10,11 @@ A list of comments associated with the pull request.
       /**
 A list of edges.
       */
-      readonly edges: (ReadonlyArray<({
+      readonly edges: ReadonlyArray<({
         /**
 The item at the end of the edge.
         */
-        readonly node: ({
+        readonly node: {
           /**
 The Node ID of the IssueComment object
           */
@@ -27,14 +27,14 @@ The body rendered to text.
           /**
 The actor who authored the comment.
           */
-          readonly author: ({
+          readonly author: {
             /**
 The username of the actor.
             */
             readonly login: string,
-          } | null),
-        } | null),
-      } | null)> | null),
+          } | null,
+        } | null,
+      } | null)> | null,
     },
   },
   readonly parameters: PullRequest__CommentList__parameters,
@@ -7,11 +7,11 @@ export type PullRequestConnection__PullRequestTable__param = {
     /**
 A list of edges.
     */
-    readonly edges: (ReadonlyArray<({
+    readonly edges: ReadonlyArray<({
       /**
 The item at the end of the edge.
       */
-      readonly node: ({
+      readonly node: {
         /**
 The Node ID of the PullRequest object
         */
@@ -28,13 +28,13 @@ Identifies the pull request title.
         /**
 The actor who authored the comment.
         */
-        readonly author: ({
+        readonly author: {
           readonly UserLink: Actor__UserLink__output_type,
           /**
 The username of the actor.
           */
           readonly login: string,
-        } | null),
+        } | null,
         /**
 `true` if the pull request is closed
         */
@@ -42,10 +42,10 @@ The username of the actor.
         /**
 Returns a count of how many comments this pull request has received.
         */
-        readonly totalCommentsCount: (number | null),
+        readonly totalCommentsCount: number | null,
         readonly createdAtFormatted: PullRequest__createdAtFormatted__output_type,
-      } | null),
-    } | null)> | null),
+      } | null,
+    } | null)> | null,
   },
   readonly parameters: Record<PropertyKey, never>,
 };
@@ -9,7 +9,7 @@ The currently authenticated user.
       /**
 The user's public profile name.
       */
-      readonly name: (string | null),
+      readonly name: string | null,
       readonly Avatar: User__Avatar__output_type,
     },
   },
@@ -14,7 +14,7 @@ The username used to login.
       /**
 The user's public profile name.
       */
-      readonly name: (string | null),
+      readonly name: string | null,
       readonly RepositoryList: User__RepositoryList__output_type,
       /**
 A refetch field for the User type.
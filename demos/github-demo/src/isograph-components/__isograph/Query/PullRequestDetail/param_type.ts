@@ -6,11 +6,11 @@ export type Query__PullRequestDetail__param = {
     /**
 Lookup a given repository by the owner and repository name.
     */
-    readonly repository: ({
+    readonly repository: {
       /**
 Returns a single pull request from the current repository by number.
       */
-      readonly pullRequest: ({
+      readonly pullRequest: {
         /**
 Identifies the pull request title.
         */
@@ -20,8 +20,8 @@ The body rendered to HTML.
         */
         readonly bodyHTML: string,
         readonly CommentList: PullRequest__CommentList__output_type,
-      } | null),
-    } | null),
+      } | null,
+    } | null,
   },
   readonly parameters: Query__PullRequestDetail__parameters,
 };
@@ -8,7 +8,7 @@ export type Query__RepositoryDetail__param = {
     /**
 Lookup a given repository by the owner and repository name.
     */
-    readonly repository: ({
+    readonly repository: {
       readonly IsStarred: Repository__IsStarred__output_type,
       /**
 The repository's name with owner.
@@ -17,20 +17,20 @@ The repository's name with owner.
       /**
 The repository parent, if this is a fork.
       */
-      readonly parent: ({
+      readonly parent: {
         readonly RepositoryLink: Repository__RepositoryLink__output_type,
         /**
 The repository's name with owner.
         */
         readonly nameWithOwner: string,
-      } | null),
+      } | null,
       /**
 A list of pull requests that have been opened in the repository.
       */
       readonly pullRequests: {
         readonly PullRequestTable: PullRequestConnection__PullRequestTable__output_type,
       },
-    } | null),
+    } | null,
   },
   readonly parameters: Query__RepositoryDetail__parameters,
 };
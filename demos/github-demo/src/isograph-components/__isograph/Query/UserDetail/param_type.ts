@@ -6,13 +6,13 @@ export type Query__UserDetail__param = {
     /**
 Lookup a user by login.
     */
-    readonly user: ({
+    readonly user: {
       /**
 The user's public profile name.
       */
-      readonly name: (string | null),
+      readonly name: string | null,
       readonly RepositoryList: User__RepositoryList__output_type,
-    } | null),
+    } | null,
   },
   readonly parameters: Query__UserDetail__parameters,
 };
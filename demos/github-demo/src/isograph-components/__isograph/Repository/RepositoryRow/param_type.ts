@@ -14,7 +14,7 @@ The repository's name with owner.
     /**
 The description of the repository.
     */
-    readonly description: (string | null),
+    readonly description: string | null,
     /**
 Returns how many forks there are of this repository in the whole network.
     */
@@ -4,7 +4,7 @@ export type User__Avatar__param = {
     /**
 The user's public profile name.
     */
-    readonly name: (string | null),
+    readonly name: string | null,
     /**
 A URL pointing to the user's public avatar.
     */
@@ -18,23 +18,23 @@ When paginating forwards, are there more items?
         /**
 When paginating forwards, the cursor to continue.
         */
-        readonly endCursor: (string | null),
+        readonly endCursor: string | null,
       },
       /**
 A list of edges.
       */
-      readonly edges: (ReadonlyArray<({
+      readonly edges: ReadonlyArray<({
         /**
 The item at the end of the edge.
         */
-        readonly node: ({
+        readonly node: {
           readonly RepositoryRow: Repository__RepositoryRow__output_type,
           /**
 The Node ID of the Repository object
           */
           readonly id: string,
-        } | null),
-      } | null)> | null),
+        } | null,
+      } | null)> | null,
     },
   },
   readonly parameters: User__RepositoryConnection__parameters,
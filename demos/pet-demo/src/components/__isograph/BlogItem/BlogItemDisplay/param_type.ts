@@ -12,9 +12,9 @@ export type BlogItem__BlogItemDisplay__param = {
       BlogItem__BlogItemMoreDetail__param,
       BlogItem__BlogItemMoreDetail__output_type
     >,
-    readonly image: ({
+    readonly image: {
       readonly ImageDisplayWrapper: Image__ImageDisplayWrapper__output_type,
-    } | null),
+    } | null,
   },
   readonly parameters: Record<PropertyKey, never>,
 };
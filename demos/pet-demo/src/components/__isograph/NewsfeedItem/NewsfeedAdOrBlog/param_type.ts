@@ -6,15 +6,15 @@ export type NewsfeedItem__NewsfeedAdOrBlog__param = {
     /**
 A client pointer for the AdItem type.
     */
-    readonly asAdItem: ({
+    readonly asAdItem: {
       readonly AdItemDisplayWrapper: AdItem__AdItemDisplayWrapper__output_type,
-    } | null),
+    } | null,
     /**
 A client pointer for the BlogItem type.
     */
-    readonly asBlogItem: ({
+    readonly asBlogItem: {
       readonly BlogItemDisplay: BlogItem__BlogItemDisplay__output_type,
-    } | null),
+    } | null,
   },
   readonly parameters: Record<PropertyKey, never>,
 };
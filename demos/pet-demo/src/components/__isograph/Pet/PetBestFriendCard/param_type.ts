@@ -9,14 +9,14 @@ A component to test behavior with respect to mutations.
 You can update the best friend and the tagline.
     */
     readonly PetUpdater: Pet__PetUpdater__output_type,
-    readonly best_friend_relationship: ({
-      readonly picture_together: (string | null),
+    readonly best_friend_relationship: {
+      readonly picture_together: string | null,
       readonly best_friend: {
         readonly id: string,
         readonly name: string,
         readonly picture: string,
       },
-    } | null),
+    } | null,
   },
   readonly parameters: Record<PropertyKey, never>,
 };
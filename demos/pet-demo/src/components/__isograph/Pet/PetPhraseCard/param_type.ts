@@ -2,7 +2,7 @@
 export type Pet__PetPhraseCard__param = {
   readonly data: {
     readonly id: string,
-    readonly favorite_phrase: (string | null),
+    readonly favorite_phrase: string | null,
   },
   readonly parameters: Record<PropertyKey, never>,
 };
@@ -3,9 +3,9 @@ import type { Query__PetByName__parameters } from './parameters_type';
 
 export type Query__PetByName__param = {
   readonly data: {
-    readonly pet: ({
+    readonly pet: {
       readonly PetDetailDeferredRouteInnerComponent: Pet__PetDetailDeferredRouteInnerComponent__output_type,
-    } | null),
+    } | null,
   },
   readonly parameters: Query__PetByName__parameters,
 };
@@ -6,14 +6,14 @@ import type { Query__PetCheckinListRoute__parameters } from './parameters_type';
 
 export type Query__PetCheckinListRoute__param = {
   readonly data: {
-    readonly pet: ({
+    readonly pet: {
       readonly FirstCheckinMakeSuperButton: Pet__FirstCheckinMakeSuperButton__output_type,
       readonly name: string,
       readonly PetCheckinsCardList: LoadableField<
         Pet__PetCheckinsCardList__param,
         Pet__PetCheckinsCardList__output_type
       >,
-    } | null),
+    } | null,
   },
   readonly parameters: Query__PetCheckinListRoute__parameters,
 };
@@ -3,12 +3,12 @@ import type { Query__PetDetailDeferredRoute__parameters } from './parameters_typ
 
 export type Query__PetDetailDeferredRoute__param = {
   readonly data: {
-    readonly pet: ({
+    readonly pet: {
       readonly PetDetailDeferredRouteInnerComponent: Pet__PetDetailDeferredRouteInnerComponent__output_type,
-    } | null),
-    readonly topLevelField: ({
+    } | null,
+    readonly topLevelField: {
       readonly __typename: string,
-    } | null),
+    } | null,
   },
   readonly parameters: Query__PetDetailDeferredRoute__parameters,
 };
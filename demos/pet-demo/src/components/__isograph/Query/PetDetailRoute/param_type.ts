@@ -8,7 +8,7 @@ import type { Query__PetDetailRoute__parameters } from './parameters_type';
 
 export type Query__PetDetailRoute__param = {
   readonly data: {
-    readonly pet: ({
+    readonly pet: {
       readonly custom_pet_refetch: Pet__custom_pet_refetch__output_type,
       readonly name: string,
       readonly PetCheckinsCard: Pet__PetCheckinsCard__output_type,
@@ -16,7 +16,7 @@ export type Query__PetDetailRoute__param = {
       readonly PetPhraseCard: Pet__PetPhraseCard__output_type,
       readonly PetTaglineCard: Pet__PetTaglineCard__output_type,
       readonly PetStatsCard: Pet__PetStatsCard__output_type,
-    } | null),
+    } | null,
   },
   readonly parameters: Query__PetDetailRoute__parameters,
 };
@@ -7,15 +7,15 @@ export type Viewer__NewsfeedPaginationComponent__param = {
       /**
 A client pointer for the AdItem type.
       */
-      readonly asAdItem: ({
+      readonly asAdItem: {
         readonly id: string,
-      } | null),
+      } | null,
       /**
 A client pointer for the BlogItem type.
       */
-      readonly asBlogItem: ({
+      readonly asBlogItem: {
         readonly id: string,
-      } | null),
+      } | null,
       readonly NewsfeedAdOrBlog: NewsfeedItem__NewsfeedAdOrBlog__output_type,
     }>,
   },
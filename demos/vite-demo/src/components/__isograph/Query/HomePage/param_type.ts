@@ -25,7 +25,7 @@ The key of the Pokémon as stored in the API
       /**
 The form identifier of a Pokémon
       */
-      readonly forme: (string | null),
+      readonly forme: string | null,
       readonly Pokemon: Pokemon__Pokemon__output_type,
     }>,
   },